@@ -0,0 +1,96 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse::Parse, parse_macro_input, DeriveInput, Expr, LitStr, Token, Type};
+
+/// Derives `Checks` for a [`Kind`] using its `#[check(...)]` attributes.
+///
+/// Each `#[check(...)]` attribute adds one check, with the following options:
+/// - `requires(A, B, ...)`: The check fails if any of the given components are missing.
+/// - `forbids(A, B, ...)`: The check fails if any of the given components are present.
+/// - `policy = "..."`: The policy to apply if the check fails. This may be `"invalid"` (default),
+///   `"purge"`, `"panic"`, or any expression which evaluates to a `Policy`.
+///
+/// [`Kind`]: https://docs.rs/moonshine-kind
+#[proc_macro_derive(Check, attributes(check))]
+pub fn derive_check(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut checks = Vec::new();
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("check"))
+    {
+        let mut terms = Vec::new();
+        let mut policy = None;
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("requires") {
+                for ty in parse_types(&meta)? {
+                    terms.push(quote!(::moonshine_check::__private::Without<#ty>));
+                }
+                Ok(())
+            } else if meta.path.is_ident("forbids") {
+                for ty in parse_types(&meta)? {
+                    terms.push(quote!(::moonshine_check::__private::With<#ty>));
+                }
+                Ok(())
+            } else if meta.path.is_ident("policy") {
+                let value: LitStr = meta.value()?.parse()?;
+                policy = Some(parse_policy(&value)?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `requires`, `forbids`, or `policy`"))
+            }
+        })?;
+
+        let filter = match terms.len() {
+            0 => {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "expected at least one of `requires(...)` or `forbids(...)`",
+                ))
+            }
+            1 => terms.pop().unwrap(),
+            _ => quote!(::moonshine_check::__private::Or<(#(#terms,)*)>),
+        };
+        let policy = policy.unwrap_or_else(|| quote!(::moonshine_check::invalid()));
+        checks.push(quote!(app.check::<Self, #filter>(#policy);));
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::moonshine_check::Checks for #name #ty_generics #where_clause {
+            fn add_checks(app: &mut ::moonshine_check::__private::SubApp) {
+                use ::moonshine_check::Check as _;
+                #(#checks)*
+            }
+        }
+    })
+}
+
+fn parse_types(meta: &syn::meta::ParseNestedMeta) -> syn::Result<Vec<Type>> {
+    let content;
+    syn::parenthesized!(content in meta.input);
+    let types = content.parse_terminated(Type::parse, Token![,])?;
+    Ok(types.into_iter().collect())
+}
+
+fn parse_policy(value: &LitStr) -> syn::Result<TokenStream2> {
+    match value.value().as_str() {
+        "invalid" => Ok(quote!(::moonshine_check::invalid())),
+        "purge" => Ok(quote!(::moonshine_check::purge())),
+        "panic" => Ok(quote!(::moonshine_check::panic())),
+        _ => {
+            let expr: Expr = value.parse()?;
+            Ok(quote!(#expr))
+        }
+    }
+}
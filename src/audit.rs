@@ -0,0 +1,298 @@
+//! Audits of checked entities and reports of check coverage.
+
+use std::any::TypeId;
+
+use bevy_ecs::{
+    prelude::*,
+    reflect::{AppTypeRegistry, ReflectComponent},
+};
+use bevy_utils::{
+    tracing::{info, warn},
+    HashSet,
+};
+
+use crate::{check_markers, CheckError, CheckRegistry, Checked, Invalid, SkipChecks};
+
+/// Marks all checked entities to be checked again.
+pub(crate) fn audit_on_startup(world: &mut World) {
+    let entities: Vec<Entity> = world
+        .query_filtered::<Entity, Or<(With<Checked>, With<Invalid>)>>()
+        .iter(world)
+        .collect();
+    info!("auditing {} checked entities", entities.len());
+    for entity in entities {
+        world.entity_mut(entity).check_again();
+    }
+}
+
+/// The result of evaluating all registered checks against all entities.
+///
+/// See [`Audit`] for details.
+#[derive(Event, Clone, Debug, Default)]
+pub struct AuditReport {
+    pub(crate) checks: Vec<CheckAudit>,
+}
+
+impl AuditReport {
+    /// Iterates over the results of all registered checks, in order of registration.
+    pub fn iter(&self) -> impl Iterator<Item = &CheckAudit> {
+        self.checks.iter()
+    }
+
+    /// Returns the results of the first check with the given name, if it exists.
+    pub fn get(&self, name: &str) -> Option<&CheckAudit> {
+        self.iter().find(|audit| audit.check == name)
+    }
+
+    /// Returns true if no entity failed any check.
+    pub fn is_valid(&self) -> bool {
+        self.iter().all(|audit| audit.failed.is_empty())
+    }
+
+    /// Iterates over all failures, in order of registration.
+    pub fn errors(&self) -> impl Iterator<Item = CheckError> + '_ {
+        self.iter().flat_map(|audit| {
+            audit.failed.iter().map(|&entity| CheckError {
+                entity,
+                check: audit.check.clone(),
+            })
+        })
+    }
+}
+
+/// The result of evaluating a single check against all of its instances.
+#[derive(Clone, Debug, Default)]
+pub struct CheckAudit {
+    /// Name of the check.
+    pub check: String,
+    /// All instances which passed the check.
+    pub passed: Vec<Entity>,
+    /// All instances which failed the check.
+    pub failed: Vec<Entity>,
+}
+
+/// An extension trait used to audit the entire [`World`] immediately.
+pub trait Audit {
+    /// Evaluates all registered checks against every entity in the world and returns an [`AuditReport`].
+    ///
+    /// # Usage
+    ///
+    /// Like [`validate_now`](crate::ValidateNow::validate_now), this does not apply any [`Policy`](crate::Policy) or mark any
+    /// entity as checked. Entities are evaluated regardless of whether they have been checked before.
+    ///
+    /// Note that dynamic checks are not included in the audit.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Apple;
+    ///
+    /// #[derive(Component)]
+    /// struct Fresh;
+    ///
+    /// let mut app = App::new();
+    /// app.check::<Apple, Without<Fresh>>(purge());
+    ///
+    /// let entity = app.world_mut().spawn(Apple).id();
+    /// let report = app.world_mut().audit();
+    /// assert!(!report.is_valid());
+    /// assert_eq!(report.errors().next().unwrap().entity, entity);
+    /// ```
+    fn audit(&mut self) -> AuditReport;
+}
+
+impl Audit for World {
+    fn audit(&mut self) -> AuditReport {
+        if !self.contains_resource::<CheckRegistry>() {
+            return AuditReport::default();
+        }
+
+        let entities: Vec<Entity> = self.iter_entities().map(|entity| entity.id()).collect();
+        self.resource_scope(|world, registry: Mut<CheckRegistry>| {
+            let checks = registry
+                .checks
+                .iter()
+                .filter(|check| check.enabled)
+                .map(|check| {
+                    let mut audit = CheckAudit {
+                        check: check.name.clone(),
+                        ..Default::default()
+                    };
+                    for &entity in &entities {
+                        match (check.evaluate)(world, entity) {
+                            Some(true) => audit.passed.push(entity),
+                            Some(false) => audit.failed.push(entity),
+                            None => {}
+                        }
+                    }
+                    audit
+                })
+                .collect();
+            AuditReport { checks }
+        })
+    }
+}
+
+/// An extension trait used to audit the entire [`World`] from [`Commands`].
+pub trait TriggerAudit {
+    /// Audits the world when commands are applied, and triggers the resulting [`AuditReport`].
+    ///
+    /// See [`Audit`] for details.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// fn on_admin_command(mut commands: Commands) {
+    ///     commands.trigger_audit();
+    /// }
+    ///
+    /// fn on_audit(trigger: Trigger<AuditReport>) {
+    ///     for error in trigger.event().errors() {
+    ///         println!("{error}");
+    ///     }
+    /// }
+    ///
+    /// let mut app = App::new();
+    /// app.add_systems(Update, on_admin_command).add_observer(on_audit);
+    /// ```
+    fn trigger_audit(&mut self) -> &mut Self;
+}
+
+impl TriggerAudit for Commands<'_, '_> {
+    fn trigger_audit(&mut self) -> &mut Self {
+        self.queue(|world: &mut World| {
+            let report = world.audit();
+            world.trigger(report);
+        });
+        self
+    }
+}
+
+/// The result of comparing all registered component types against all registered checks.
+///
+/// See [`Coverage::check_coverage`] for details.
+#[derive(Clone, Debug, Default)]
+pub struct CoverageReport {
+    pub(crate) checked: Vec<&'static str>,
+    pub(crate) unchecked: Vec<&'static str>,
+}
+
+impl CoverageReport {
+    /// Returns the type paths of all component types with at least one registered check, sorted by path.
+    pub fn checked(&self) -> &[&'static str] {
+        &self.checked
+    }
+
+    /// Returns the type paths of all component types without any registered checks, sorted by path.
+    pub fn unchecked(&self) -> &[&'static str] {
+        &self.unchecked
+    }
+
+    /// Iterates over the type paths of all unchecked component types which start with the given prefix.
+    ///
+    /// This is useful to only audit the types of your own crate, such as `"my_game::"`.
+    pub fn unchecked_in<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = &'static str> + 'a {
+        self.unchecked
+            .iter()
+            .copied()
+            .filter(move |path| path.starts_with(prefix))
+    }
+}
+
+/// An extension trait used to find component types without any checks.
+pub trait Coverage {
+    /// Lists all component types which are registered for reflection, and whether any checks are registered for them.
+    ///
+    /// # Usage
+    ///
+    /// Teams may use this report to audit validation coverage, similar to test coverage.
+    ///
+    /// Since kinds are not registered at runtime, only component types which are registered in the [`AppTypeRegistry`]
+    /// are listed. A component type is covered if any check in the [`CheckRegistry`] uses it as its [`Kind`](moonshine_kind::prelude::Kind).
+    /// Dynamic checks, and components of this crate, are not included.
+    ///
+    /// See [`CheckPlugin::log_coverage`](crate::CheckPlugin::log_coverage) to log all unchecked types on startup.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy::reflect::TypePath;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component, Reflect)]
+    /// #[reflect(Component)]
+    /// struct Apple;
+    ///
+    /// #[derive(Component, Reflect)]
+    /// #[reflect(Component)]
+    /// struct Orange;
+    ///
+    /// #[derive(Component)]
+    /// struct Fresh;
+    ///
+    /// let mut app = App::new();
+    /// app.register_type::<Apple>()
+    ///     .register_type::<Orange>()
+    ///     .check::<Apple, Without<Fresh>>(purge());
+    ///
+    /// let report = app.world().check_coverage();
+    /// assert!(report.checked().contains(&Apple::type_path()));
+    /// assert!(report.unchecked().contains(&Orange::type_path()));
+    /// ```
+    fn check_coverage(&self) -> CoverageReport;
+}
+
+impl Coverage for World {
+    fn check_coverage(&self) -> CoverageReport {
+        let Some(type_registry) = self.get_resource::<AppTypeRegistry>() else {
+            return CoverageReport::default();
+        };
+        let kinds: HashSet<TypeId> = self
+            .get_resource::<CheckRegistry>()
+            .map(|registry| registry.iter().map(|check| check.kind).collect())
+            .unwrap_or_default();
+
+        let markers = check_markers();
+
+        let mut report = CoverageReport::default();
+        for registration in type_registry.read().iter() {
+            let type_id = registration.type_id();
+            if registration.data::<ReflectComponent>().is_none()
+                || markers.contains(&type_id)
+                || type_id == TypeId::of::<SkipChecks>()
+            {
+                continue;
+            }
+            let path = registration.type_info().type_path();
+            if kinds.contains(&type_id) {
+                report.checked.push(path);
+            } else {
+                report.unchecked.push(path);
+            }
+        }
+        report.checked.sort_unstable();
+        report.unchecked.sort_unstable();
+        report
+    }
+}
+
+/// Logs all unchecked component types which start with the given prefix.
+pub(crate) fn log_coverage(prefix: String) -> impl Fn(&World) {
+    move |world: &World| {
+        let report = world.check_coverage();
+        let unchecked: Vec<&str> = report.unchecked_in(&prefix).collect();
+        info!(
+            "{} of {} component types are checked",
+            report.checked().len(),
+            report.checked().len() + report.unchecked().len()
+        );
+        for path in unchecked {
+            warn!("{path} has no checks");
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! Limits on the time spent on checks each frame.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use bevy_ecs::prelude::*;
+
+use crate::{Checked, Invalid};
+
+/// A [`Resource`] which limits the time spent on checks each frame.
+///
+/// # Usage
+///
+/// When this resource exists, check systems stop once the total time spent on checks during the current frame
+/// exceeds the budget. Any remaining unchecked instances are checked during the next frame(s).
+///
+/// This is useful to avoid frame spikes when a large number of entities are spawned at once, such as after a load.
+///
+/// Note that since instances are not considered valid until they are checked, systems which rely on [`Valid`](crate::Valid)
+/// will not see the deferred instances until they are checked. An instance which is deferred by any check
+/// is not marked as checked, even if it passed all other checks, so it is evaluated by all checks again.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// let mut app = App::new();
+/// app.insert_resource(CheckBudget::new(Duration::from_micros(500)));
+/// ```
+#[derive(Resource, Debug)]
+pub struct CheckBudget {
+    pub(crate) limit: Duration,
+    spent: AtomicU64,
+    /// Instances which were deferred by any check during the current frame.
+    deferred: Mutex<Vec<Entity>>,
+}
+
+impl CheckBudget {
+    /// Number of instances checked between each budget test.
+    const INTERVAL: usize = 64;
+
+    /// Creates a new budget with the given time limit per frame.
+    pub fn new(limit: Duration) -> Self {
+        Self {
+            limit,
+            spent: AtomicU64::new(0),
+            deferred: Mutex::default(),
+        }
+    }
+
+    /// Returns the time limit per frame.
+    pub fn limit(&self) -> Duration {
+        self.limit
+    }
+
+    /// Returns the time spent on checks during the current frame.
+    pub fn spent(&self) -> Duration {
+        Duration::from_nanos(self.spent.load(Ordering::Relaxed))
+    }
+
+    pub(crate) fn is_exhausted(&self, elapsed: Duration) -> bool {
+        self.spent() + elapsed >= self.limit
+    }
+
+    pub(crate) fn spend(&self, elapsed: Duration) {
+        let nanos = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+        self.spent.fetch_add(nanos, Ordering::Relaxed);
+    }
+
+    pub(crate) fn defer(&self, entities: impl IntoIterator<Item = Entity>) {
+        self.deferred.lock().unwrap().extend(entities);
+    }
+
+    pub(crate) fn reset(&self) {
+        self.spent.store(0, Ordering::Relaxed);
+    }
+}
+
+/// Unmarks all instances which were deferred by any check as checked, so they're evaluated by all checks again.
+pub(crate) fn release_deferred(
+    budget: Res<CheckBudget>,
+    query: Query<(), (With<Checked>, Without<Invalid>)>,
+    mut commands: Commands,
+) {
+    for entity in budget.deferred.lock().unwrap().drain(..) {
+        if query.contains(entity) {
+            commands.entity(entity).remove::<Checked>();
+        }
+    }
+}
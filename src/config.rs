@@ -0,0 +1,816 @@
+//! Checks defined in data files, and registered at runtime using reflection.
+//!
+//! # Usage
+//!
+//! A check definition file is a [RON](https://github.com/ron-rs/ron) list of [`CheckDefinition`]s,
+//! saved with a `.checks.ron` extension:
+//!
+//! ```ron
+//! [
+//!     (
+//!         kind: "my_game::Apple",
+//!         requires: ["my_game::Fresh"],
+//!         policy: Purge,
+//!     ),
+//!     (
+//!         kind: "my_game::Crate",
+//!         forbids: ["my_game::Broken"],
+//!         policy: RepairInsertDefault("my_game::Label"),
+//!     ),
+//! ]
+//! ```
+//!
+//! Definition files are loaded as [`CheckDefinitions`] assets using [`LoadChecks::load_checks`].
+//! If the asset is modified (such as with hot reloading), all of its checks are replaced.
+//!
+//! All type paths are resolved using the [`AppTypeRegistry`] when the check first runs.
+//! All referenced types must be registered and reflect [`Component`].
+//! Any type used with [`DynamicPolicy::RepairInsertDefault`] must also reflect [`Default`].
+//!
+//! Checks may also be defined using [`ComponentId`]s directly, for components which are not known at compile time
+//! and may not be registered for reflection. See [`LoadChecks::add_component_check`].
+//!
+//! Once resolved, each check is added to the [`CheckRegistry`], so it may also be applied immediately
+//! (such as with [`spawn_checked`](crate::SpawnChecked::spawn_checked)).
+
+use std::any::TypeId;
+use std::borrow::Cow;
+use std::sync::{Arc, Mutex};
+
+use bevy_app::prelude::*;
+use bevy_asset::{
+    io::Reader, Asset, AssetApp, AssetEvent, AssetId, AssetLoader, AssetPath, AssetServer, Assets,
+    Handle, LoadContext,
+};
+use bevy_ecs::{
+    component::ComponentId,
+    prelude::*,
+    query::QueryBuilder,
+    reflect::{AppTypeRegistry, ReflectComponent},
+    world::WorldId,
+};
+use bevy_hierarchy::DespawnRecursiveExt;
+use bevy_reflect::{std_traits::ReflectDefault, PartialReflect, TypePath};
+use bevy_utils::tracing::{error, warn, Level};
+use moonshine_save::load::LoadSystem;
+use serde::Deserialize;
+
+use crate::{
+    add_check_systems, check_schedule, init, invalid, panic, purge, repair, CheckEntry,
+    CheckLedger, CheckRegistry, CheckReport, CheckSchedule, CheckStats, CheckSystems, Checked,
+    CollectPending, Evaluate, Invalid, Outcome, Policy, PolicyKind, Severity, SkipChecks,
+};
+
+/// A single check, defined in data. See [module documentation](self) for details.
+#[derive(Deserialize, Debug, Clone)]
+pub struct CheckDefinition {
+    /// Type path of the [`Component`] which identifies the checked kind.
+    pub kind: String,
+    /// Type paths of the components which must be present on every instance.
+    #[serde(default)]
+    pub requires: Vec<String>,
+    /// Type paths of the components which must not be present on any instance.
+    #[serde(default)]
+    pub forbids: Vec<String>,
+    /// The policy to apply if the check fails.
+    #[serde(default)]
+    pub policy: DynamicPolicy,
+}
+
+impl CheckDefinition {
+    /// Returns the name of this check, as it appears in logs and the [`CheckReport`].
+    pub fn name(&self) -> String {
+        format!("{}: {}", self.kind, self.description())
+    }
+
+    /// Returns a description of the conditions of this check.
+    pub fn description(&self) -> String {
+        let mut conditions = Vec::new();
+        if !self.requires.is_empty() {
+            conditions.push(format!("requires [{}]", self.requires.join(", ")));
+        }
+        if !self.forbids.is_empty() {
+            conditions.push(format!("forbids [{}]", self.forbids.join(", ")));
+        }
+        conditions.join(", ")
+    }
+}
+
+/// A policy which may be defined in data. See [`Policy`](crate::Policy) for details.
+#[derive(Deserialize, Debug, Clone, Default)]
+pub enum DynamicPolicy {
+    /// See [`invalid`](crate::invalid).
+    #[default]
+    Invalid,
+    /// See [`purge`](crate::purge).
+    Purge,
+    /// See [`panic`](crate::panic).
+    Panic,
+    /// Inserts the default value of the component with the given type path.
+    ///
+    /// See [`repair_insert_default`](crate::repair_insert_default).
+    RepairInsertDefault(String),
+}
+
+/// A single check, defined using runtime [`ComponentId`]s. See [`LoadChecks::add_component_check`].
+#[derive(Debug, Clone)]
+pub struct ComponentCheck {
+    /// The component which identifies the checked kind.
+    pub kind: ComponentId,
+    /// The components which must be present on every instance.
+    pub requires: Vec<ComponentId>,
+    /// The components which must not be present on any instance.
+    pub forbids: Vec<ComponentId>,
+    /// The policy to apply if the check fails.
+    pub policy: DynamicPolicy,
+}
+
+/// Parses a list of [`CheckDefinition`]s from a RON string.
+pub fn parse_check_definitions(
+    source: &str,
+) -> Result<Vec<CheckDefinition>, ron::error::SpannedError> {
+    ron::from_str(source)
+}
+
+/// An [`Asset`] which contains all [`CheckDefinition`]s of a single definition file.
+///
+/// See [module documentation](self) for details.
+#[derive(Asset, TypePath, Deserialize, Debug, Clone)]
+#[serde(transparent)]
+pub struct CheckDefinitions(pub Vec<CheckDefinition>);
+
+/// An [`AssetLoader`] which loads [`CheckDefinitions`] from `.checks.ron` files.
+#[derive(Default)]
+pub struct CheckDefinitionsLoader;
+
+impl AssetLoader for CheckDefinitionsLoader {
+    type Asset = CheckDefinitions;
+    type Settings = ();
+    type Error = LoadCheckDefinitionsError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<CheckDefinitions, LoadCheckDefinitionsError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["checks.ron"]
+    }
+}
+
+/// An error which may occur while loading [`CheckDefinitions`].
+#[derive(Debug)]
+pub enum LoadCheckDefinitionsError {
+    /// The definition file could not be read.
+    Io(std::io::Error),
+    /// The definition file could not be parsed.
+    Parse(ron::error::SpannedError),
+}
+
+impl std::fmt::Display for LoadCheckDefinitionsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(why) => write!(f, "failed to read check definitions: {why}"),
+            Self::Parse(why) => write!(f, "failed to parse check definitions: {why}"),
+        }
+    }
+}
+
+impl std::error::Error for LoadCheckDefinitionsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(why) => Some(why),
+            Self::Parse(why) => Some(why),
+        }
+    }
+}
+
+impl From<std::io::Error> for LoadCheckDefinitionsError {
+    fn from(why: std::io::Error) -> Self {
+        Self::Io(why)
+    }
+}
+
+impl From<ron::error::SpannedError> for LoadCheckDefinitionsError {
+    fn from(why: ron::error::SpannedError) -> Self {
+        Self::Parse(why)
+    }
+}
+
+/// A [`Plugin`] which loads [`CheckDefinitions`] assets, and adds their checks to the [`App`].
+///
+/// # Usage
+///
+/// This plugin requires the [`AssetPlugin`](bevy_asset::AssetPlugin). It is added automatically
+/// by [`load_checks`](LoadChecks::load_checks) if needed.
+pub struct CheckDefinitionsPlugin;
+
+impl Plugin for CheckDefinitionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<CheckDefinitions>()
+            .init_asset_loader::<CheckDefinitionsLoader>();
+        let app = app.main_mut();
+        init_config(app);
+        add_check_systems(
+            app,
+            check_schedule(app),
+            load_check_definitions
+                .after(LoadSystem::Load)
+                .before(resolve_config_checks),
+        );
+    }
+}
+
+/// An extension trait used to add data-driven checks to an [`App`] or [`SubApp`].
+pub trait LoadChecks {
+    /// Loads all [`CheckDefinition`]s from the definition file at the given asset path, and adds them.
+    ///
+    /// The checks are added once the asset is loaded, and replaced whenever it is modified.
+    /// Any errors while loading the file are logged by the [`AssetServer`], and no checks are added.
+    ///
+    /// # Usage
+    ///
+    /// For a [`SubApp`], the [`CheckDefinitionsPlugin`] must be added to its parent [`App`] first.
+    fn load_checks(&mut self, path: impl Into<AssetPath<'static>>) -> &mut Self;
+
+    /// Adds the given [`CheckDefinition`]s.
+    fn add_check_definitions(
+        &mut self,
+        definitions: impl IntoIterator<Item = CheckDefinition>,
+    ) -> &mut Self;
+
+    /// Adds the given [`ComponentCheck`].
+    ///
+    /// # Usage
+    ///
+    /// Unlike [`CheckDefinition`]s, the checked components are not resolved by type path, so they do not need
+    /// to be registered for reflection. This is useful for scripting layers and mods which define their own components.
+    ///
+    /// Any type used with [`DynamicPolicy::RepairInsertDefault`] must still be registered and reflect [`Default`].
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::config::{ComponentCheck, DynamicPolicy, LoadChecks};
+    ///
+    /// #[derive(Component)]
+    /// struct Apple;
+    ///
+    /// #[derive(Component)]
+    /// struct Fresh;
+    ///
+    /// let mut app = App::new();
+    /// let apple = app.world_mut().register_component::<Apple>();
+    /// let fresh = app.world_mut().register_component::<Fresh>();
+    /// app.add_component_check(ComponentCheck {
+    ///     kind: apple,
+    ///     requires: vec![fresh],
+    ///     forbids: vec![],
+    ///     policy: DynamicPolicy::Purge,
+    /// });
+    /// ```
+    fn add_component_check(&mut self, check: ComponentCheck) -> &mut Self;
+}
+
+impl LoadChecks for SubApp {
+    fn load_checks(&mut self, path: impl Into<AssetPath<'static>>) -> &mut Self {
+        assert!(
+            self.world().contains_resource::<Assets<CheckDefinitions>>(),
+            "CheckDefinitionsPlugin is required to load check definitions"
+        );
+        init_config(self);
+        let handle = self
+            .world()
+            .resource::<AssetServer>()
+            .load::<CheckDefinitions>(path);
+        self.world_mut()
+            .resource_mut::<ConfigChecks>()
+            .handles
+            .push(handle);
+        self
+    }
+
+    fn add_check_definitions(
+        &mut self,
+        definitions: impl IntoIterator<Item = CheckDefinition>,
+    ) -> &mut Self {
+        init_config(self);
+        let mut config = self.world_mut().resource_mut::<ConfigChecks>();
+        for definition in definitions {
+            config
+                .pending
+                .push((None, DynamicCheck::new(definition, None)));
+        }
+        self
+    }
+
+    fn add_component_check(&mut self, check: ComponentCheck) -> &mut Self {
+        init_config(self);
+        let world = self.world_mut();
+        let component_name = |id: ComponentId| {
+            world
+                .components()
+                .get_info(id)
+                .map_or_else(|| format!("{id:?}"), |info| info.name().to_owned())
+        };
+        let definition = CheckDefinition {
+            kind: component_name(check.kind),
+            requires: check.requires.iter().copied().map(component_name).collect(),
+            forbids: check.forbids.iter().copied().map(component_name).collect(),
+            policy: check.policy,
+        };
+        let state = match resolve_ids(
+            world,
+            &definition.policy,
+            check.kind,
+            check.requires,
+            check.forbids,
+        ) {
+            Ok(resolved) => State::Resolved(resolved),
+            Err(why) => {
+                error!("failed to resolve check '{}': {why}", definition.name());
+                State::Failed
+            }
+        };
+        let mut check = DynamicCheck::new(definition, None);
+        check.state = state;
+        world
+            .resource_mut::<ConfigChecks>()
+            .pending
+            .push((None, check));
+        self
+    }
+}
+
+impl LoadChecks for App {
+    fn load_checks(&mut self, path: impl Into<AssetPath<'static>>) -> &mut Self {
+        if !self.is_plugin_added::<CheckDefinitionsPlugin>() {
+            self.add_plugins(CheckDefinitionsPlugin);
+        }
+        self.main_mut().load_checks(path);
+        self
+    }
+
+    fn add_check_definitions(
+        &mut self,
+        definitions: impl IntoIterator<Item = CheckDefinition>,
+    ) -> &mut Self {
+        self.main_mut().add_check_definitions(definitions);
+        self
+    }
+
+    fn add_component_check(&mut self, check: ComponentCheck) -> &mut Self {
+        self.main_mut().add_component_check(check);
+        self
+    }
+}
+
+/// A [`Resource`] which contains all data-driven checks, along with the asset they were loaded from, if any.
+#[derive(Resource, Default)]
+struct ConfigChecks {
+    /// Handles of all definition files loaded using [`LoadChecks::load_checks`], which keep them loaded.
+    handles: Vec<Handle<CheckDefinitions>>,
+    pending: Vec<(Option<AssetId<CheckDefinitions>>, DynamicCheck)>,
+    removed: Vec<AssetId<CheckDefinitions>>,
+    checks: Mutex<Vec<(Option<AssetId<CheckDefinitions>>, DynamicCheck)>>,
+}
+
+fn init_config(app: &mut SubApp) {
+    init(app);
+    if app.world().contains_resource::<ConfigChecks>() {
+        return;
+    }
+    app.init_resource::<ConfigChecks>();
+    add_check_systems(
+        app,
+        check_schedule(app),
+        (
+            resolve_config_checks
+                .after(LoadSystem::Load)
+                .before(CheckSystems),
+            run_config_checks
+                .run_if(not(resource_exists::<CheckLedger>))
+                .after(LoadSystem::Load)
+                .in_set(CheckSystems),
+        ),
+    );
+}
+
+fn load_check_definitions(
+    mut events: EventReader<AssetEvent<CheckDefinitions>>,
+    assets: Res<Assets<CheckDefinitions>>,
+    mut config: ResMut<ConfigChecks>,
+) {
+    for event in events.read() {
+        match *event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => {
+                // NOTE: Only definitions loaded using `load_checks` are added.
+                if !config.handles.iter().any(|handle| handle.id() == id) {
+                    continue;
+                }
+                config.removed.push(id);
+                let Some(definitions) = assets.get(id) else {
+                    continue;
+                };
+                for definition in definitions.0.iter().cloned() {
+                    config
+                        .pending
+                        .push((Some(id), DynamicCheck::new(definition, None)));
+                }
+            }
+            AssetEvent::Removed { id } => {
+                config.removed.push(id);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn resolve_config_checks(world: &mut World) {
+    world.resource_scope(|world, mut config: Mut<ConfigChecks>| {
+        let ConfigChecks {
+            pending,
+            removed,
+            checks,
+            ..
+        } = &mut *config;
+        let checks = checks.get_mut().unwrap();
+        for id in removed.drain(..) {
+            for (_, check) in checks.iter_mut().filter(|(source, _)| *source == Some(id)) {
+                check.retire(world);
+            }
+            checks.retain(|(source, _)| *source != Some(id));
+        }
+        checks.append(pending);
+        for (_, check) in checks.iter_mut() {
+            check.resolve(world);
+        }
+    });
+}
+
+// NOTE: Data-driven checks are evaluated with all other checks, before any of them are marked as checked.
+fn run_config_checks(world: &World, mut commands: Commands) {
+    let config = world.resource::<ConfigChecks>();
+    for (_, check) in config.checks.lock().unwrap().iter_mut() {
+        check.run(world, &mut commands);
+    }
+}
+
+enum State {
+    Unresolved,
+    Resolved(Resolved),
+    /// The check could not be resolved, or it was removed.
+    Failed,
+}
+
+struct Resolved {
+    kind: ComponentId,
+    query: QueryState<Entity, (Without<Checked>, Without<SkipChecks>)>,
+    requires: Vec<ComponentId>,
+    forbids: Vec<ComponentId>,
+    repair: Option<(ReflectComponent, ReflectDefault)>,
+}
+
+/// A callback used to repair an instance which failed a [`DynamicCheck`].
+pub(crate) type DynamicFixer = Arc<dyn Fn(&mut World, Entity) + Send + Sync>;
+
+/// A check which is resolved and evaluated at runtime using reflection.
+pub(crate) struct DynamicCheck {
+    definition: CheckDefinition,
+    name: String,
+    /// Index of this check in the [`CheckReport`], once it is resolved.
+    report: Option<usize>,
+    /// Index of this check in the [`CheckRegistry`], once it is resolved.
+    entry: Option<usize>,
+    state: State,
+    fixer: Option<DynamicFixer>,
+}
+
+impl DynamicCheck {
+    /// Creates a new dynamic check.
+    ///
+    /// If a fixer is given, it is used to repair any failed instances instead of the definition policy.
+    pub(crate) fn new(definition: CheckDefinition, fixer: Option<DynamicFixer>) -> Self {
+        Self {
+            name: definition.name(),
+            definition,
+            report: None,
+            entry: None,
+            state: State::Unresolved,
+            fixer,
+        }
+    }
+
+    /// Resolves all type paths of this check, and registers it, if it is not resolved yet.
+    pub(crate) fn resolve(&mut self, world: &mut World) {
+        if self.report.is_some() {
+            return;
+        }
+
+        let mut report = world.resource_mut::<CheckReport>();
+        self.report = Some(report.checks.len());
+        report
+            .checks
+            .push((self.name.clone(), CheckStats::default()));
+
+        if let State::Unresolved = self.state {
+            self.state = match resolve(world, &self.definition) {
+                Ok(resolved) => State::Resolved(resolved),
+                Err(why) => {
+                    error!("failed to resolve check '{}': {why}", self.name);
+                    State::Failed
+                }
+            };
+        }
+
+        if let State::Resolved(resolved) = &self.state {
+            let entry = self.check_entry(world, resolved);
+            let mut registry = world.resource_mut::<CheckRegistry>();
+            self.entry = Some(registry.checks.len());
+            registry.checks.push(entry);
+        }
+    }
+
+    /// Stops evaluating this check, and disables it in the [`CheckRegistry`].
+    pub(crate) fn retire(&mut self, world: &mut World) {
+        self.state = State::Failed;
+        if let Some(entry) = self.entry {
+            world.resource_mut::<CheckRegistry>().checks[entry].enabled = false;
+        }
+    }
+
+    /// Returns the [`CheckEntry`] of this check, which allows it to be applied immediately.
+    fn check_entry(&self, world: &World, resolved: &Resolved) -> CheckEntry {
+        let kind = resolved.kind;
+        let requires = resolved.requires.clone();
+        let forbids = resolved.forbids.clone();
+        let evaluate: Evaluate = Arc::new(move |world: &mut World, entity: Entity| {
+            let entity = world
+                .get_entity(entity)
+                .ok()
+                .filter(|entity| entity.contains_id(kind))?;
+            Some(!fails(&entity, &requires, &forbids))
+        });
+
+        type PendingState = (
+            WorldId,
+            QueryState<Entity, (Without<Checked>, Without<SkipChecks>)>,
+        );
+        let state: Mutex<Option<PendingState>> = Mutex::default();
+        let pending: CollectPending =
+            Arc::new(move |world: &mut World, entities: &mut Vec<Entity>| {
+                let mut state = state.lock().unwrap();
+                if !state.as_ref().is_some_and(|(id, _)| *id == world.id()) {
+                    let query =
+                        QueryBuilder::<Entity, (Without<Checked>, Without<SkipChecks>)>::new(world)
+                            .with_id(kind)
+                            .build();
+                    *state = Some((world.id(), query));
+                }
+                let (_, query) = state.as_mut().unwrap();
+                entities.extend(query.iter(world));
+            });
+
+        let policy = self.policy(resolved);
+        CheckEntry {
+            name: self.name.clone(),
+            kind: world
+                .components()
+                .get_info(kind)
+                .and_then(|info| info.type_id())
+                .unwrap_or(TypeId::of::<CheckDefinition>()),
+            kind_name: Cow::Owned(self.definition.kind.clone()),
+            filter: TypeId::of::<CheckDefinition>(),
+            filter_name: Cow::Owned(self.definition.description()),
+            condition: Some(Arc::as_ptr(&evaluate) as *const () as usize),
+            policy: PolicyKind::of(&policy),
+            labels: Vec::new(),
+            group: None,
+            schedule: world
+                .get_resource::<CheckSchedule>()
+                .map_or(PreUpdate.intern(), |schedule| schedule.0),
+            enabled: true,
+            severity: Severity::Error,
+            evaluate,
+            action: Some(policy),
+            skip: |_, _| false,
+            pending,
+            gate: None,
+            description: self.definition.description(),
+            log_level: Some(Level::ERROR),
+            formatter: None,
+            lenient: false,
+        }
+    }
+
+    /// Returns the [`Policy`] equivalent of this check.
+    fn policy(&self, resolved: &Resolved) -> Policy {
+        if let Some(fixer) = &self.fixer {
+            let fixer = fixer.clone();
+            return repair(move |entity: EntityRef, commands: &mut Commands| {
+                let fixer = fixer.clone();
+                let entity = entity.id();
+                commands.queue(move |world: &mut World| fixer(world, entity));
+            });
+        }
+
+        match &self.definition.policy {
+            DynamicPolicy::Invalid => invalid(),
+            DynamicPolicy::Purge => purge(),
+            DynamicPolicy::Panic => panic(),
+            DynamicPolicy::RepairInsertDefault(_) => {
+                let (reflect_component, reflect_default) = resolved.repair.clone().unwrap();
+                repair(move |entity: EntityRef, commands: &mut Commands| {
+                    let reflect_component = reflect_component.clone();
+                    let reflect_default = reflect_default.clone();
+                    let entity = entity.id();
+                    commands.queue(move |world: &mut World| {
+                        insert_default(world, entity, &reflect_component, &reflect_default);
+                    });
+                })
+            }
+        }
+    }
+
+    /// Evaluates this check against all unchecked instances, and queues its policy for any failed instances.
+    ///
+    /// The check must be [resolved](Self::resolve) first.
+    pub(crate) fn run(&mut self, world: &World, commands: &mut Commands) {
+        let Self {
+            definition,
+            name,
+            report,
+            entry,
+            state,
+            fixer,
+        } = self;
+
+        let (State::Resolved(resolved), Some(report)) = (state, *report) else {
+            return;
+        };
+
+        if entry.is_some_and(|entry| !world.resource::<CheckRegistry>().checks[entry].enabled) {
+            return;
+        }
+
+        let mut stats = CheckStats::default();
+        for entity in resolved.query.iter(world) {
+            let entity_ref = world.entity(entity);
+            if !fails(&entity_ref, &resolved.requires, &resolved.forbids) {
+                commands.entity(entity).try_insert(Checked);
+                stats.record(Outcome::Valid);
+                continue;
+            }
+
+            if let Some(fixer) = fixer {
+                error!("{entity} is invalid: {name}");
+                // Insert `Checked` before fixing to let the fixer remove it if needed
+                commands.entity(entity).try_insert(Checked);
+                let fixer = fixer.clone();
+                commands.queue(move |world: &mut World| {
+                    if world.get_entity(entity).is_ok() {
+                        fixer(world, entity);
+                    }
+                });
+                warn!("{entity} was repaired.");
+                stats.record(Outcome::Repaired);
+                continue;
+            }
+
+            match &definition.policy {
+                DynamicPolicy::Invalid => {
+                    error!("{entity} is invalid: {name}");
+                    let invalid = Invalid::new(name.as_str(), world);
+                    commands.entity(entity).try_insert((Checked, invalid));
+                    stats.record(Outcome::Invalid);
+                }
+                DynamicPolicy::Purge => {
+                    error!("{entity} is purged: {name}");
+                    commands.entity(entity).despawn_recursive();
+                    stats.record(Outcome::Purged);
+                }
+                DynamicPolicy::Panic => {
+                    panic!("{entity} is strictly invalid: {name}");
+                }
+                DynamicPolicy::RepairInsertDefault(_) => {
+                    error!("{entity} is invalid: {name}");
+                    let (reflect_component, reflect_default) = resolved.repair.clone().unwrap();
+                    commands.entity(entity).try_insert(Checked);
+                    commands.queue(move |world: &mut World| {
+                        insert_default(world, entity, &reflect_component, &reflect_default);
+                    });
+                    warn!("{entity} was repaired.");
+                    stats.record(Outcome::Repaired);
+                }
+            }
+        }
+
+        if stats.checked > 0 {
+            commands.queue(move |world: &mut World| {
+                world.resource_mut::<CheckReport>().checks[report].1 = stats;
+            });
+        }
+    }
+}
+
+/// Returns true if the given entity fails a check with the given required and forbidden components.
+fn fails(entity: &EntityRef, requires: &[ComponentId], forbids: &[ComponentId]) -> bool {
+    requires.iter().any(|id| !entity.contains_id(*id))
+        || forbids.iter().any(|id| entity.contains_id(*id))
+}
+
+/// Inserts the default value of a reflected component into the given entity, if it exists.
+fn insert_default(
+    world: &mut World,
+    entity: Entity,
+    reflect_component: &ReflectComponent,
+    reflect_default: &ReflectDefault,
+) {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let component = reflect_default.default();
+    let Ok(mut entity_mut) = world.get_entity_mut(entity) else {
+        return;
+    };
+    reflect_component.insert(&mut entity_mut, component.as_partial_reflect(), &registry);
+}
+
+fn resolve(world: &mut World, definition: &CheckDefinition) -> Result<Resolved, String> {
+    let registry = world
+        .get_resource::<AppTypeRegistry>()
+        .ok_or("type registry does not exist")?
+        .clone();
+    let registry = registry.read();
+
+    let mut component_id = |path: &str| -> Result<ComponentId, String> {
+        let registration = registry
+            .get_with_type_path(path)
+            .ok_or_else(|| format!("type '{path}' is not registered"))?;
+        let reflect_component = registration
+            .data::<ReflectComponent>()
+            .ok_or_else(|| format!("type '{path}' does not reflect Component"))?;
+        Ok(reflect_component.register_component(world))
+    };
+
+    let kind = component_id(&definition.kind)?;
+    let requires = definition
+        .requires
+        .iter()
+        .map(|path| component_id(path))
+        .collect::<Result<Vec<_>, _>>()?;
+    let forbids = definition
+        .forbids
+        .iter()
+        .map(|path| component_id(path))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    drop(registry);
+    resolve_ids(world, &definition.policy, kind, requires, forbids)
+}
+
+fn resolve_ids(
+    world: &mut World,
+    policy: &DynamicPolicy,
+    kind: ComponentId,
+    requires: Vec<ComponentId>,
+    forbids: Vec<ComponentId>,
+) -> Result<Resolved, String> {
+    let repair = match policy {
+        DynamicPolicy::RepairInsertDefault(path) => {
+            let registry = world
+                .get_resource::<AppTypeRegistry>()
+                .ok_or("type registry does not exist")?
+                .clone();
+            let registry = registry.read();
+            let registration = registry
+                .get_with_type_path(path)
+                .ok_or_else(|| format!("type '{path}' is not registered"))?;
+            let reflect_component = registration
+                .data::<ReflectComponent>()
+                .ok_or_else(|| format!("type '{path}' does not reflect Component"))?;
+            let reflect_default = registration
+                .data::<ReflectDefault>()
+                .ok_or_else(|| format!("type '{path}' does not reflect Default"))?;
+            Some((reflect_component.clone(), reflect_default.clone()))
+        }
+        _ => None,
+    };
+
+    let query = QueryBuilder::<Entity, (Without<Checked>, Without<SkipChecks>)>::new(world)
+        .with_id(kind)
+        .build();
+
+    Ok(Resolved {
+        kind,
+        query,
+        requires,
+        forbids,
+        repair,
+    })
+}
@@ -0,0 +1,45 @@
+//! Evaluation of checks without applying their policies.
+
+use bevy_ecs::prelude::*;
+
+/// A [`Resource`] which, when present, prevents all checks from modifying the world.
+///
+/// # Usage
+///
+/// In dry-run mode, checks are evaluated as usual, but policies are not applied. Instead, the outcome
+/// which *would* have been applied is logged, counted in the [`CheckReport`](crate::CheckReport), and sent to any [`SnapshotHook`](crate::SnapshotHook)s.
+///
+/// Use this to safely preview the impact of new checks on existing save data.
+///
+/// Note that instances are not marked as checked in dry-run mode, so they are evaluated again every frame.
+/// However, each instance is only reported once. Instances which are already checked are not checked again,
+/// and any [delayed purges](crate::purge_after) are suspended. [Count checks](crate::Check::check_count) and
+/// [escalations](crate::Check::escalate_invalid) are reported, but their policies are not applied.
+///
+/// Statically dispatched, data-driven and scripted checks are not affected.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Apple;
+///
+/// #[derive(Component)]
+/// struct Fresh;
+///
+/// let mut app = App::new();
+/// app.add_plugins(MinimalPlugins)
+///     .init_resource::<DryRun>()
+///     .check::<Apple, Without<Fresh>>(purge());
+///
+/// let entity = app.world_mut().spawn(Apple).id();
+/// app.update();
+///
+/// // Not purged:
+/// assert!(app.world().get_entity(entity).is_ok());
+/// assert_eq!(app.world().resource::<CheckReport>().total().purged, 1);
+/// ```
+#[derive(Resource, Clone, Copy, Debug, Default)]
+pub struct DryRun;
@@ -0,0 +1,97 @@
+//! A debug panel which lists all invalid entities, using [`bevy_egui`].
+
+use bevy_app::prelude::*;
+use bevy_core::Name;
+use bevy_ecs::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use bevy_hierarchy::DespawnRecursiveExt;
+use bevy_time::Time;
+
+use crate::{CheckAgain, Invalid};
+
+/// A [`Plugin`] which shows a debug window listing all invalid entities.
+///
+/// For each invalid entity, the window shows the check which invalidated it, the time since it was invalidated,
+/// and buttons to purge it, check it again, or select it. See [`SelectedInvalid`].
+///
+/// This plugin requires [`EguiPlugin`](bevy_egui::EguiPlugin).
+pub struct CheckPanelPlugin;
+
+impl Plugin for CheckPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SelectedInvalid>()
+            .init_resource::<CheckPanel>()
+            .add_systems(
+                Update,
+                show_check_panel.run_if(|panel: Res<CheckPanel>| panel.open),
+            );
+    }
+}
+
+/// A [`Resource`] used to control the visibility of the check panel.
+#[derive(Resource)]
+pub struct CheckPanel {
+    pub open: bool,
+}
+
+impl Default for CheckPanel {
+    fn default() -> Self {
+        Self { open: true }
+    }
+}
+
+/// A [`Resource`] which contains the invalid entity most recently selected in the check panel.
+#[derive(Resource, Default)]
+pub struct SelectedInvalid(pub Option<Entity>);
+
+fn show_check_panel(
+    mut contexts: EguiContexts,
+    query: Query<(Entity, &Invalid, Option<&Name>)>,
+    time: Res<Time>,
+    mut panel: ResMut<CheckPanel>,
+    mut selected: ResMut<SelectedInvalid>,
+    mut commands: Commands,
+) {
+    let mut open = panel.open;
+    egui::Window::new("Invalid Entities")
+        .open(&mut open)
+        .show(contexts.ctx_mut(), |ui| {
+            if query.is_empty() {
+                ui.label("No invalid entities.");
+                return;
+            }
+
+            egui::Grid::new("invalid_entities")
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong("Entity");
+                    ui.strong("Check");
+                    ui.strong("Since");
+                    ui.end_row();
+
+                    for (entity, invalid, name) in query.iter() {
+                        match name {
+                            Some(name) => ui.label(format!("{name} ({entity})")),
+                            None => ui.label(entity.to_string()),
+                        };
+                        ui.label(&invalid.reason);
+                        let since = time.elapsed().saturating_sub(invalid.time);
+                        ui.label(format!("{:.1}s", since.as_secs_f32()));
+                        if ui.button("Purge").clicked() {
+                            commands.entity(entity).despawn_recursive();
+                        }
+                        if ui.button("Check Again").clicked() {
+                            commands.entity(entity).check_again();
+                        }
+                        if ui
+                            .selectable_label(selected.0 == Some(entity), "Select")
+                            .clicked()
+                        {
+                            selected.0 = Some(entity);
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+    panel.open = open;
+}
@@ -0,0 +1,101 @@
+//! Formatting and reporting of check failures.
+
+use std::sync::Arc;
+
+use bevy_ecs::prelude::*;
+use bevy_reflect::TypeRegistry;
+
+use crate::{dump_components, Outcome, Severity};
+
+pub(crate) type FormatFn = Arc<dyn Fn(&FailureInfo) -> String + Send + Sync>;
+
+/// Information about a failed [`Check`](crate::Check) used to describe the failure. See [`CheckFormatter`].
+pub struct FailureInfo<'a> {
+    /// The instance which failed the check.
+    pub entity: EntityRef<'a>,
+    /// The world which contains the instance.
+    pub world: &'a World,
+    /// The type registry of the world, or an empty registry if it doesn't exist.
+    pub registry: &'a TypeRegistry,
+    /// Name of the failed check.
+    pub check: &'a str,
+    /// Name of the check filter.
+    pub filter: &'a str,
+}
+
+impl FailureInfo<'_> {
+    /// Returns a description of all components of the instance, including their values if reflected.
+    pub fn dump(&self) -> String {
+        dump_components(self.entity, self.world, self.registry)
+    }
+}
+
+/// A [`Resource`] used to describe failures of all checks which don't have their own formatter.
+///
+/// By default, a failure is described by the name of the check filter.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// let mut app = App::new();
+/// app.insert_resource(CheckFormatter::new(|failure| {
+///     format!("{} {}", failure.filter, failure.dump())
+/// }));
+/// ```
+#[derive(Resource)]
+pub struct CheckFormatter(pub(crate) FormatFn);
+
+impl CheckFormatter {
+    pub fn new(f: impl Fn(&FailureInfo) -> String + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+}
+
+/// A [`Resource`] which contains a callback invoked for every failed [`Check`](crate::Check).
+///
+/// # Usage
+///
+/// This is useful to forward validation failures to external analytics or crash reporting services.
+/// The callback is invoked before the policy is applied, from within the check systems, which may run in parallel.
+///
+/// Note that statically dispatched and dynamic checks do not invoke this callback.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// let mut app = App::new();
+/// app.insert_resource(OnCheckFailure::new(|failure| {
+///     println!("{} failed {}: {}", failure.entity, failure.check, failure.reason);
+/// }));
+/// ```
+#[derive(Resource)]
+pub struct OnCheckFailure(pub(crate) Box<dyn Fn(&CheckFailure) + Send + Sync>);
+
+impl OnCheckFailure {
+    pub fn new(f: impl Fn(&CheckFailure) + Send + Sync + 'static) -> Self {
+        Self(Box::new(f))
+    }
+}
+
+/// A single failed [`Check`](crate::Check), as passed to [`OnCheckFailure`].
+#[derive(Clone, Debug)]
+pub struct CheckFailure {
+    /// The instance which failed the check.
+    pub entity: Entity,
+    /// Name of the checked kind.
+    pub kind: String,
+    /// Name of the failed check.
+    pub check: String,
+    /// Description of the failure.
+    pub reason: String,
+    /// Severity of the failed check.
+    pub severity: Severity,
+    /// The expected outcome of the check policy, or `None` if it panics.
+    ///
+    /// Note that if a repair fails, the actual outcome is determined by its fallback policy.
+    pub outcome: Option<Outcome>,
+}
@@ -0,0 +1,908 @@
+//! Repairs applied to instances which fail a check.
+
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use bevy_ecs::{
+    component::ComponentId,
+    prelude::*,
+    reflect::AppTypeRegistry,
+    system::{SystemParam, SystemParamItem, SystemState},
+    world::DeferredWorld,
+};
+use bevy_hierarchy::{BuildChildren, Parent};
+use bevy_reflect::{PartialReflect, Struct};
+use bevy_utils::{
+    tracing::{error, warn},
+    HashMap,
+};
+use moonshine_kind::prelude::*;
+
+use crate::{
+    count_children, dangling_components, invalid, template_components, with_registry,
+    ExclusiveComponents, Policy,
+};
+
+/// A fixer to be used with a [`Policy::Repair`] to try and fix an invalid instance.
+///
+/// Cloning a fixer is cheap, as all clones share the same underlying [`Fix`].
+#[derive(Clone)]
+pub struct Fixer(pub(crate) Arc<dyn Fix>, Option<Arc<Policy>>);
+
+impl Fixer {
+    pub fn new(f: impl Fix) -> Self {
+        Self(Arc::new(f), None)
+    }
+
+    /// Creates a new fixer which applies the given fallback [`Policy`] if the repair fails.
+    pub fn with_fallback(f: impl Fix, fallback: Policy) -> Self {
+        Self(Arc::new(f), Some(Arc::new(fallback)))
+    }
+
+    pub fn fix(&self, entity: EntityRef, commands: &mut Commands) {
+        self.0.fix(entity, commands)
+    }
+
+    /// See [`Fix::try_fix`].
+    pub fn try_fix(&self, entity: EntityRef, commands: &mut Commands) -> Result<(), FixError> {
+        self.0.try_fix(entity, commands)
+    }
+
+    /// Returns the [`Policy`] to apply if the repair fails, if any.
+    ///
+    /// By default, instances which can't be repaired are marked as [`invalid`].
+    pub fn fallback(&self) -> Option<&Policy> {
+        self.1.as_deref()
+    }
+
+    /// Returns true if both fixers are clones of the same fixer.
+    pub(crate) fn ptr_eq(&self, other: &Fixer) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+            && match (&self.1, &other.1) {
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                (None, None) => true,
+                _ => false,
+            }
+    }
+}
+
+pub trait Fix: 'static + Send + Sync {
+    fn fix(&self, entity: EntityRef, commands: &mut Commands);
+
+    /// Tries to repair the given `entity`, and returns an error if it can't be repaired.
+    ///
+    /// By default, this calls [`fix`](Fix::fix) and always succeeds.
+    fn try_fix(&self, entity: EntityRef, commands: &mut Commands) -> Result<(), FixError> {
+        self.fix(entity, commands);
+        Ok(())
+    }
+}
+
+/// An error returned by [`Fix::try_fix`] if an instance can't be repaired.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FixError(pub(crate) String);
+
+impl FixError {
+    pub fn new(reason: impl Into<String>) -> Self {
+        Self(reason.into())
+    }
+
+    /// Returns the reason why the instance could not be repaired.
+    pub fn reason(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for FixError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for FixError {}
+
+impl From<String> for FixError {
+    fn from(reason: String) -> Self {
+        Self(reason)
+    }
+}
+
+impl From<&str> for FixError {
+    fn from(reason: &str) -> Self {
+        Self(reason.to_owned())
+    }
+}
+
+/// An [`Event`] sent when an instance could not be repaired. See [`try_repair`].
+#[derive(Event, Clone, Debug)]
+pub struct RepairFailed {
+    /// The instance which could not be repaired.
+    pub entity: Entity,
+    /// Name of the failed check.
+    pub check: String,
+    /// The error returned by the fixer.
+    pub error: FixError,
+}
+
+struct TryFix<F>(pub(crate) F);
+
+impl<F> Fix for TryFix<F>
+where
+    F: 'static + Send + Sync + Fn(EntityRef, &mut Commands) -> Result<(), FixError>,
+{
+    fn fix(&self, entity: EntityRef, commands: &mut Commands) {
+        if let Err(error) = (self.0)(entity, commands) {
+            error!("{} could not be repaired: {error}", entity.id());
+        }
+    }
+
+    fn try_fix(&self, entity: EntityRef, commands: &mut Commands) -> Result<(), FixError> {
+        (self.0)(entity, commands)
+    }
+}
+
+impl<F: Fn(EntityRef, &mut Commands)> Fix for F
+where
+    F: 'static + Send + Sync,
+{
+    fn fix(&self, entity: EntityRef, commands: &mut Commands) {
+        self(entity, commands)
+    }
+}
+
+/// A fixer which requires some [`SystemParam`] to repair an invalid instance.
+///
+/// This is implemented for all functions which take an [`Entity`] and a [`SystemParam`].
+/// Like [`SystemParamFunction`](bevy_ecs::system::SystemParamFunction), the `Marker` is only used to
+/// infer the parameter type, and should never be specified. See [`repair_with_param`] for details.
+pub trait FixWithParam<Marker>: 'static + Send + Sync {
+    /// The [`SystemParam`] required by this fixer.
+    type Param: SystemParam + 'static;
+
+    fn fix(&self, entity: Entity, param: SystemParamItem<Self::Param>);
+}
+
+impl<P, F> FixWithParam<fn(P)> for F
+where
+    P: SystemParam + 'static,
+    F: 'static + Send + Sync,
+    for<'a> &'a F: Fn(Entity, P) + Fn(Entity, SystemParamItem<P>),
+{
+    type Param = P;
+
+    fn fix(&self, entity: Entity, param: SystemParamItem<P>) {
+        // NOTE: Call through a generic function to help the compiler resolve the parameter lifetimes.
+        fn call<P>(f: impl Fn(Entity, P), entity: Entity, param: P) {
+            f(entity, param)
+        }
+        call(self, entity, param)
+    }
+}
+
+/// Returns a [`Policy`] which tries to repair matching instances.
+///
+/// # Usage
+///
+/// Use this policy if the matching instances can be repaired by inserting or removing components.
+/// This is especially useful to handle backwards compatibility when loading from saved data.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// #[derive(Bundle, Default)]
+/// struct AB {
+///    a: A,
+///    b: B,
+/// }
+///
+/// #[derive(Component, Default)]
+/// struct A;
+///
+/// #[derive(Component, Default)]
+/// struct B;
+///
+/// let mut app = App::new();
+/// app.add_plugins(MinimalPlugins)
+///     .check::<A, Without<B>>(repair(|entity: EntityRef, commands: &mut Commands| {
+///         commands.entity(entity.id()).insert(B);
+///     }));
+///
+/// app.world_mut().spawn(A); // Bug! `B` is missing!
+/// app.update();
+///
+/// fn update(items: Query<Entity, With<A>>, query: Query<&B>) {
+///     for entity in items.iter() {
+///         // Guaranteed:
+///         assert!(query.contains(entity));
+///     }
+/// }
+pub fn repair(f: impl Fix) -> Policy {
+    Policy::Repair(Fixer::new(f))
+}
+
+/// Returns a [`Policy`] which tries to repair an invalid instance, or applies a fallback [`Policy`] if it fails.
+///
+/// # Usage
+///
+/// Use this for repairs which may not always be possible. If the fixer returns an error, it is logged,
+/// sent as a [`RepairFailed`] event, and counted in the [`CheckReport`](crate::CheckReport). The fallback policy is then applied
+/// to the instance instead.
+///
+/// Note that any commands issued by the fixer before it fails are still applied.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Player;
+///
+/// #[derive(Component, Clone)]
+/// struct Weapon;
+///
+/// #[derive(Component)]
+/// struct Holster(Option<Weapon>);
+///
+/// let mut app = App::new();
+/// app.check::<Player, Without<Weapon>>(try_repair(
+///     |entity: EntityRef, commands: &mut Commands| {
+///         let Some(weapon) = entity.get::<Holster>().and_then(|holster| holster.0.clone()) else {
+///             return Err("Holster is empty".into());
+///         };
+///         commands.entity(entity.id()).insert(weapon);
+///         Ok(())
+///     },
+///     purge(),
+/// ));
+/// ```
+pub fn try_repair(
+    f: impl Fn(EntityRef, &mut Commands) -> Result<(), FixError> + Send + Sync + 'static,
+    fallback: Policy,
+) -> Policy {
+    Policy::Repair(Fixer::with_fallback(TryFix(f), fallback))
+}
+
+/// Returns a [`Policy`] which repairs an invalid instance with exclusive [`World`] access.
+///
+/// # Usage
+///
+/// The given function is applied as a deferred command, after all commands of the check are applied.
+/// Use this for repairs which need more than [`Commands`], such as spawning entire hierarchies,
+/// modifying resources, or running queries.
+///
+/// The function is not invoked if the instance is despawned before the repair is applied.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Player;
+///
+/// #[derive(Component)]
+/// struct Inventory;
+///
+/// let mut app = App::new();
+/// app.check::<Player, Without<Children>>(repair_world(|entity: Entity, world: &mut World| {
+///     let inventory = world.spawn(Inventory).id();
+///     world.entity_mut(entity).add_child(inventory);
+/// }));
+/// ```
+pub fn repair_world(f: impl Fn(Entity, &mut World) + Send + Sync + 'static) -> Policy {
+    let f = Arc::new(f);
+    repair(move |entity: EntityRef, commands: &mut Commands| {
+        let f = f.clone();
+        let entity = entity.id();
+        commands.queue(move |world: &mut World| {
+            if world.get_entity(entity).is_ok() {
+                f(entity, world);
+            }
+        });
+    })
+}
+
+/// Returns a [`Policy`] which repairs an invalid instance using some [`SystemParam`].
+///
+/// # Usage
+///
+/// Use this for repairs which depend on resources, assets, or other entities, such as when default values
+/// are defined in some game configuration rather than in code.
+///
+/// Like [`repair_world`], the fixer is applied as a deferred command. Any commands issued by the fixer are
+/// applied immediately after.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Player;
+///
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// #[derive(Resource)]
+/// struct GameConfig {
+///     max_health: u32,
+/// }
+///
+/// fn insert_health(entity: Entity, (config, mut commands): (Res<GameConfig>, Commands)) {
+///     commands.entity(entity).insert(Health(config.max_health));
+/// }
+///
+/// let mut app = App::new();
+/// app.check::<Player, Without<Health>>(repair_with_param(insert_health));
+/// ```
+pub fn repair_with_param<M, F: FixWithParam<M>>(f: F) -> Policy {
+    let f = Arc::new(f);
+    let state: Arc<Mutex<Option<SystemState<F::Param>>>> = Default::default();
+    repair(move |entity: EntityRef, commands: &mut Commands| {
+        let f = f.clone();
+        let state = state.clone();
+        let entity = entity.id();
+        commands.queue(move |world: &mut World| {
+            if world.get_entity(entity).is_err() {
+                return;
+            }
+            let mut state = state.lock().unwrap();
+            let state = state.get_or_insert_with(|| SystemState::new(world));
+            f.fix(entity, state.get_mut(world));
+            state.apply(world);
+        });
+    })
+}
+
+/// Returns a [`Policy`] which repairs an invalid instance in multiple steps, across successive check passes.
+///
+/// # Usage
+///
+/// Each time an instance fails the check, the next step is applied and the instance is checked again
+/// during the next pass. The instance is marked as checked as soon as it passes the check. If the instance
+/// still fails the check after the final step, it is marked as [`invalid`].
+///
+/// Progress is reset once the instance is checked, so if it fails the check again later, the pipeline
+/// starts over from the first step.
+///
+/// Use this for complex migrations which can't be done with a single batch of commands.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Player;
+///
+/// #[derive(Component)]
+/// struct Legacy;
+///
+/// #[derive(Component, Default)]
+/// struct Stamina(f32);
+///
+/// let mut app = App::new();
+/// app.check::<Player, Or<(With<Legacy>, Without<Stamina>)>>(repair_pipeline([
+///     Fixer::new(|entity: EntityRef, commands: &mut Commands| {
+///         commands.entity(entity.id()).remove::<Legacy>();
+///     }),
+///     Fixer::new(|entity: EntityRef, commands: &mut Commands| {
+///         commands.entity(entity.id()).insert(Stamina::default());
+///     }),
+/// ]));
+/// ```
+pub fn repair_pipeline(steps: impl IntoIterator<Item = Fixer>) -> Policy {
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let steps: Vec<Fixer> = steps.into_iter().collect();
+    try_repair(
+        move |entity: EntityRef, commands: &mut Commands| {
+            let step = entity
+                .get::<RepairProgress>()
+                .and_then(|progress| progress.0.get(&id))
+                .copied()
+                .unwrap_or_default();
+            let Some(fixer) = steps.get(step) else {
+                return Err(FixError::new(format!(
+                    "all {} steps were applied",
+                    steps.len()
+                )));
+            };
+            fixer.try_fix(entity, commands)?;
+            // NOTE: Progress is recorded after `Checked` is inserted by the repair, which resets it.
+            commands
+                .entity(entity.id())
+                .queue(move |entity: Entity, world: &mut World| {
+                    if let Ok(mut entity) = world.get_entity_mut(entity) {
+                        entity
+                            .entry::<RepairProgress>()
+                            .or_default()
+                            .0
+                            .insert(id, step + 1);
+                    }
+                })
+                .check_again();
+            Ok(())
+        },
+        invalid(),
+    )
+}
+
+/// Returns a [`Policy`] which moves an invalid instance under the given fallback parent.
+///
+/// This is typically used with [`check_parent`](crate::Check::check_parent).
+pub fn repair_reparent(fallback: Entity) -> Policy {
+    repair(move |entity: EntityRef, commands: &mut Commands| {
+        commands.entity(entity.id()).set_parent(fallback);
+    })
+}
+
+/// Returns a [`Policy`] which spawns children with the given bundle until an invalid instance has
+/// at least `min_count` children with component `C`.
+///
+/// This is typically used with [`check_children`](crate::Check::check_children).
+pub fn repair_spawn_children<C: Component, B: Bundle + Clone>(
+    min_count: usize,
+    bundle: B,
+) -> Policy {
+    repair(move |entity: EntityRef, commands: &mut Commands| {
+        let bundle = bundle.clone();
+        commands
+            .entity(entity.id())
+            .queue(move |entity: Entity, world: &mut World| {
+                // NOTE: The entity may have been despawned before this command is applied.
+                let Ok(entity_ref) = world.get_entity(entity) else {
+                    return;
+                };
+                let count = count_children::<C>(entity_ref, world);
+                for _ in count..min_count {
+                    world.spawn(bundle.clone()).set_parent(entity);
+                }
+            });
+    })
+}
+
+/// Returns a [`Policy`] which removes the [`Parent`] of an invalid instance.
+///
+/// This is typically used with [`check_dangling_parents`](crate::Check::check_dangling_parents).
+pub fn repair_orphan() -> Policy {
+    repair(|entity: EntityRef, commands: &mut Commands| {
+        // NOTE: Remove `Parent` directly, since the parent may not exist.
+        commands.entity(entity.id()).remove::<Parent>();
+    })
+}
+
+/// Returns a [`Policy`] which removes all [`ExclusiveComponents`] from an invalid instance,
+/// except the one with the highest priority.
+///
+/// This is typically used with [`check_exclusive`](crate::Check::check_exclusive).
+pub fn repair_exclusive<E: ExclusiveComponents>() -> Policy {
+    repair(|entity: EntityRef, commands: &mut Commands| {
+        E::retain_first(entity, &mut commands.entity(entity.id()));
+    })
+}
+
+/// Returns a [`Policy`] which replaces component `A` of an invalid instance with a new value
+/// derived from the current values of `A` and `B`.
+///
+/// This is typically used with [`check_pair`](crate::Check::check_pair).
+pub fn repair_derive<A: Component, B: Component>(
+    f: impl Fn(&A, &B) -> A + Send + Sync + 'static,
+) -> Policy {
+    repair(move |entity: EntityRef, commands: &mut Commands| {
+        let (Some(a), Some(b)) = (entity.get::<A>(), entity.get::<B>()) else {
+            return;
+        };
+        commands.entity(entity.id()).insert(f(a, b));
+    })
+}
+
+/// Returns a [`Policy`] which clamps component `A` of an invalid instance to an upper bound
+/// derived from `B`.
+///
+/// This is typically used with [`check_pair`](crate::Check::check_pair).
+pub fn repair_clamp<A: Component + Clone + PartialOrd, B: Component>(
+    max: impl Fn(&B) -> A + Send + Sync + 'static,
+) -> Policy {
+    repair_derive::<A, B>(move |a, b| {
+        let max = max(b);
+        if *a > max {
+            max
+        } else {
+            a.clone()
+        }
+    })
+}
+
+/// Returns a [`Policy`] which removes all components of an invalid instance which reference an entity that doesn't exist.
+///
+/// This is typically used with [`check_entity_refs`](crate::Check::check_entity_refs).
+pub fn repair_remove_dangling() -> Policy {
+    repair(|entity: EntityRef, commands: &mut Commands| {
+        commands
+            .entity(entity.id())
+            .queue(|entity: Entity, world: &mut World| {
+                let Ok(entity_ref) = world.get_entity(entity) else {
+                    return;
+                };
+                let components = with_registry(world, |registry| {
+                    dangling_components(entity_ref, world, registry)
+                });
+                let Ok(mut entity) = world.get_entity_mut(entity) else {
+                    return;
+                };
+                for id in components {
+                    entity.remove_by_id(id);
+                }
+            });
+    })
+}
+
+/// Returns a [`Policy`] which clones any missing components of an invalid instance from the template of its kind.
+///
+/// # Usage
+///
+/// The template is the first entity with a [`Template<T>`] component. Use this to repair instances into the
+/// canonical state of their kind, rather than into some default state.
+///
+/// Only components which are registered and reflect [`Component`] are cloned. Hierarchy components and the
+/// [`Template<T>`] component itself are never cloned. If there is no template, nothing is cloned.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Goblin;
+///
+/// #[derive(Component, Reflect)]
+/// #[reflect(Component)]
+/// struct Health(u32);
+///
+/// let mut app = App::new();
+/// app.register_type::<Health>()
+///     .check::<Goblin, Without<Health>>(repair_clone_from::<Goblin>());
+///
+/// app.world_mut().spawn((Template::<Goblin>::default(), Health(50)));
+/// ```
+pub fn repair_clone_from<T: Kind>() -> Policy {
+    repair(|entity: EntityRef, commands: &mut Commands| {
+        commands
+            .entity(entity.id())
+            .queue(|entity: Entity, world: &mut World| {
+                let Some(template) = world
+                    .query_filtered::<Entity, With<Template<T>>>()
+                    .iter(world)
+                    .next()
+                else {
+                    warn!("{entity} cannot be repaired: Template is missing");
+                    return;
+                };
+                let Some(registry) = world.get_resource::<AppTypeRegistry>().cloned() else {
+                    return;
+                };
+                let registry = registry.read();
+                let Ok(entity_ref) = world.get_entity(entity) else {
+                    return;
+                };
+                let components =
+                    template_components::<T>(world.entity(template), entity_ref, world, &registry);
+                let Ok(mut entity) = world.get_entity_mut(entity) else {
+                    return;
+                };
+                for (reflect_component, component) in components {
+                    reflect_component.insert(
+                        &mut entity,
+                        component.as_partial_reflect(),
+                        &registry,
+                    );
+                }
+            });
+    })
+}
+
+/// Returns a [`Policy`] which modifies component `C` of an invalid instance with the given function.
+///
+/// This is typically used with [`check_instance_field`](crate::Check::check_instance_field) to clear or retarget
+/// an invalid reference.
+pub fn repair_instance_field<C: Component + Clone>(
+    f: impl Fn(&mut C) + Send + Sync + 'static,
+) -> Policy {
+    repair(move |entity: EntityRef, commands: &mut Commands| {
+        let Some(mut component) = entity.get::<C>().cloned() else {
+            return;
+        };
+        f(&mut component);
+        commands.entity(entity.id()).insert(component);
+    })
+}
+
+/// Returns a [`Policy`] which inserts all missing required components of `T` using their registered constructors.
+///
+/// This is typically used with [`check_required`](crate::Check::check_required).
+pub fn repair_required<T: Component>() -> Policy {
+    repair(|entity: EntityRef, commands: &mut Commands| {
+        commands
+            .entity(entity.id())
+            .queue(|entity: Entity, world: &mut World| {
+                // NOTE: Re-insert `T` to let Bevy insert any missing required components.
+                let Ok(mut entity) = world.get_entity_mut(entity) else {
+                    return;
+                };
+                if let Some(component) = entity.take::<T>() {
+                    entity.insert(component);
+                }
+            });
+    })
+}
+
+/// Returns a [`Policy`] which inserts the default values of all missing components of bundle `B`
+/// into an invalid instance. Any existing components are not modified.
+///
+/// This is typically used with [`check_bundle`](crate::Check::check_bundle).
+pub fn repair_complete_bundle<B: Bundle + Default>() -> Policy {
+    repair(|entity: EntityRef, commands: &mut Commands| {
+        commands.entity(entity.id()).insert_if_new(B::default());
+    })
+}
+
+pub fn repair_insert<T: Component + Clone>(component: T) -> Policy {
+    repair(move |entity: EntityRef, commands: &mut Commands| {
+        commands.entity(entity.id()).insert(component.clone());
+    })
+}
+
+pub fn repair_insert_default<T: Component + Default>() -> Policy {
+    repair(move |entity: EntityRef, commands: &mut Commands| {
+        commands.entity(entity.id()).insert(T::default());
+    })
+}
+
+/// Returns a [`Policy`] which inserts a clone of the given [`Bundle`] into an invalid instance.
+///
+/// Any existing components of the bundle are replaced. See [`repair_complete_bundle`] to only insert missing components.
+pub fn repair_insert_bundle<B: Bundle + Clone>(bundle: B) -> Policy {
+    repair(move |entity: EntityRef, commands: &mut Commands| {
+        commands.entity(entity.id()).insert(bundle.clone());
+    })
+}
+
+/// Returns a [`Policy`] which inserts the default value of the given [`Bundle`] into an invalid instance.
+///
+/// See [`repair_insert_bundle`] for details.
+pub fn repair_insert_bundle_default<B: Bundle + Default>() -> Policy {
+    repair(move |entity: EntityRef, commands: &mut Commands| {
+        commands.entity(entity.id()).insert(B::default());
+    })
+}
+
+/// Returns a [`Policy`] which inserts a new component into an invalid instance, constructed at repair time.
+///
+/// Unlike [`repair_insert`], the component is constructed separately for each instance.
+/// Use this for components which contain per-entity state, such as unique IDs or timestamps.
+pub fn repair_insert_with<T: Component, F>(f: F) -> Policy
+where
+    F: 'static + Fn() -> T + Send + Sync,
+{
+    repair(move |entity: EntityRef, commands: &mut Commands| {
+        commands.entity(entity.id()).insert(f());
+    })
+}
+
+/// Returns a [`Policy`] which inserts a new component into an invalid instance, constructed from the instance itself.
+///
+/// See [`repair_insert_with`] for details.
+pub fn repair_insert_from<T: Component, F>(f: F) -> Policy
+where
+    F: 'static + Fn(EntityRef) -> T + Send + Sync,
+{
+    repair(move |entity: EntityRef, commands: &mut Commands| {
+        commands.entity(entity.id()).insert(f(entity));
+    })
+}
+
+/// Returns a [`Policy`] which modifies component `T` of an invalid instance in place.
+///
+/// # Usage
+///
+/// Unlike [`repair_replace_with`], the component is not removed or inserted again. Instead, it is modified
+/// with deferred [`World`] access. This avoids triggering any component hooks or observers, and the
+/// component is only marked as changed.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Health(f32);
+///
+/// #[derive(Component)]
+/// struct Dead;
+///
+/// let mut app = App::new();
+/// app.check::<Health, Without<Dead>>(repair_modify(|health: &mut Health| {
+///     health.0 = health.0.max(1.0);
+/// }));
+/// ```
+pub fn repair_modify<T: Component, F>(f: F) -> Policy
+where
+    F: 'static + Fn(&mut T) + Send + Sync,
+{
+    let f = Arc::new(f);
+    repair(move |entity: EntityRef, commands: &mut Commands| {
+        let f = f.clone();
+        commands
+            .entity(entity.id())
+            .queue(move |entity: Entity, world: &mut World| {
+                if let Some(mut component) = world.get_mut::<T>(entity) {
+                    f(&mut component);
+                }
+            });
+    })
+}
+
+/// Returns a [`Policy`] which resets any missing or invalid fields among the given fields of component `T`
+/// of an invalid instance to their default values, while preserving all other fields.
+///
+/// # Usage
+///
+/// Use this to migrate components which gained new fields between versions. Unlike [`repair_insert_default`],
+/// any existing data in other fields is not lost. Like [`repair_modify`], the component is modified in place.
+///
+/// A field is missing if its value is not a concrete instance of its type (such as a dynamic value left
+/// by deserialization), and invalid if it is not equal to itself (such as a `NaN` float). All other fields
+/// are preserved, even if listed. Use [`repair_default_fields_where`] to detect invalid fields explicitly.
+///
+/// Any field names which don't exist in `T` are ignored with a warning.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// #[derive(Component, Reflect)]
+/// struct Stats {
+///     health: u32,
+///     stamina: f32, // Added in version 2
+/// }
+///
+/// impl Default for Stats {
+///     fn default() -> Self {
+///         Self { health: 100, stamina: 100.0 }
+///     }
+/// }
+///
+/// let mut app = App::new();
+/// app.migrate_before::<Stats, ()>(2, repair_default_fields::<Stats>(["stamina"]));
+/// ```
+pub fn repair_default_fields<T: Component + Struct + Default>(
+    fields: impl IntoIterator<Item = &'static str>,
+) -> Policy {
+    repair_default_fields_where::<T>(fields, |_, value| {
+        value.try_as_reflect().is_none() || value.reflect_partial_eq(value) == Some(false)
+    })
+}
+
+/// Returns a [`Policy`] which resets the given fields of component `T` of an invalid instance to their
+/// default values if they fail the given predicate, while preserving all other fields.
+///
+/// # Usage
+///
+/// The predicate is called with the name and value of each given field, and returns true if the field is invalid.
+///
+/// See [`repair_default_fields`] for more details.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// #[derive(Component, Reflect)]
+/// struct Stats {
+///     health: u32,
+///     stamina: u32, // Added in version 2
+/// }
+///
+/// impl Default for Stats {
+///     fn default() -> Self {
+///         Self { health: 100, stamina: 100 }
+///     }
+/// }
+///
+/// let mut app = App::new();
+/// app.migrate_before::<Stats, ()>(
+///     2,
+///     repair_default_fields_where::<Stats>(["stamina"], |_, value| {
+///         value.try_downcast_ref::<u32>() == Some(&0)
+///     }),
+/// );
+/// ```
+pub fn repair_default_fields_where<T: Component + Struct + Default>(
+    fields: impl IntoIterator<Item = &'static str>,
+    fails: impl Fn(&str, &dyn PartialReflect) -> bool + Send + Sync + 'static,
+) -> Policy {
+    let fields: Vec<&'static str> = fields.into_iter().collect();
+    repair_modify(move |component: &mut T| {
+        let default = T::default();
+        for name in &fields {
+            match (component.field_mut(name), default.field(name)) {
+                (Some(field), Some(value)) => {
+                    if fails(name, field) {
+                        field.apply(value);
+                    }
+                }
+                _ => warn!(
+                    "{} has no field '{name}'",
+                    moonshine_util::get_short_name(std::any::type_name::<T>())
+                ),
+            }
+        }
+    })
+}
+
+pub fn repair_replace<T: Component, U: Component + Clone>(component: U) -> Policy {
+    repair(move |entity: EntityRef, commands: &mut Commands| {
+        commands
+            .entity(entity.id())
+            .remove::<T>()
+            .insert(component.clone());
+    })
+}
+
+pub fn repair_replace_default<T: Component, U: Component + Default>() -> Policy {
+    repair(move |entity: EntityRef, commands: &mut Commands| {
+        commands
+            .entity(entity.id())
+            .remove::<T>()
+            .insert(U::default());
+    })
+}
+
+pub fn repair_replace_with<T: Component, U: Component, F>(f: F) -> Policy
+where
+    F: 'static + Fn(&T) -> U + Send + Sync,
+{
+    repair(move |entity: EntityRef, commands: &mut Commands| {
+        let component = entity.get::<T>().unwrap();
+        commands
+            .entity(entity.id())
+            .remove::<T>()
+            .insert(f(component));
+    })
+}
+
+pub fn repair_remove<T: Component>() -> Policy {
+    repair(move |entity: EntityRef, commands: &mut Commands| {
+        commands.entity(entity.id()).remove::<T>();
+    })
+}
+
+/// A [`Component`] which marks an [`Entity`] as the template of kind `T`.
+///
+/// See [`repair_clone_from`] for details.
+#[derive(Component)]
+pub struct Template<T: Kind>(pub(crate) PhantomData<T>);
+
+impl<T: Kind> Default for Template<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// Removes the [`RepairProgress`] of an instance once it is checked.
+fn reset_repair_progress(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+    if world.entity(entity).contains::<RepairProgress>() {
+        world.commands().entity(entity).remove::<RepairProgress>();
+    }
+}
+
+/// The next step of each [`repair_pipeline`] applied to an instance since it was last checked, by pipeline.
+#[derive(Component, Default)]
+pub(crate) struct RepairProgress(pub(crate) HashMap<usize, usize>);
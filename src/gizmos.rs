@@ -0,0 +1,84 @@
+//! Visualization of invalid entities using [`Gizmos`].
+
+use bevy_app::prelude::*;
+use bevy_color::Color;
+use bevy_ecs::prelude::*;
+use bevy_gizmos::prelude::*;
+use bevy_math::{Isometry3d, Vec3};
+use bevy_transform::prelude::*;
+
+use crate::Invalid;
+
+/// A [`Plugin`] which draws a gizmo at the position of every invalid entity with a [`GlobalTransform`].
+///
+/// See [`InvalidGizmos`] for configuration.
+pub struct CheckGizmosPlugin;
+
+impl Plugin for CheckGizmosPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InvalidGizmos>()
+            .add_systems(PostUpdate, draw_invalid_gizmos);
+    }
+}
+
+/// A [`Resource`] used to configure the gizmos drawn by [`CheckGizmosPlugin`].
+#[derive(Resource, Clone, Debug)]
+pub struct InvalidGizmos {
+    /// If false, no gizmos are drawn.
+    pub enabled: bool,
+    /// Shape of the gizmo.
+    pub shape: InvalidGizmoShape,
+    /// Size of the gizmo, in world units.
+    pub size: f32,
+    /// Color of the gizmo.
+    pub color: Color,
+}
+
+impl Default for InvalidGizmos {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            shape: InvalidGizmoShape::Box,
+            size: 1.0,
+            color: Color::srgb(1.0, 0.0, 0.0),
+        }
+    }
+}
+
+/// Shape of the gizmo drawn for each invalid entity. See [`InvalidGizmos`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvalidGizmoShape {
+    /// A wireframe cube with edges equal to the gizmo size.
+    Box,
+    /// A wireframe sphere with a diameter equal to the gizmo size.
+    Sphere,
+}
+
+fn draw_invalid_gizmos(
+    query: Query<&GlobalTransform, With<Invalid>>,
+    config: Res<InvalidGizmos>,
+    mut gizmos: Gizmos,
+) {
+    if !config.enabled {
+        return;
+    }
+
+    for transform in query.iter() {
+        let translation = transform.translation();
+        match config.shape {
+            InvalidGizmoShape::Box => {
+                gizmos.cuboid(
+                    Transform::from_translation(translation).with_scale(Vec3::splat(config.size)),
+                    config.color,
+                );
+            }
+            InvalidGizmoShape::Sphere => {
+                gizmos.sphere(
+                    Isometry3d::from_translation(translation),
+                    config.size / 2.0,
+                    config.color,
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,114 @@
+//! Capture of purged entities for post-mortem inspection.
+
+use bevy_ecs::prelude::*;
+use bevy_scene::{DynamicScene, DynamicSceneBuilder};
+
+use crate::with_descendants;
+
+/// A [`Resource`] which stores the state of recently purged instances.
+///
+/// # Usage
+///
+/// When this resource exists, each instance purged by a [`Check`](crate::Check) is extracted (along with all of its descendants)
+/// into a [`DynamicScene`] before it is despawned. This allows a post-mortem inspection of purged instances.
+///
+/// Only the most recent graves are kept, up to the capacity of the graveyard.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Apple;
+///
+/// #[derive(Component)]
+/// struct Fresh;
+///
+/// let mut app = App::new();
+/// app.add_plugins(MinimalPlugins)
+///     .insert_resource(Graveyard::new(16))
+///     .check::<Apple, Without<Fresh>>(purge());
+///
+/// app.world_mut().spawn(Apple);
+/// app.update();
+///
+/// let graveyard = app.world().resource::<Graveyard>();
+/// assert_eq!(graveyard.len(), 1);
+/// ```
+#[derive(Resource)]
+pub struct Graveyard {
+    pub(crate) capacity: usize,
+    graves: std::collections::VecDeque<Grave>,
+}
+
+impl Graveyard {
+    /// Creates a new graveyard which keeps at most `capacity` graves.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            graves: std::collections::VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the maximum number of graves kept in this graveyard.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of graves in this graveyard.
+    pub fn len(&self) -> usize {
+        self.graves.len()
+    }
+
+    /// Returns true if this graveyard has no graves.
+    pub fn is_empty(&self) -> bool {
+        self.graves.is_empty()
+    }
+
+    /// Iterates over all graves in this graveyard, from oldest to newest.
+    pub fn iter(&self) -> impl Iterator<Item = &Grave> {
+        self.graves.iter()
+    }
+
+    /// Removes all graves from this graveyard.
+    pub fn clear(&mut self) {
+        self.graves.clear();
+    }
+
+    pub(crate) fn push(&mut self, grave: Grave) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.graves.len() >= self.capacity {
+            self.graves.pop_front();
+        }
+        self.graves.push_back(grave);
+    }
+}
+
+/// The state of a purged instance. See [`Graveyard`].
+pub struct Grave {
+    /// The purged entity. Note that this entity no longer exists.
+    pub entity: Entity,
+    /// Name of the check which purged the entity.
+    pub check: String,
+    /// The entity and all of its descendants, as they were before they were purged.
+    pub scene: DynamicScene,
+}
+
+pub(crate) fn bury(world: &mut World, entity: Entity, check: String) {
+    if world.get_entity(entity).is_err() {
+        return;
+    }
+
+    let scene = DynamicSceneBuilder::from_world(world)
+        .extract_entities(with_descendants(world, entity).into_iter())
+        .build();
+
+    world.resource_mut::<Graveyard>().push(Grave {
+        entity,
+        check,
+        scene,
+    });
+}
@@ -0,0 +1,363 @@
+//! Records of all checks and policies applied to entities.
+
+use std::time::Duration;
+
+use bevy_ecs::{component::Tick, prelude::*, world::CommandQueue};
+use bevy_time::Time;
+
+use crate::{dump_components, with_registry, Outcome};
+
+/// A [`Resource`] which records all repairs and purges applied by checks.
+///
+/// # Usage
+///
+/// When this resource exists, each instance repaired or purged by a [`Check`](crate::Check) is recorded as a [`JournalEntry`].
+/// This allows you to find out exactly what checks did to the world, such as after loading an old save file.
+///
+/// Only the most recent entries are kept, up to the capacity of the journal.
+/// Use [`with_snapshots`](ValidationJournal::with_snapshots) to also record the state of each instance before
+/// the policy was applied.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Apple;
+///
+/// #[derive(Component)]
+/// struct Fresh;
+///
+/// let mut app = App::new();
+/// app.add_plugins(MinimalPlugins)
+///     .insert_resource(ValidationJournal::new(1024).with_snapshots())
+///     .check::<Apple, Without<Fresh>>(purge());
+///
+/// let entity = app.world_mut().spawn(Apple).id();
+/// app.update();
+///
+/// let journal = app.world().resource::<ValidationJournal>();
+/// assert_eq!(journal.entries_for(entity).count(), 1);
+/// println!("{}", journal.dump());
+/// ```
+#[derive(Resource, Clone, Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+pub struct ValidationJournal {
+    pub(crate) capacity: usize,
+    pub(crate) snapshots: bool,
+    entries: std::collections::VecDeque<JournalEntry>,
+}
+
+impl ValidationJournal {
+    /// Creates a new journal which keeps at most `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            snapshots: false,
+            entries: std::collections::VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Records the components of each instance before the policy is applied.
+    ///
+    /// Only components which are registered and reflect [`Component`] are recorded with their values.
+    pub fn with_snapshots(mut self) -> Self {
+        self.snapshots = true;
+        self
+    }
+
+    /// Returns the maximum number of entries kept in this journal.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of entries in this journal.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if this journal has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over all entries in this journal, from oldest to newest.
+    pub fn iter(&self) -> impl Iterator<Item = &JournalEntry> {
+        self.entries.iter()
+    }
+
+    /// Iterates over all entries of the given `entity`, from oldest to newest.
+    pub fn entries_for(&self, entity: Entity) -> impl Iterator<Item = &JournalEntry> {
+        self.iter().filter(move |entry| entry.entity == entity)
+    }
+
+    /// Removes all entries from this journal.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Returns all entries in this journal as human readable text, one entry per line.
+    pub fn dump(&self) -> String {
+        self.iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub(crate) fn push(&mut self, entry: JournalEntry) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+}
+
+/// A [`Resource`] which keeps a log of recent validation actions, for display in the application.
+///
+/// # Usage
+///
+/// When this resource exists, every instance which is invalidated, repaired, or purged by a [`Check`](crate::Check) is
+/// recorded as a [`ValidationLogEntry`] with a human readable message. Unlike [`ValidationJournal`], this log
+/// is meant to be displayed to users, such as in an in-game debug console or as toast notifications.
+///
+/// Only the most recent entries are kept, up to the capacity of the log.
+/// Use [`since`](ValidationLog::since) to find new entries since the last time the log was displayed.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// fn show_toasts(log: Res<ValidationLog>, mut seen: Local<usize>) {
+///     for entry in log.since(*seen) {
+///         println!("[{:.2}s] {}", entry.time.as_secs_f32(), entry.message);
+///     }
+///     *seen = log.total();
+/// }
+///
+/// let mut app = App::new();
+/// app.insert_resource(ValidationLog::new(100))
+///     .add_systems(Update, show_toasts);
+/// ```
+#[derive(Resource, Clone, Debug)]
+pub struct ValidationLog {
+    pub(crate) capacity: usize,
+    pub(crate) total: usize,
+    entries: std::collections::VecDeque<ValidationLogEntry>,
+}
+
+impl ValidationLog {
+    /// Creates a new log which keeps at most `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            total: 0,
+            entries: std::collections::VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the maximum number of entries kept in this log.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of entries in this log.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if this log has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the total number of entries ever added to this log, including any which were discarded.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Iterates over all entries in this log, from oldest to newest.
+    pub fn iter(&self) -> impl Iterator<Item = &ValidationLogEntry> {
+        self.entries.iter()
+    }
+
+    /// Iterates over all entries added after the given [`total`](ValidationLog::total), from oldest to newest.
+    pub fn since(&self, total: usize) -> impl Iterator<Item = &ValidationLogEntry> {
+        let count = self.total.saturating_sub(total).min(self.entries.len());
+        self.entries.iter().skip(self.entries.len() - count)
+    }
+
+    /// Removes all entries from this log.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub(crate) fn push(&mut self, entry: ValidationLogEntry) {
+        self.total += 1;
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+}
+
+/// A single validation action recorded in a [`ValidationLog`].
+#[derive(Clone, Debug)]
+pub struct ValidationLogEntry {
+    /// Elapsed [`Time`] when the action was applied.
+    pub time: Duration,
+    /// The affected entity. Note that purged entities no longer exist.
+    pub entity: Entity,
+    /// Name of the check which applied the action.
+    pub check: String,
+    /// The outcome of the check.
+    pub outcome: Outcome,
+    /// A human readable description of the action.
+    pub message: String,
+}
+
+/// Adds a new entry to the [`ValidationLog`], if it exists.
+pub(crate) fn log_action(
+    world: &World,
+    entity: Entity,
+    check: &str,
+    outcome: Outcome,
+    message: String,
+    commands: &mut Commands,
+) {
+    if !world.contains_resource::<ValidationLog>() {
+        return;
+    }
+    let entry = ValidationLogEntry {
+        time: world
+            .get_resource::<Time>()
+            .map(|time| time.elapsed())
+            .unwrap_or_default(),
+        entity,
+        check: check.to_owned(),
+        outcome,
+        message,
+    };
+    commands.queue(move |world: &mut World| {
+        if let Some(mut log) = world.get_resource_mut::<ValidationLog>() {
+            log.push(entry);
+        }
+    });
+}
+
+/// A single repair or purge recorded in a [`ValidationJournal`].
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+pub struct JournalEntry {
+    /// The repaired or purged entity. Note that purged entities no longer exist.
+    ///
+    /// This is [`Entity::PLACEHOLDER`] if the whole kind was repaired, such as by [`check_count`](crate::Check::check_count).
+    pub entity: Entity,
+    /// Name of the kind which failed the check, or empty if the entity was purged by
+    /// [`escalate_invalid`](crate::Check::escalate_invalid).
+    pub kind: String,
+    /// Name of the failed check.
+    pub check: String,
+    /// The applied outcome, either [`Outcome::Repaired`] or [`Outcome::Purged`].
+    pub outcome: Outcome,
+    /// The change tick of the world when the outcome was applied.
+    #[cfg_attr(feature = "serialize", serde(serialize_with = "serialize_tick"))]
+    pub tick: Tick,
+    /// The components of the entity before the outcome was applied, if snapshots are enabled.
+    pub snapshot: Option<String>,
+}
+
+impl std::fmt::Display for JournalEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[{}] {} ({}) was {:?}: {}",
+            self.tick.get(),
+            self.entity,
+            self.kind,
+            self.outcome,
+            self.check
+        )?;
+        if let Some(snapshot) = &self.snapshot {
+            write!(f, " {snapshot}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serialize")]
+fn serialize_tick<S: serde::Serializer>(tick: &Tick, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u32(tick.get())
+}
+
+#[cfg(feature = "serialize")]
+impl ValidationJournal {
+    /// Serializes this journal into a JSON string.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Serializes this journal into a RON string.
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, Default::default())
+    }
+}
+
+/// Records the given outcome of the given `entity` in the [`ValidationJournal`] immediately, if it exists.
+pub(crate) fn journal_now(
+    world: &mut World,
+    entity: Entity,
+    kind: &str,
+    check: &str,
+    outcome: Outcome,
+) {
+    let mut queue = CommandQueue::default();
+    if let Ok(entity) = world.get_entity(entity) {
+        record(
+            world,
+            entity,
+            kind,
+            check,
+            outcome,
+            &mut Commands::new(&mut queue, world),
+        );
+    }
+    queue.apply(world);
+}
+
+/// Records the given outcome of the given `entity` in the [`ValidationJournal`], if it exists.
+pub(crate) fn record(
+    world: &World,
+    entity: EntityRef,
+    kind: &str,
+    check: &str,
+    outcome: Outcome,
+    commands: &mut Commands,
+) {
+    let Some(journal) = world.get_resource::<ValidationJournal>() else {
+        return;
+    };
+    let snapshot = journal
+        .snapshots
+        .then(|| with_registry(world, |registry| dump_components(entity, world, registry)));
+    let entry = JournalEntry {
+        entity: entity.id(),
+        kind: kind.to_owned(),
+        check: check.to_owned(),
+        outcome,
+        tick: world.read_change_tick(),
+        snapshot,
+    };
+    commands.queue(move |world: &mut World| {
+        if let Some(mut journal) = world.get_resource_mut::<ValidationJournal>() {
+            journal.push(entry);
+        }
+    });
+}
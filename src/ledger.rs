@@ -0,0 +1,123 @@
+//! Marker-free validation, which records checked entities in a ledger instead of marker components.
+
+use bevy_ecs::{
+    component::ComponentId,
+    entity::{EntityHashMap, EntityHashSet},
+    prelude::*,
+};
+use moonshine_kind::prelude::*;
+
+use crate::{CheckFilter, Checked};
+
+/// A [`Resource`] which tracks the checked and invalid entities without any marker components.
+///
+/// See [`CheckPlugin::marker_free`](crate::CheckPlugin::marker_free) for details.
+#[derive(Resource, Default, Debug)]
+pub struct CheckLedger {
+    pub(crate) checked: EntityHashSet,
+    pub(crate) invalid: EntityHashMap<String>,
+}
+
+impl CheckLedger {
+    /// Returns true if the given entity has been checked, regardless of its validity.
+    pub fn is_checked(&self, entity: Entity) -> bool {
+        self.checked.contains(&entity)
+    }
+
+    /// Returns true if the given entity has been checked and is invalid.
+    pub fn is_invalid(&self, entity: Entity) -> bool {
+        self.invalid.contains_key(&entity)
+    }
+
+    /// Returns true if the given entity has been checked and is valid.
+    ///
+    /// This is the equivalent of the [`Valid`](crate::Valid) query filter.
+    pub fn is_valid(&self, entity: Entity) -> bool {
+        self.is_checked(entity) && !self.is_invalid(entity)
+    }
+
+    /// Returns the reason the given entity is invalid, if it is invalid.
+    pub fn reason(&self, entity: Entity) -> Option<&str> {
+        self.invalid.get(&entity).map(String::as_str)
+    }
+
+    /// Filters the given entities, returning only the valid ones.
+    pub fn filter_valid<'a>(
+        &'a self,
+        entities: impl IntoIterator<Item = Entity> + 'a,
+    ) -> impl Iterator<Item = Entity> + 'a {
+        entities.into_iter().filter(|&entity| self.is_valid(entity))
+    }
+
+    /// Iterates over all invalid entities and their reasons.
+    pub fn iter_invalid(&self) -> impl Iterator<Item = (Entity, &str)> {
+        self.invalid
+            .iter()
+            .map(|(&entity, reason)| (entity, reason.as_str()))
+    }
+
+    /// Marks the given entity to be checked again.
+    ///
+    /// This is the equivalent of [`check_again`](crate::CheckAgain::check_again).
+    pub fn check_again(&mut self, entity: Entity) {
+        self.checked.remove(&entity);
+        self.invalid.remove(&entity);
+    }
+}
+
+/// Returns a run condition which is true if the given entity is valid according to the [`CheckLedger`].
+pub fn ledger_valid(entity: Entity) -> impl Fn(Option<Res<CheckLedger>>) -> bool + Clone {
+    move |ledger: Option<Res<CheckLedger>>| ledger.is_some_and(|ledger| ledger.is_valid(entity))
+}
+
+/// Checks all instances which are not in the [`CheckLedger`].
+pub(crate) fn ledger_checks(world: &mut World) {
+    world.apply_checks();
+}
+
+/// Removes any entity from the [`CheckLedger`] when a component used by a check of kind `T` with
+/// filter `F` is added or removed, so that it is checked again.
+///
+/// This also removes any despawned entity from the ledger.
+pub(crate) fn watch_ledger<T: Kind, F: CheckFilter>(world: &mut World) {
+    let kind = QueryState::<(), T::Filter>::new(world);
+    let filter = QueryState::<(), F>::new(world);
+    let mut components: Vec<ComponentId> = [kind.component_access(), filter.component_access()]
+        .into_iter()
+        .flat_map(|access| {
+            access
+                .with_filters()
+                .chain(access.without_filters())
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    components.sort();
+    components.dedup();
+    if components.is_empty() {
+        return;
+    }
+
+    let mut on_add = Observer::new(ledger_check_again::<OnAdd>);
+    let mut on_remove = Observer::new(ledger_check_again::<OnRemove>);
+    for &component in &components {
+        on_add = on_add.with_component(component);
+        on_remove = on_remove.with_component(component);
+    }
+    world.spawn(on_add);
+    world.spawn(on_remove);
+}
+
+fn ledger_check_again<E: Event>(trigger: Trigger<E>, ledger: Option<ResMut<CheckLedger>>) {
+    if let Some(mut ledger) = ledger {
+        ledger.check_again(trigger.entity());
+    }
+}
+
+/// Marks the given entity as checked, using the [`CheckLedger`] if it exists.
+pub(crate) fn mark_checked(world: &mut World, entity: Entity) {
+    if let Some(mut ledger) = world.get_resource_mut::<CheckLedger>() {
+        ledger.checked.insert(entity);
+    } else {
+        world.entity_mut(entity).insert(Checked);
+    }
+}
@@ -1,23 +1,455 @@
+use std::any::TypeId;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::ops::RangeBounds;
+use std::path::Path;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
 use bevy_app::prelude::*;
 use bevy_ecs::{
+    archetype::ArchetypeId,
+    component::{ComponentId, Tick},
+    entity::{Entities, EntityHashMap, EntityHashSet},
     prelude::*,
-    query::{QueryEntityError, QueryFilter},
+    query::{QueryFilter, ReadOnlyQueryData},
+    reflect::{AppTypeRegistry, ReflectComponent},
+    schedule::{InternedScheduleLabel, InternedSystemSet, ScheduleLabel, SystemConfigs},
     system::EntityCommands,
+    world::WorldId,
+};
+use bevy_hierarchy::{Children, DespawnRecursiveExt, Parent};
+use bevy_reflect::{PartialReflect, Reflect, ReflectRef, TypeRegistry};
+use bevy_tasks::ComputeTaskPool;
+use bevy_time::Time;
+use bevy_utils::{
+    tracing::{debug, error, field, info_span, warn, Level},
+    HashMap, HashSet, Instant, Parallel,
 };
-use bevy_hierarchy::DespawnRecursiveExt;
-use bevy_utils::tracing::{debug, error, warn};
 use moonshine_kind::prelude::*;
-use moonshine_save::load::LoadSystem;
+use moonshine_save::load::{LoadSystem, Loaded};
+use moonshine_save::save::Save;
+
+/// Logs a message at the given optional [`Level`], or not at all if `None`.
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {
+        match $level {
+            Some(Level::ERROR) => bevy_utils::tracing::error!($($arg)+),
+            Some(Level::WARN) => bevy_utils::tracing::warn!($($arg)+),
+            Some(Level::INFO) => bevy_utils::tracing::info!($($arg)+),
+            Some(Level::DEBUG) => bevy_utils::tracing::debug!($($arg)+),
+            Some(_) => bevy_utils::tracing::trace!($($arg)+),
+            None => {}
+        }
+    };
+}
+
+mod audit;
+mod budget;
+mod dry_run;
+mod failure;
+mod fix;
+#[cfg(feature = "graveyard")]
+mod graveyard;
+mod journal;
+mod ledger;
+mod policy;
+mod query;
+mod reason;
+mod registry;
+mod report;
+mod snapshot;
+mod strict;
+mod threshold;
+mod validate;
+
+pub use audit::*;
+pub use budget::*;
+pub use dry_run::*;
+pub use failure::*;
+pub use fix::*;
+#[cfg(feature = "graveyard")]
+pub use graveyard::*;
+pub use journal::*;
+pub use ledger::*;
+pub use policy::*;
+pub use query::*;
+pub use reason::*;
+pub use registry::*;
+pub use report::*;
+pub use snapshot::*;
+pub use strict::*;
+pub use threshold::*;
+pub use validate::*;
+
+pub mod test;
+
+extern crate self as moonshine_check;
+
+#[cfg(feature = "derive")]
+pub use moonshine_check_derive::Check;
+
+#[cfg(feature = "egui")]
+pub mod egui;
+
+#[cfg(feature = "gizmos")]
+pub mod gizmos;
+
+#[cfg(feature = "remote")]
+pub mod remote;
+
+#[cfg(feature = "config")]
+pub mod config;
+
+#[cfg(feature = "scripting")]
+pub mod scripting;
+
+#[cfg(feature = "presets")]
+pub mod presets;
 
 pub mod prelude {
-    pub use super::{invalid, panic, purge};
-    pub use super::{repair, repair_remove};
-    pub use super::{repair_insert, repair_insert_default};
+    pub use super::{invalid, panic, purge, purge_after, purge_after_frames, PendingPurge};
+    pub use super::{ledger_valid, CheckFlush, CheckLedger, CheckPlugin};
+    pub use super::{repair, repair_orphan, repair_remove, repair_reparent, repair_spawn_children};
+    pub use super::{repair_clamp, repair_derive, repair_exclusive, ExclusiveComponents};
+    pub use super::{repair_clone_from, repair_modify, Template};
+    pub use super::{repair_complete_bundle, repair_instance_field};
+    pub use super::{repair_default_fields, repair_default_fields_where};
+    pub use super::{repair_insert, repair_insert_default, repair_insert_from, repair_insert_with};
+    pub use super::{repair_insert_bundle, repair_insert_bundle_default};
+    pub use super::{repair_pipeline, repair_with_param, repair_world, FixWithParam, Fixer};
+    pub use super::{repair_remove_dangling, repair_required};
     pub use super::{repair_replace, repair_replace_default, repair_replace_with};
-    pub use super::{Check, Valid};
+    pub use super::{try_repair, FixError, RepairFailed};
+    pub use super::{ApplyChecks, CheckEntry, CheckRegistry, CheckSet, PolicyKind};
+    pub use super::{Audit, AuditReport, CheckAudit, SpawnChecked, TriggerAudit};
+    pub use super::{Check, CheckAgain, CheckAgainAll, ConfigureCheck, MarkInvalid, Valid};
+    pub use super::{CheckBudget, CheckError, CheckReport, CheckStats, SaveVersion, ValidateNow};
+    pub use super::{CheckFailure, CheckFormatter, FailureInfo, OnCheckFailure};
+    pub use super::{CheckedAt, InvalidReason, InvalidReasons, IsChecked, IsInvalid, Unchecked};
+    pub use super::{CountPolicy, CountViolation};
+    pub use super::{Coverage, CoverageReport};
+    pub use super::{DryRun, StrictChecks, ValidationOverload, ValidationThreshold};
+    pub use super::{InstanceQueryExt, ValidInstances, Validated};
+    pub use super::{InvalidPolicy, PanicPolicy, PolicyImpl, PurgePolicy, RepairPolicy};
+    pub use super::{InvalidTimeout, WatchdogPolicy};
+    pub use super::{JournalEntry, ValidationJournal, ValidationLog, ValidationLogEntry};
+    pub use super::{Severity, SkipCheck, SkipChecks};
+
+    #[cfg(all(feature = "dump", not(target_arch = "wasm32")))]
+    pub use super::{dump, dump_and_purge};
+
+    #[cfg(feature = "graveyard")]
+    pub use super::{Grave, Graveyard};
+    pub use super::{Outcome, OutcomeSnapshot, SnapshotHook};
+
+    pub use super::Checks;
+
+    pub use crate::checks;
+
+    #[cfg(feature = "derive")]
+    pub use moonshine_check_derive::Check;
+}
+
+#[doc(hidden)]
+pub mod __private {
+    pub use bevy_app::{App, SubApp};
+    pub use bevy_ecs::query::{Or, With, Without};
+}
+
+/// A [`Plugin`] which configures how checks are evaluated.
+///
+/// Adding this plugin is optional, since checks are initialized automatically when they are added.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// let mut app = App::new();
+/// app.add_plugins(CheckPlugin::default().audit_on_startup());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CheckPlugin {
+    /// If true, all existing entities are checked again after [`Startup`].
+    ///
+    /// See [`audit_on_startup`](CheckPlugin::audit_on_startup) for details.
+    pub audit_on_startup: bool,
+    /// If true, duplicate or conflicting check registrations panic on [`Startup`] instead of logging a warning.
+    ///
+    /// See [`strict_registration`](CheckPlugin::strict_registration) for details.
+    pub strict_registration: bool,
+    /// Names of all check groups which are disabled when the app starts.
+    ///
+    /// See [`disable_group`](CheckPlugin::disable_group) for details.
+    pub disabled_groups: Vec<String>,
+    /// If true, all unchecked entities are checked again at the end of [`Update`].
+    ///
+    /// See [`flush_checks`](CheckPlugin::flush_checks) for details.
+    pub flush_checks: bool,
+    /// The schedule in which all checks are evaluated, if not [`PreUpdate`].
+    ///
+    /// See [`in_schedule`](CheckPlugin::in_schedule) for details.
+    pub schedule: Option<InternedScheduleLabel>,
+    /// If true, check results are tracked in a [`CheckLedger`] instead of marker components.
+    ///
+    /// See [`marker_free`](CheckPlugin::marker_free) for details.
+    pub marker_free: bool,
+    /// If set, all unchecked component types with this type path prefix are logged after [`Startup`].
+    ///
+    /// See [`log_coverage`](CheckPlugin::log_coverage) for details.
+    pub coverage: Option<String>,
+}
+
+impl CheckPlugin {
+    /// Checks all existing entities again after [`Startup`], including any which are already checked.
+    ///
+    /// # Usage
+    ///
+    /// By default, each entity is only checked once. Entities which were marked as checked before the app started
+    /// (such as entities inserted by other plugins, or from scenes) are never evaluated against any checks
+    /// which were added afterwards. Use this option to ensure the entire world is audited when the app starts.
+    ///
+    /// All entities are checked again during the first check pass, before any [`Update`] systems run.
+    pub fn audit_on_startup(mut self) -> Self {
+        self.audit_on_startup = true;
+        self
+    }
+
+    /// Panics on [`Startup`] if any check is registered more than once, or with conflicting policies.
+    ///
+    /// # Usage
+    ///
+    /// Two checks conflict if they check the same kind with the same condition. By default, this is
+    /// only logged as a warning, since it is harmless, but often a sign that a check was accidentally
+    /// added by multiple plugins.
+    pub fn strict_registration(mut self) -> Self {
+        self.strict_registration = true;
+        self
+    }
+
+    /// Disables all checks in the given group.
+    ///
+    /// See [`in_check_group`](ConfigureCheck::in_check_group) for details.
+    pub fn disable_group(mut self, group: impl Into<String>) -> Self {
+        self.disabled_groups.push(group.into());
+        self
+    }
+
+    /// Checks all unchecked entities again during [`Update`], in an exclusive system.
+    ///
+    /// # Usage
+    ///
+    /// By default, checks are evaluated during [`PreUpdate`]. Any entity spawned during [`Update`] is not
+    /// checked until the next frame, so it is not [`Valid`] until then.
+    ///
+    /// With this option, all unchecked entities are checked again in the [`CheckFlush`] set, and all policies
+    /// are applied immediately. Any [`Update`] systems ordered after [`CheckFlush`] observe the results of
+    /// the checks in the same frame:
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Apple;
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins(CheckPlugin::default().flush_checks())
+    ///     .add_systems(Update, (spawn_apples.before(CheckFlush), eat_apples.after(CheckFlush)));
+    ///
+    /// fn spawn_apples(mut commands: Commands) {
+    ///     commands.spawn(Apple);
+    /// }
+    ///
+    /// fn eat_apples(apples: Query<&Apple, Valid>) {
+    ///     // Apples spawned this frame are included here ...
+    /// }
+    /// ```
+    ///
+    /// Checks which are statically dispatched are not flushed, and are still evaluated in the next check pass.
+    /// Because the flush is an exclusive system, it prevents any other [`Update`] systems from running in parallel with it.
+    pub fn flush_checks(mut self) -> Self {
+        self.flush_checks = true;
+        self
+    }
+
+    /// Evaluates all checks in the given schedule, instead of [`PreUpdate`].
+    ///
+    /// # Usage
+    ///
+    /// By default, all checks are evaluated during [`PreUpdate`]. If any [`PreUpdate`] systems
+    /// (including systems from other plugins) assume all entities are already checked, use this option
+    /// to evaluate the checks in an earlier schedule, such as [`First`]:
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Apple;
+    ///
+    /// #[derive(Component)]
+    /// struct Fresh;
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins(CheckPlugin::default().in_schedule(First))
+    ///     .check::<Apple, Without<Fresh>>(purge());
+    /// ```
+    ///
+    /// Note that data loaded during [`PreUpdate`] is not checked until the next frame if checks
+    /// are evaluated in an earlier schedule.
+    ///
+    /// # Panics
+    ///
+    /// This plugin must be added before any checks, since checks are added to the schedule when they are registered.
+    /// Panics if any checks were already added to another schedule.
+    pub fn in_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.schedule = Some(schedule.intern());
+        self
+    }
+
+    /// Tracks check results in a [`CheckLedger`] instead of inserting [`Checked`] and [`Invalid`] markers.
+    ///
+    /// # Usage
+    ///
+    /// By default, checks insert marker components into every checked entity, which moves it into a new archetype.
+    /// Use this option for worlds where avoiding any archetype changes during validation is worth the cost.
+    ///
+    /// In this mode, all checks are evaluated by a single exclusive system against every instance which is not
+    /// yet in the ledger. Any entity which gains or loses a component used by a check is removed from the ledger,
+    /// and so it is checked again. Query filters such as [`Valid`] do not work in this mode; use the [`CheckLedger`]
+    /// to look up the state of each entity instead, or [`ledger_valid`] as a run condition.
+    ///
+    /// Checks which are statically dispatched, and dynamic checks, are not supported in this mode.
+    /// Policies which purge the entity after a delay purge it immediately.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Apple;
+    ///
+    /// #[derive(Component)]
+    /// struct Fresh;
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins((MinimalPlugins, CheckPlugin::default().marker_free()))
+    ///     .check::<Apple, Without<Fresh>>(invalid());
+    ///
+    /// let entity = app.world_mut().spawn(Apple).id();
+    /// app.update();
+    ///
+    /// let ledger = app.world().resource::<CheckLedger>();
+    /// assert!(ledger.is_invalid(entity));
+    /// let mut invalid = app.world_mut().query_filtered::<Entity, IsInvalid>();
+    /// assert_eq!(invalid.iter(app.world()).count(), 0);
+    /// ```
+    pub fn marker_free(mut self) -> Self {
+        self.marker_free = true;
+        self
+    }
+
+    /// Logs a warning after [`Startup`] for each component type with the given type path prefix which has no checks.
+    ///
+    /// Use an empty prefix to log all unchecked types, including types from other crates.
+    /// See [`Coverage::check_coverage`] for details.
+    pub fn log_coverage(mut self, prefix: impl Into<String>) -> Self {
+        self.coverage = Some(prefix.into());
+        self
+    }
+}
+
+impl CheckPlugin {
+    /// Applies this configuration to the given [`SubApp`].
+    ///
+    /// # Usage
+    ///
+    /// This is called on the main sub-app when this plugin is added to an [`App`].
+    /// Use it to configure checks for any other sub-app, such as the render world.
+    ///
+    /// See [`Check`] for details on checking sub-apps.
+    pub fn configure(&self, app: &mut SubApp) {
+        if let Some(schedule) = self.schedule {
+            // NOTE: Check systems are added to the schedule when they are registered, so they can't be moved.
+            let current = check_schedule(app);
+            if app.world().contains_resource::<CheckRegistry>() && current != schedule {
+                panic!(
+                    "CheckPlugin must be added before any checks to evaluate them in {schedule:?}; \
+                    checks are already evaluated in {current:?}"
+                );
+            }
+            app.insert_resource(CheckSchedule(schedule));
+        }
+        init(app);
+        let mut registry = app.world_mut().resource_mut::<CheckRegistry>();
+        registry.strict = self.strict_registration;
+        for group in &self.disabled_groups {
+            registry.set_group_enabled(group, false);
+        }
+        if self.audit_on_startup {
+            add_check_systems(app, PostStartup, audit_on_startup);
+        }
+        if self.flush_checks {
+            add_check_systems(app, Update, flush_checks.in_set(CheckFlush));
+        }
+        if let Some(prefix) = &self.coverage {
+            add_check_systems(app, PostStartup, log_coverage(prefix.clone()));
+        }
+        if self.marker_free {
+            app.init_resource::<CheckLedger>();
+            add_check_systems(
+                app,
+                check_schedule(app),
+                ledger_checks.after(LoadSystem::Load).in_set(CheckSystems),
+            );
+        }
+    }
+}
+
+impl Plugin for CheckPlugin {
+    fn build(&self, app: &mut App) {
+        self.configure(app.main_mut());
+    }
+}
+
+/// The schedule in which all checks are evaluated. See [`CheckPlugin::in_schedule`].
+#[derive(Resource)]
+struct CheckSchedule(InternedScheduleLabel);
+
+/// Returns the schedule in which all checks are evaluated.
+pub(crate) fn check_schedule(app: &SubApp) -> InternedScheduleLabel {
+    app.world()
+        .get_resource::<CheckSchedule>()
+        .map_or(PreUpdate.intern(), |schedule| schedule.0)
 }
 
-/// An extension trait used to add checks to an [`App`].
+/// An extension trait used to add checks to an [`App`] or a [`SubApp`].
+///
+/// # Sub-Apps
+///
+/// Checks added to an [`App`] are added to its main [`SubApp`]. Checks may also be added to any other
+/// sub-app (such as the render world) to validate its entities with the same machinery. Each sub-app has
+/// its own [`CheckRegistry`] and [`CheckReport`].
+///
+/// Only components which are extracted into the sub-app may be checked. The [`Checked`] and [`Invalid`]
+/// markers of the main world are not extracted, so each world is validated independently.
+///
+/// By default, checks are evaluated in [`PreUpdate`], which is not run by most sub-apps.
+/// Use [`CheckPlugin::configure`] to evaluate the checks in a schedule which the sub-app runs:
+///
+/// ```ignore
+/// use bevy::prelude::*;
+/// use bevy::render::{Render, RenderApp};
+/// use moonshine_check::prelude::*;
+///
+/// let render_app = app.sub_app_mut(RenderApp);
+/// CheckPlugin::default().in_schedule(Render).configure(render_app);
+/// render_app.check::<ExtractedSprite, Without<ExtractedTransform>>(purge());
+/// ```
 pub trait Check {
     /// Adds a new checked requirement to this [`App`] with a given [`Policy`].
     ///
@@ -48,472 +480,6802 @@ pub trait Check {
     /// // ...
     /// app.check::<Apple, Without<Fresh>>(purge());
     /// ```
-    fn check<T: Kind, F: CheckFilter>(&mut self, _: Policy) -> &mut Self;
-}
+    fn check<T: Kind, F: CheckFilter>(&mut self, _: impl Into<CheckConfig>) -> &mut Self;
 
-impl Check for App {
-    fn check<T: Kind, F: CheckFilter>(&mut self, policy: Policy) -> &mut Self {
-        let filter_name = || moonshine_util::get_short_name(std::any::type_name::<F>());
-        self.add_systems(
-            PreUpdate,
-            (move |query: Query<Instance<T>, Unchecked>,
-                   check: Query<(), F>,
-                   world: &World,
-                   mut commands: Commands| {
-                for instance in query.iter() {
-                    match check.get(instance.entity()) {
-                        // NOTE: Query Mismatch implies OK!
-                        Err(QueryEntityError::QueryDoesNotMatch(..)) => {
-                            if let Some(mut entity) = commands.get_entity(instance.entity()) {
-                                entity.try_insert(Checked);
-                                debug!("{instance:?} is valid.");
-                            }
-                            continue;
-                        }
-                        Err(QueryEntityError::NoSuchEntity(_)) => {
-                            continue;
-                        }
-                        _ => {}
-                    };
-
-                    match &policy {
-                        Policy::Invalid => {
-                            if let Some(mut entity) = commands.get_entity(instance.entity()) {
-                                entity.try_insert((Checked, Invalid));
-                                error!("{instance:?} is invalid: {}", filter_name());
-                            }
-                        }
-                        Policy::Purge => {
-                            if let Some(entity) = commands.get_entity(instance.entity()) {
-                                entity.despawn_recursive();
-                                error!("{instance:?} is purged: {}", filter_name());
-                            }
-                        }
-                        Policy::Panic => {
-                            panic!("{instance:?} is strictly invalid: {}", filter_name());
-                        }
-                        Policy::Repair(fixer) => {
-                            if let Some(mut entity) = commands.get_entity(instance.entity()) {
-                                // Inset `Checked` before fixing to let the fixer remove it if needed
-                                entity.try_insert(Checked);
-                                error!("{instance:?} is invalid: {}", filter_name());
-
-                                let entity = world.entity(instance.entity());
-                                fixer.fix(entity, &mut commands);
-                                warn!("{instance:?} was repaired.");
-                            }
-                        }
-                    }
-                }
-            })
-            .after(LoadSystem::Load)
-            .in_set(CheckSystems),
-        )
-    }
-}
+    /// Adds a new checked requirement which asserts that every instance of `T` also has component `U`.
+    ///
+    /// This is equivalent to `check::<T, Without<U>>`.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Apple;
+    ///
+    /// #[derive(Component)]
+    /// struct Fresh;
+    ///
+    /// let mut app = App::new();
+    /// // Every `Apple` must be `Fresh`:
+    /// app.require::<Apple, Fresh>(purge());
+    /// ```
+    fn require<T: Kind, U: Component>(&mut self, _: impl Into<CheckConfig>) -> &mut Self;
 
-pub trait CheckFilter: 'static + QueryFilter + Send + Sync {}
+    /// Adds a new checked requirement which is only registered in debug builds.
+    ///
+    /// # Usage
+    ///
+    /// Use this for expensive checks which are only intended for development.
+    /// In release builds, this check is not registered at all, unless the `debug_checks` feature is enabled.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Apple;
+    ///
+    /// #[derive(Component)]
+    /// struct Fresh;
+    ///
+    /// let mut app = App::new();
+    /// app.debug_check::<Apple, Without<Fresh>>(panic());
+    /// ```
+    fn debug_check<T: Kind, F: CheckFilter>(&mut self, _: impl Into<CheckConfig>) -> &mut Self;
 
-impl<F> CheckFilter for F where F: 'static + QueryFilter + Send + Sync {}
+    /// Adds a new checked requirement which is evaluated for all new instances every frame.
+    ///
+    /// This is the default behavior of [`check`](Check::check).
+    fn check_on_spawn<T: Kind, F: CheckFilter>(&mut self, _: impl Into<CheckConfig>)
+        -> &mut Self;
 
-#[derive(Clone, Debug, Hash, PartialEq, Eq, SystemSet)]
-pub struct CheckSystems;
+    /// Adds a new checked requirement which is evaluated for all new instances at each fixed simulation tick.
+    ///
+    /// # Usage
+    ///
+    /// By default, checks are evaluated once per frame. For deterministic simulations, use this method to
+    /// evaluate the check in [`FixedPreUpdate`] instead, so that validation frequency matches the simulation
+    /// rather than the frame rate.
+    ///
+    /// Note that the check is not evaluated during frames in which no fixed tick runs.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Body;
+    ///
+    /// #[derive(Component)]
+    /// struct Velocity;
+    ///
+    /// let mut app = App::new();
+    /// app.check_fixed::<Body, Without<Velocity>>(invalid());
+    /// ```
+    fn check_fixed<T: Kind, F: CheckFilter>(&mut self, _: impl Into<CheckConfig>) -> &mut Self;
 
-/// An action to be invoked if a [`Check`] *passes*.
-///
-/// See [`invalid`], [`purge`], [`panic`], and [`repair`] for details.
-pub enum Policy {
-    /// Mark the instance as invalid.
-    Invalid,
-    /// Despawn the instance and all of its children.
-    Purge,
-    /// Panic!
-    Panic,
-    /// Try to repair the instance with a given [`Fixer`].
-    Repair(Fixer),
-}
+    /// Adds a new checked requirement which is only evaluated after a load completes.
+    ///
+    /// # Usage
+    ///
+    /// Use this for invariants which may only be violated by old save data.
+    /// Unlike [`check_on_spawn`](Check::check_on_spawn), this check is not evaluated on frames without a load.
+    /// Any instances which are checked on these frames pass this check without being evaluated.
+    ///
+    /// Note that when it is evaluated, this check evaluates *all* unchecked instances of `T`,
+    /// including any which were not loaded.
+    fn check_on_load<T: Kind, F: CheckFilter>(&mut self, _: impl Into<CheckConfig>) -> &mut Self;
+
+    /// Adds a new checked requirement which is only evaluated after a load completes, and only if
+    /// the [`SaveVersion`] of the loaded data is less than the given `version`.
+    ///
+    /// # Usage
+    ///
+    /// Use this to migrate old save data to a new schema. Unlike [`check_on_load`](Check::check_on_load),
+    /// the check is skipped entirely for any save data which is already up to date.
+    ///
+    /// Any instances which are not loaded, or loaded from save data which is already up to date,
+    /// pass this check without being evaluated.
+    ///
+    /// The [`SaveVersion`] resource should be updated before [`LoadSystem::Load`] completes.
+    /// If it does not exist, the loaded data is assumed to be at version `0`.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Player;
+    ///
+    /// #[derive(Component, Default)]
+    /// struct Stamina(f32);
+    ///
+    /// let mut app = App::new();
+    /// // `Stamina` was added in version 3:
+    /// app.migrate_before::<Player, Without<Stamina>>(3, repair_insert_default::<Stamina>());
+    /// ```
+    fn migrate_before<T: Kind, F: CheckFilter>(
+        &mut self,
+        version: u32,
+        _: impl Into<CheckConfig>,
+    ) -> &mut Self;
+
+    /// Adds a new checked requirement with a statically dispatched [`PolicyImpl`].
+    ///
+    /// # Usage
+    ///
+    /// Unlike [`check`](Check::check), the policy is monomorphized into the check system, which avoids
+    /// any dynamic dispatch when applying the policy. This is useful for performance-sensitive applications
+    /// with a large number of instances to check.
+    ///
+    /// Note that statically dispatched checks do not support any [`CheckConfig`] options.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Apple;
+    ///
+    /// #[derive(Component, Default)]
+    /// struct Fresh;
+    ///
+    /// let mut app = App::new();
+    /// app.check_static::<Apple, Without<Fresh>, _>(RepairPolicy(
+    ///     |entity: EntityRef, commands: &mut Commands| {
+    ///         commands.entity(entity.id()).insert(Fresh);
+    ///     },
+    /// ));
+    /// ```
+    fn check_static<T: Kind, F: CheckFilter, P: PolicyImpl>(&mut self, _: P) -> &mut Self;
+
+    /// Adds a new checked requirement which asserts that every instance of `T` is a child of a `P`.
+    ///
+    /// # Usage
+    ///
+    /// Any instance without a parent, or with a parent which is not a `P`, fails the check.
+    ///
+    /// See [`repair_reparent`] to move failed instances under a fallback parent.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Wheel;
+    ///
+    /// #[derive(Component)]
+    /// struct Vehicle;
+    ///
+    /// let mut app = App::new();
+    /// app.check_parent::<Wheel, Vehicle>(purge());
+    /// ```
+    fn check_parent<T: Kind, P: Component>(&mut self, _: impl Into<CheckConfig>) -> &mut Self;
+
+    /// Adds a new checked requirement which asserts that every instance of `T` has at least
+    /// `min_count` children with component `C`.
+    ///
+    /// See [`repair_spawn_children`] to spawn any missing children.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Squad;
+    ///
+    /// #[derive(Component, Clone)]
+    /// struct Unit;
+    ///
+    /// let mut app = App::new();
+    /// app.check_children::<Squad, Unit>(1, repair_spawn_children::<Unit, _>(1, Unit));
+    /// ```
+    fn check_children<T: Kind, C: Component>(
+        &mut self,
+        min_count: usize,
+        _: impl Into<CheckConfig>,
+    ) -> &mut Self;
+
+    /// Adds a new checked requirement which asserts that the [`Parent`] of every entity exists.
+    ///
+    /// # Usage
+    ///
+    /// Dangling parent references are usually caused by manual hierarchy changes or partial loads.
+    ///
+    /// Use [`repair_orphan`] to remove the dangling reference, or [`purge`] to despawn the entity.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// let mut app = App::new();
+    /// app.check_dangling_parents(repair_orphan());
+    /// ```
+    fn check_dangling_parents(&mut self, _: impl Into<CheckConfig>) -> &mut Self;
+
+    /// Adds a new checked requirement which asserts that there are no cycles in the hierarchy.
+    ///
+    /// # Usage
+    ///
+    /// For each cycle, only the entity with the lowest [`Entity`] ID fails the check.
+    /// This allows the cycle to be broken by removing a single edge using [`repair_orphan`].
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// let mut app = App::new();
+    /// app.check_hierarchy_cycles(repair_orphan());
+    /// ```
+    fn check_hierarchy_cycles(&mut self, _: impl Into<CheckConfig>) -> &mut Self;
+
+    /// Adds a new checked requirement which asserts that every instance of `T` is nested at most
+    /// `max_depth` levels deep in the hierarchy.
+    ///
+    /// Root entities have a depth of 0.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Room;
+    ///
+    /// let mut app = App::new();
+    /// app.check_max_depth::<Room>(8, invalid());
+    /// ```
+    fn check_max_depth<T: Kind>(
+        &mut self,
+        max_depth: usize,
+        _: impl Into<CheckConfig>,
+    ) -> &mut Self;
+
+    /// Adds a new checked requirement which asserts that there is at most one instance of `T`.
+    ///
+    /// # Usage
+    ///
+    /// If there is already a valid instance of `T`, all new instances fail the check.
+    /// Otherwise, the instance with the lowest [`Entity`] ID is kept, and all others fail the check.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Player;
+    ///
+    /// let mut app = App::new();
+    /// app.check_unique::<Player>(purge());
+    /// ```
+    fn check_unique<T: Kind>(&mut self, _: impl Into<CheckConfig>) -> &mut Self;
+
+    /// Adds a new world-level requirement which asserts that the number of instances of `T` is within the given range.
+    ///
+    /// # Usage
+    ///
+    /// Unlike other checks, this check applies to the world as a whole, rather than each instance.
+    /// It is evaluated whenever the number of instances changes.
+    ///
+    /// If the count is out of range, an error is logged and a [`CountViolation`] event is sent
+    /// before the given [`CountPolicy`] is applied.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component, Clone)]
+    /// struct SpawnPoint;
+    ///
+    /// let mut app = App::new();
+    /// app.check_count::<SpawnPoint>(2.., CountPolicy::spawn_missing(2, SpawnPoint));
+    /// ```
+    fn check_count<T: Kind>(
+        &mut self,
+        range: impl RangeBounds<usize> + Debug + Send + Sync + 'static,
+        policy: CountPolicy,
+    ) -> &mut Self;
+
+    /// Adds a new checked requirement which asserts that every instance of `T` has at most one of
+    /// the given [`ExclusiveComponents`].
+    ///
+    /// See [`repair_exclusive`] to keep only the component with the highest priority.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Unit;
+    ///
+    /// #[derive(Component)]
+    /// struct Dead;
+    ///
+    /// #[derive(Component)]
+    /// struct Alive;
+    ///
+    /// let mut app = App::new();
+    /// app.check_exclusive::<Unit, (Dead, Alive)>(repair_exclusive::<(Dead, Alive)>());
+    /// ```
+    fn check_exclusive<T: Kind, E: ExclusiveComponents>(
+        &mut self,
+        _: impl Into<CheckConfig>,
+    ) -> &mut Self;
+
+    /// Adds a new checked requirement which asserts that the values of components `A` and `B`
+    /// on the same entity satisfy the given predicate.
+    ///
+    /// # Usage
+    ///
+    /// Any entity with both `A` and `B` fails the check if the predicate returns false.
+    ///
+    /// See [`repair_derive`] and [`repair_clamp`] to fix `A` using the value of `B`.
+    ///
+    /// Note that like any other check, this check is only evaluated for new instances.
+    /// Use [`watch`](ConfigureCheck::watch) to evaluate it again whenever either value changes.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component, Clone, PartialEq, PartialOrd)]
+    /// struct Health(f32);
+    ///
+    /// #[derive(Component)]
+    /// struct MaxHealth(f32);
+    ///
+    /// let mut app = App::new();
+    /// app.check_pair::<Health, MaxHealth>(
+    ///     |health, max| health.0 <= max.0,
+    ///     repair_clamp::<Health, MaxHealth>(|max| Health(max.0))
+    ///         .watch::<Health>()
+    ///         .watch::<MaxHealth>(),
+    /// );
+    /// ```
+    fn check_pair<A: Component, B: Component>(
+        &mut self,
+        predicate: impl Fn(&A, &B) -> bool + Send + Sync + 'static,
+        _: impl Into<CheckConfig>,
+    ) -> &mut Self;
+
+    /// Adds a new checked requirement which asserts that no two instances of `T` share the same key,
+    /// as derived from their component `C`.
+    ///
+    /// # Usage
+    ///
+    /// If there is already a valid instance with a given key, all new instances with the same key fail the check.
+    /// Otherwise, the instance with the lowest [`Entity`] ID is kept, and all others fail the check.
+    ///
+    /// Note that the keys of all other instances are compared against each instance which is checked,
+    /// so this check is expensive when checking many instances at once.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Item;
+    ///
+    /// #[derive(Component)]
+    /// struct ItemId(u64);
+    ///
+    /// let mut app = App::new();
+    /// app.check_unique_value::<Item, ItemId, u64>(|id| id.0, invalid());
+    /// ```
+    fn check_unique_value<T: Kind, C: Component, K: Eq + Hash + Send + Sync + 'static>(
+        &mut self,
+        key: impl Fn(&C) -> K + Send + Sync + 'static,
+        _: impl Into<CheckConfig>,
+    ) -> &mut Self;
+
+    /// Adds a new checked requirement which asserts that all [`Entity`] references within
+    /// the reflected components of every instance of `T` point to existing entities.
+    ///
+    /// # Usage
+    ///
+    /// All components of each instance are walked using reflection, so only reflected components
+    /// which are registered in the [`AppTypeRegistry`] are validated.
+    ///
+    /// See [`repair_remove_dangling`] to remove any components with dangling references.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Unit;
+    ///
+    /// let mut app = App::new();
+    /// app.check_entity_refs::<Unit>(invalid());
+    /// ```
+    fn check_entity_refs<T: Kind>(&mut self, _: impl Into<CheckConfig>) -> &mut Self;
+
+    /// Adds a new checked requirement which asserts that the [`Instance<U>`] stored in component `C`
+    /// of every instance of `T` refers to an existing instance of `U`.
+    ///
+    /// # Usage
+    ///
+    /// The given function is used to read the instance from the component, if any.
+    ///
+    /// See [`repair_instance_field`] to clear or retarget the invalid reference.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    /// use moonshine_kind::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Driver;
+    ///
+    /// #[derive(Component)]
+    /// struct Vehicle;
+    ///
+    /// #[derive(Component, Clone)]
+    /// struct Driving(Option<Instance<Vehicle>>);
+    ///
+    /// let mut app = App::new();
+    /// app.check_instance_field::<Driver, Driving, Vehicle>(
+    ///     |driving| driving.0,
+    ///     repair_instance_field::<Driving>(|driving| driving.0 = None),
+    /// );
+    /// ```
+    fn check_instance_field<T: Kind, C: Component, U: Kind>(
+        &mut self,
+        get: impl Fn(&C) -> Option<Instance<U>> + Send + Sync + 'static,
+        _: impl Into<CheckConfig>,
+    ) -> &mut Self;
+
+    /// Periodically scans all entities for stale [`Entity`] references, and marks them as invalid.
+    ///
+    /// # Usage
+    ///
+    /// Unlike other checks, this sweep applies to all entities, regardless of their kind or whether they were checked.
+    /// All reflected components which are registered in the [`AppTypeRegistry`] are scanned, including any
+    /// [`Instance`] handles which are reflected.
+    ///
+    /// Any entity with a stale reference is marked as invalid, just like the [`invalid`] policy.
+    ///
+    /// This sweep may be expensive for large worlds, so use a period which is appropriate for the application.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// let mut app = App::new();
+    /// app.sweep_stale_references(Duration::from_secs(10));
+    /// ```
+    fn sweep_stale_references(&mut self, period: Duration) -> &mut Self;
+
+    /// Escalates all entities which have been invalid for longer than the given `timeout`.
+    ///
+    /// # Usage
+    ///
+    /// The [`invalid`] policy is useful during development, but invalid entities are never removed from the world.
+    /// Use this to ensure invalid entities don't remain in the world indefinitely.
+    ///
+    /// When an entity is escalated, an [`InvalidTimeout`] event is sent, and then the given [`WatchdogPolicy`] is applied.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// let mut app = App::new();
+    /// app.escalate_invalid(
+    ///     Duration::from_secs(60),
+    ///     if cfg!(debug_assertions) {
+    ///         WatchdogPolicy::Panic
+    ///     } else {
+    ///         WatchdogPolicy::Purge
+    ///     },
+    /// );
+    /// ```
+    fn escalate_invalid(&mut self, timeout: Duration, policy: WatchdogPolicy) -> &mut Self;
+
+    /// Adds a new checked requirement which asserts that every instance of component `T` has all
+    /// of its [required components](Component::register_required_components).
+    ///
+    /// # Usage
+    ///
+    /// Required components are normally inserted automatically, but entities loaded from old save data may bypass this.
+    ///
+    /// Note that the required components are read when this check is added, so any requirements which are
+    /// registered afterwards are not validated.
+    ///
+    /// See [`repair_required`] to insert the missing components using their registered constructors.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component, Default)]
+    /// struct Fresh;
+    ///
+    /// #[derive(Component)]
+    /// #[require(Fresh)]
+    /// struct Apple;
+    ///
+    /// let mut app = App::new();
+    /// app.check_required::<Apple>(repair_required::<Apple>());
+    /// ```
+    fn check_required<T: Component>(&mut self, _: impl Into<CheckConfig>) -> &mut Self;
+
+    /// Applies all checks of kind `B` to instances of kind `A`.
+    ///
+    /// # Usage
+    ///
+    /// Any unchecked instance of `A` is checked by all checks registered for `B`, unless they are
+    /// configured with [`no_inherit`](ConfigureCheck::no_inherit).
+    /// Instances of `A` which are also instances of `B` are only checked once.
+    ///
+    /// Note that inherited checks are only evaluated for new instances of `A`. Any options which re-check
+    /// valid instances (such as [`watch`](ConfigureCheck::watch)) only apply to instances of `B`.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    /// use moonshine_kind::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Fruit;
+    ///
+    /// #[derive(Component)]
+    /// struct Apple;
+    ///
+    /// #[derive(Component)]
+    /// struct Fresh;
+    ///
+    /// kind!(Apple is Fruit);
+    ///
+    /// let mut app = App::new();
+    /// app.check::<Fruit, Without<Fresh>>(purge())
+    ///     .inherit_checks::<Apple, Fruit>();
+    /// ```
+    fn inherit_checks<A: Kind + CastInto<B>, B: Kind>(&mut self) -> &mut Self;
+
+    /// Skips all remaining checks for an instance of `T` once it is invalidated or purged by any check.
+    ///
+    /// # Usage
+    ///
+    /// By default, every check evaluates every instance. An instance which is badly broken may fail many checks
+    /// at once, which results in redundant logs and possibly conflicting policies. With this option, only the
+    /// first failed check which invalidates or purges the instance in each check pass applies its policy.
+    /// Checks of `T` are evaluated in the order in which they were added, so the first failed check is deterministic.
+    ///
+    /// Note that repairs do not short-circuit, since the instance may still be valid after the repair.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Apple;
+    ///
+    /// #[derive(Component)]
+    /// struct Fresh;
+    ///
+    /// #[derive(Component)]
+    /// struct Ripe;
+    ///
+    /// let mut app = App::new();
+    /// app.check::<Apple, Without<Fresh>>(purge())
+    ///     .check::<Apple, Without<Ripe>>(invalid())
+    ///     .short_circuit_checks::<Apple>();
+    /// ```
+    fn short_circuit_checks<T: Kind>(&mut self) -> &mut Self;
+
+    /// Adds a new checked requirement which asserts that every instance of `T` has all components of bundle `B`.
+    ///
+    /// See [`repair_complete_bundle`] to insert the default values of any missing components.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Player;
+    ///
+    /// #[derive(Component, Default)]
+    /// struct Health(u32);
+    ///
+    /// #[derive(Component, Default)]
+    /// struct Inventory;
+    ///
+    /// #[derive(Bundle, Default)]
+    /// struct PlayerBundle {
+    ///     health: Health,
+    ///     inventory: Inventory,
+    /// }
+    ///
+    /// let mut app = App::new();
+    /// app.check_bundle::<Player, PlayerBundle>(repair_complete_bundle::<PlayerBundle>());
+    /// ```
+    fn check_bundle<T: Kind, B: Bundle>(&mut self, _: impl Into<CheckConfig>) -> &mut Self;
+
+    /// Adds all checks declared by [`Checks`] type `T`.
+    ///
+    /// See [`Checks`] for details.
+    fn add_checks_for<T: Checks>(&mut self) -> &mut Self;
+}
+
+mod sealed {
+    use bevy_app::{App, SubApp};
+
+    /// Anything which [`Check`](super::Check) may add checks to.
+    pub trait CheckApp {
+        fn sub_app(&mut self) -> &mut SubApp;
+    }
+
+    impl CheckApp for SubApp {
+        fn sub_app(&mut self) -> &mut SubApp {
+            self
+        }
+    }
+
+    impl CheckApp for App {
+        fn sub_app(&mut self) -> &mut SubApp {
+            self.main_mut()
+        }
+    }
+}
+
+impl<A: sealed::CheckApp> Check for A {
+    fn check<T: Kind, F: CheckFilter>(&mut self, config: impl Into<CheckConfig>) -> &mut Self {
+        self.check_on_spawn::<T, F>(config)
+    }
+
+    fn require<T: Kind, U: Component>(&mut self, config: impl Into<CheckConfig>) -> &mut Self {
+        self.check::<T, Without<U>>(config)
+    }
+
+    fn debug_check<T: Kind, F: CheckFilter>(
+        &mut self,
+        config: impl Into<CheckConfig>,
+    ) -> &mut Self {
+        if cfg!(debug_assertions) || cfg!(feature = "debug_checks") {
+            self.check::<T, F>(config)
+        } else {
+            self
+        }
+    }
+
+    fn check_on_spawn<T: Kind, F: CheckFilter>(
+        &mut self,
+        config: impl Into<CheckConfig>,
+    ) -> &mut Self {
+        let spec = CheckSpec::filter::<F>();
+        add_check::<T, F>(self.sub_app(), config.into(), spec);
+        self
+    }
+
+    fn check_fixed<T: Kind, F: CheckFilter>(
+        &mut self,
+        config: impl Into<CheckConfig>,
+    ) -> &mut Self {
+        let mut config = config.into();
+        config.schedule = Some(FixedPreUpdate.intern());
+        add_check::<T, F>(self.sub_app(), config, CheckSpec::filter::<F>());
+        self
+    }
+
+    fn check_on_load<T: Kind, F: CheckFilter>(
+        &mut self,
+        config: impl Into<CheckConfig>,
+    ) -> &mut Self {
+        let mut config = config.into();
+        config.gate = Some(Gate::Load);
+        add_check::<T, F>(self.sub_app(), config, CheckSpec::filter::<F>());
+        self
+    }
+
+    fn migrate_before<T: Kind, F: CheckFilter>(
+        &mut self,
+        version: u32,
+        config: impl Into<CheckConfig>,
+    ) -> &mut Self {
+        let mut config = config.into();
+        config.gate = Some(Gate::Migration(version));
+        add_check::<T, F>(self.sub_app(), config, CheckSpec::filter::<F>());
+        self
+    }
+
+    fn check_static<T: Kind, F: CheckFilter, P: PolicyImpl>(&mut self, policy: P) -> &mut Self {
+        let app = self.sub_app();
+        let system = check_static_system::<T, F, P>(app, policy);
+        add_check_systems(app, check_schedule(app), system);
+        self
+    }
+
+    fn check_parent<T: Kind, P: Component>(&mut self, config: impl Into<CheckConfig>) -> &mut Self {
+        let spec = CheckSpec {
+            description: format!(
+                "Parent: {}",
+                moonshine_util::get_short_name(std::any::type_name::<P>())
+            ),
+            condition: Some(condition(|entity: EntityRef, world: &World| {
+                !entity
+                    .get::<Parent>()
+                    .and_then(|parent| world.get_entity(parent.get()).ok())
+                    .is_some_and(|parent| parent.contains::<P>())
+            })),
+        };
+        add_check::<T, ()>(self.sub_app(), config.into(), spec);
+        self
+    }
+
+    fn check_children<T: Kind, C: Component>(
+        &mut self,
+        min_count: usize,
+        config: impl Into<CheckConfig>,
+    ) -> &mut Self {
+        let spec = CheckSpec {
+            description: format!(
+                "Children: {} >= {min_count}",
+                moonshine_util::get_short_name(std::any::type_name::<C>())
+            ),
+            condition: Some(condition(move |entity: EntityRef, world: &World| {
+                count_children::<C>(entity, world) < min_count
+            })),
+        };
+        add_check::<T, ()>(self.sub_app(), config.into(), spec);
+        self
+    }
+
+    fn check_dangling_parents(&mut self, config: impl Into<CheckConfig>) -> &mut Self {
+        let spec = CheckSpec {
+            description: "Parent does not exist".to_owned(),
+            condition: Some(condition(|entity: EntityRef, world: &World| {
+                entity
+                    .get::<Parent>()
+                    .is_some_and(|parent| world.get_entity(parent.get()).is_err())
+            })),
+        };
+        add_check::<Parent, ()>(self.sub_app(), config.into(), spec);
+        self
+    }
+
+    fn check_hierarchy_cycles(&mut self, config: impl Into<CheckConfig>) -> &mut Self {
+        let spec = CheckSpec {
+            description: "Hierarchy contains a cycle".to_owned(),
+            condition: Some(condition(|entity: EntityRef, world: &World| {
+                find_cycle(entity.id(), world).is_some_and(|cycle| cycle == entity.id())
+            })),
+        };
+        add_check::<Parent, ()>(self.sub_app(), config.into(), spec);
+        self
+    }
+
+    fn check_max_depth<T: Kind>(
+        &mut self,
+        max_depth: usize,
+        config: impl Into<CheckConfig>,
+    ) -> &mut Self {
+        let spec = CheckSpec {
+            description: format!("Depth > {max_depth}"),
+            condition: Some(condition(move |entity: EntityRef, world: &World| {
+                let mut depth = 0;
+                let mut current = entity.id();
+                while let Some(parent) = world.get::<Parent>(current).map(Parent::get) {
+                    depth += 1;
+                    // NOTE: Stop early, since the hierarchy may be arbitrarily deep or contain cycles.
+                    if depth > max_depth {
+                        return true;
+                    }
+                    current = parent;
+                }
+                false
+            })),
+        };
+        add_check::<T, ()>(self.sub_app(), config.into(), spec);
+        self
+    }
+
+    fn check_unique<T: Kind>(&mut self, config: impl Into<CheckConfig>) -> &mut Self {
+        let valid = ConditionQuery::<Instance<T>, Valid>::default();
+        let unchecked = ConditionQuery::<Instance<T>, Pending<T>>::default();
+        let spec = CheckSpec {
+            description: "Not unique".to_owned(),
+            condition: Some(condition(move |entity: EntityRef, world: &World| {
+                // NOTE: Keep any valid instance, otherwise keep the unchecked instance with the lowest ID.
+                let first = valid
+                    .with(world, |valid| {
+                        valid
+                            .iter_manual(world)
+                            .next()
+                            .map(|instance| instance.entity())
+                    })
+                    .flatten()
+                    .or_else(|| {
+                        unchecked
+                            .with(world, |unchecked| {
+                                unchecked
+                                    .iter_manual(world)
+                                    .map(|instance| instance.entity())
+                                    .min()
+                            })
+                            .flatten()
+                    });
+                first.is_some_and(|first| first != entity.id())
+            })),
+        };
+        add_check::<T, ()>(self.sub_app(), config.into(), spec);
+        self
+    }
+
+    fn check_count<T: Kind>(
+        &mut self,
+        range: impl RangeBounds<usize> + Debug + Send + Sync + 'static,
+        policy: CountPolicy,
+    ) -> &mut Self {
+        let check_name = format!(
+            "{}: Count {range:?}",
+            moonshine_util::get_short_name(std::any::type_name::<T>())
+        );
+        let mut last_count = None;
+        let app = self.sub_app();
+        app.add_event::<CountViolation>();
+        add_check_systems(
+            app,
+            check_schedule(app),
+            (move |query: Query<Instance<T>>,
+                   dry_run: Option<Res<DryRun>>,
+                   mut events: EventWriter<CountViolation>,
+                   mut commands: Commands| {
+                let count = query.iter().count();
+                if last_count.replace(count) == Some(count) || range.contains(&count) {
+                    return;
+                }
+
+                error!("{check_name} is violated: {count} instance(s)");
+                events.send(CountViolation {
+                    check: check_name.clone(),
+                    count,
+                });
+                match &policy {
+                    CountPolicy::Report => {}
+                    CountPolicy::Panic if dry_run.is_some() => {
+                        error!("{check_name} would panic: {count} instance(s)");
+                    }
+                    CountPolicy::Repair(_) if dry_run.is_some() => {
+                        warn!("{check_name} would be repaired.");
+                    }
+                    CountPolicy::Panic => {
+                        panic!("{check_name} is strictly violated: {count} instance(s)");
+                    }
+                    CountPolicy::Repair(f) => {
+                        f(count, &mut commands);
+                        warn!("{check_name} was repaired.");
+                        let kind = moonshine_util::get_short_name(std::any::type_name::<T>());
+                        let check = check_name.clone();
+                        commands.queue(move |world: &mut World| {
+                            let tick = world.read_change_tick();
+                            if let Some(mut journal) = world.get_resource_mut::<ValidationJournal>()
+                            {
+                                journal.push(JournalEntry {
+                                    entity: Entity::PLACEHOLDER,
+                                    kind,
+                                    check,
+                                    outcome: Outcome::Repaired,
+                                    tick,
+                                    snapshot: None,
+                                });
+                            }
+                        });
+                    }
+                }
+            })
+            .after(LoadSystem::Load)
+            .in_set(CheckSystems),
+        );
+        self
+    }
+
+    fn check_exclusive<T: Kind, E: ExclusiveComponents>(
+        &mut self,
+        config: impl Into<CheckConfig>,
+    ) -> &mut Self {
+        let spec = CheckSpec {
+            description: format!("Exclusive: {}", E::describe()),
+            condition: Some(condition(|entity: EntityRef, _: &World| {
+                E::count(entity) > 1
+            })),
+        };
+        add_check::<T, ()>(self.sub_app(), config.into(), spec);
+        self
+    }
+
+    fn check_pair<A: Component, B: Component>(
+        &mut self,
+        predicate: impl Fn(&A, &B) -> bool + Send + Sync + 'static,
+        config: impl Into<CheckConfig>,
+    ) -> &mut Self {
+        let spec = CheckSpec {
+            description: format!(
+                "Pair: {}, {}",
+                moonshine_util::get_short_name(std::any::type_name::<A>()),
+                moonshine_util::get_short_name(std::any::type_name::<B>())
+            ),
+            condition: Some(condition(move |entity: EntityRef, _: &World| {
+                let (Some(a), Some(b)) = (entity.get::<A>(), entity.get::<B>()) else {
+                    return false;
+                };
+                !predicate(a, b)
+            })),
+        };
+        add_check::<A, With<B>>(self.sub_app(), config.into(), spec);
+        self
+    }
+
+    fn check_unique_value<T: Kind, C: Component, K: Eq + Hash + Send + Sync + 'static>(
+        &mut self,
+        key: impl Fn(&C) -> K + Send + Sync + 'static,
+        config: impl Into<CheckConfig>,
+    ) -> &mut Self {
+        let valid = ConditionQuery::<(Instance<T>, &C), Valid>::default();
+        let unchecked = ConditionQuery::<(Instance<T>, &C), Pending<T>>::default();
+        let spec = CheckSpec {
+            description: format!(
+                "Unique: {}",
+                moonshine_util::get_short_name(std::any::type_name::<C>())
+            ),
+            condition: Some(condition(move |entity: EntityRef, world: &World| {
+                let Some(component) = entity.get::<C>() else {
+                    return false;
+                };
+                let value = key(component);
+                // NOTE: Keep any valid instance with the same key, or the unchecked instance with the lowest ID.
+                let owner = valid
+                    .with(world, |valid| {
+                        valid
+                            .iter_manual(world)
+                            .find(|(_, other)| key(other) == value)
+                            .map(|(instance, _)| instance.entity())
+                    })
+                    .flatten()
+                    .or_else(|| {
+                        unchecked
+                            .with(world, |unchecked| {
+                                unchecked
+                                    .iter_manual(world)
+                                    .filter(|(_, other)| key(other) == value)
+                                    .map(|(instance, _)| instance.entity())
+                                    .min()
+                            })
+                            .flatten()
+                    });
+                owner.is_some_and(|owner| owner != entity.id())
+            })),
+        };
+        add_check::<T, With<C>>(self.sub_app(), config.into(), spec);
+        self
+    }
+
+    fn check_entity_refs<T: Kind>(&mut self, config: impl Into<CheckConfig>) -> &mut Self {
+        let spec = CheckSpec {
+            description: "Dangling entity reference".to_owned(),
+            condition: Some(condition(|entity: EntityRef, world: &World| {
+                with_registry(world, |registry| {
+                    !dangling_components(entity, world, registry).is_empty()
+                })
+            })),
+        };
+        add_check::<T, ()>(self.sub_app(), config.into(), spec);
+        self
+    }
+
+    fn check_instance_field<T: Kind, C: Component, U: Kind>(
+        &mut self,
+        get: impl Fn(&C) -> Option<Instance<U>> + Send + Sync + 'static,
+        config: impl Into<CheckConfig>,
+    ) -> &mut Self {
+        let targets = ConditionQuery::<Instance<U>>::default();
+        let spec = CheckSpec {
+            description: format!(
+                "{} is not a {}",
+                moonshine_util::get_short_name(std::any::type_name::<C>()),
+                moonshine_util::get_short_name(std::any::type_name::<U>())
+            ),
+            condition: Some(condition(move |entity: EntityRef, world: &World| {
+                let Some(target) = entity.get::<C>().and_then(&get) else {
+                    return false;
+                };
+                !targets
+                    .with(world, |targets| {
+                        targets.get_manual(world, target.entity()).is_ok()
+                    })
+                    .unwrap_or(false)
+            })),
+        };
+        add_check::<T, With<C>>(self.sub_app(), config.into(), spec);
+        self
+    }
+
+    fn sweep_stale_references(&mut self, period: Duration) -> &mut Self {
+        let app = self.sub_app();
+        init(app);
+        let mut elapsed = Duration::ZERO;
+        add_check_systems(
+            app,
+            check_schedule(app),
+            (move |world: &mut World| {
+                elapsed += world
+                    .get_resource::<Time>()
+                    .map(|time| time.delta())
+                    .unwrap_or_default();
+                if elapsed < period {
+                    return;
+                }
+                elapsed = Duration::ZERO;
+                sweep_stale_references(world);
+            })
+            .after(LoadSystem::Load)
+            .after(CheckSystems),
+        );
+        self
+    }
+
+    fn escalate_invalid(&mut self, timeout: Duration, policy: WatchdogPolicy) -> &mut Self {
+        let app = self.sub_app();
+        init(app);
+        let mut last_run: Option<Duration> = None;
+        app.add_event::<InvalidTimeout>();
+        add_check_systems(
+            app,
+            check_schedule(app),
+            (move |query: Query<(Entity, &Invalid)>,
+                   time: Option<Res<Time>>,
+                   dry_run: Option<Res<DryRun>>,
+                   mut events: EventWriter<InvalidTimeout>,
+                   mut commands: Commands| {
+                let now = time.map(|time| time.elapsed()).unwrap_or_default();
+                for (entity, invalid) in query.iter() {
+                    let deadline = invalid.time + timeout;
+                    // NOTE: Only escalate each entity once, when its deadline is first reached.
+                    if deadline > now || last_run.is_some_and(|last_run| deadline <= last_run) {
+                        continue;
+                    }
+                    let age = now - invalid.time;
+                    events.send(InvalidTimeout {
+                        entity,
+                        reason: invalid.reason.clone(),
+                        age,
+                    });
+                    match policy {
+                        WatchdogPolicy::Report => {
+                            warn!("{entity} has been invalid for {age:?}: {}", invalid.reason);
+                        }
+                        WatchdogPolicy::Purge | WatchdogPolicy::Panic if dry_run.is_some() => {
+                            error!(
+                                "{entity} would be escalated after being invalid for {age:?}: {}",
+                                invalid.reason
+                            );
+                        }
+                        WatchdogPolicy::Purge => {
+                            error!(
+                                "{entity} is purged after being invalid for {age:?}: {}",
+                                invalid.reason
+                            );
+                            let check = invalid.reason.clone();
+                            commands.queue(move |world: &mut World| {
+                                journal_now(world, entity, "", &check, Outcome::Purged);
+                            });
+                            commands.entity(entity).despawn_recursive();
+                        }
+                        WatchdogPolicy::Panic => {
+                            panic!("{entity} has been invalid for {age:?}: {}", invalid.reason);
+                        }
+                    }
+                }
+                last_run = Some(now);
+            })
+            .after(LoadSystem::Load)
+            .after(CheckSystems),
+        );
+        self
+    }
+
+    fn check_required<T: Component>(&mut self, config: impl Into<CheckConfig>) -> &mut Self {
+        let world = self.sub_app().world_mut();
+        let id = world.register_component::<T>();
+        let components = world.components();
+        let required: Vec<ComponentId> = components
+            .get_info(id)
+            .map(|info| info.required_components().iter_ids().collect())
+            .unwrap_or_default();
+        let names: Vec<String> = required
+            .iter()
+            .filter_map(|id| components.get_info(*id))
+            .map(|info| moonshine_util::get_short_name(info.name()))
+            .collect();
+        let spec = CheckSpec {
+            description: format!("Required: [{}]", names.join(", ")),
+            condition: Some(condition(move |entity: EntityRef, _: &World| {
+                required.iter().any(|id| !entity.contains_id(*id))
+            })),
+        };
+        add_check::<T, ()>(self.sub_app(), config.into(), spec);
+        self
+    }
+
+    fn inherit_checks<A: Kind + CastInto<B>, B: Kind>(&mut self) -> &mut Self {
+        let app = self.sub_app();
+        app.init_resource::<Inherited<B>>();
+        add_check_systems(
+            app,
+            check_schedule(app),
+            (|query: Query<Instance<A>, Pending<A>>,
+              base: Query<Instance<B>>,
+              mut inherited: ResMut<Inherited<B>>| {
+                let instances = query
+                    .iter()
+                    .filter(|instance| !base.contains(instance.entity()))
+                    .map(|instance| instance.cast_into())
+                    .collect();
+                inherited.instances.insert(TypeId::of::<A>(), instances);
+            })
+            .after(LoadSystem::Load)
+            .before(CheckSystems),
+        );
+        self
+    }
+
+    fn check_bundle<T: Kind, B: Bundle>(&mut self, config: impl Into<CheckConfig>) -> &mut Self {
+        let spec = CheckSpec {
+            description: format!(
+                "Bundle: {}",
+                moonshine_util::get_short_name(std::any::type_name::<B>())
+            ),
+            condition: Some(condition(|entity: EntityRef, world: &World| {
+                let mut missing = false;
+                B::get_component_ids(world.components(), &mut |id| {
+                    // NOTE: Component is missing if it's not even registered.
+                    missing |= id.map_or(true, |id| !entity.contains_id(id));
+                });
+                missing
+            })),
+        };
+        add_check::<T, ()>(self.sub_app(), config.into(), spec);
+        self
+    }
+
+    fn short_circuit_checks<T: Kind>(&mut self) -> &mut Self {
+        let app = self.sub_app();
+        if app.world().contains_resource::<ShortCircuit<T>>() {
+            return self;
+        }
+        app.init_resource::<ShortCircuit<T>>();
+        // NOTE: Evaluate checks in order of registration, so the first failed check is deterministic.
+        let kind = TypeId::of::<T>();
+        let indices: Vec<usize> = app
+            .world()
+            .resource::<CheckRegistry>()
+            .checks
+            .iter()
+            .enumerate()
+            .filter(|(_, check)| check.kind == kind)
+            .map(|(index, _)| index)
+            .collect();
+        for index in indices {
+            order_after_previous(app, index);
+        }
+        add_check_systems(
+            app,
+            check_schedule(app),
+            (|short_circuit: Res<ShortCircuit<T>>| short_circuit.reset())
+                .after(LoadSystem::Load)
+                .before(CheckSystems),
+        );
+        self
+    }
+
+    fn add_checks_for<T: Checks>(&mut self) -> &mut Self {
+        T::add_checks(self.sub_app());
+        self
+    }
+}
+
+/// A [`Kind`] which declares its own checks.
+///
+/// # Usage
+///
+/// With the `derive` feature enabled, this trait may be derived using `#[derive(Check)]`.
+/// Each `#[check(...)]` attribute declares a single check, using `requires(...)`, `forbids(...)`, and `policy = "..."`.
+///
+/// The policy may be `"invalid"` (default), `"purge"`, `"panic"`, or any expression which evaluates to a [`Policy`].
+///
+/// # Example
+/// ```ignore
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// #[derive(Component, Check)]
+/// #[check(requires(Fresh), policy = "purge")]
+/// #[check(forbids(Rotten), policy = "repair_remove::<Rotten>()")]
+/// struct Apple;
+///
+/// #[derive(Component)]
+/// struct Fresh;
+///
+/// #[derive(Component)]
+/// struct Rotten;
+///
+/// let mut app = App::new();
+/// app.add_checks_for::<Apple>();
+/// ```
+pub trait Checks: Kind {
+    /// Adds all checks of this [`Kind`] to the given [`SubApp`].
+    fn add_checks(app: &mut SubApp);
+}
+
+/// A tuple of mutually exclusive components. See [`check_exclusive`](Check::check_exclusive).
+///
+/// The order of components in the tuple defines their priority, from highest to lowest.
+pub trait ExclusiveComponents: 'static + Send + Sync {
+    /// Returns the number of components in this set which are present on the given entity.
+    fn count(entity: EntityRef) -> usize;
+
+    /// Removes all components in this set from the given entity, except the one with the highest priority.
+    fn retain_first(entity: EntityRef, commands: &mut EntityCommands);
+
+    /// Returns a comma-separated list of all components in this set.
+    fn describe() -> String;
+}
+
+macro_rules! impl_exclusive_components {
+    ($($T:ident),+) => {
+        impl<$($T: Component),+> ExclusiveComponents for ($($T,)+) {
+            fn count(entity: EntityRef) -> usize {
+                0 $(+ entity.contains::<$T>() as usize)+
+            }
+
+            #[allow(unused_assignments)]
+            fn retain_first(entity: EntityRef, commands: &mut EntityCommands) {
+                let mut found = false;
+                $(
+                    if entity.contains::<$T>() {
+                        if found {
+                            commands.remove::<$T>();
+                        }
+                        found = true;
+                    }
+                )+
+            }
+
+            fn describe() -> String {
+                [$(moonshine_util::get_short_name(std::any::type_name::<$T>())),+].join(", ")
+            }
+        }
+    };
+}
+
+impl_exclusive_components!(A, B);
+impl_exclusive_components!(A, B, C);
+impl_exclusive_components!(A, B, C, D);
+impl_exclusive_components!(A, B, C, D, E);
+impl_exclusive_components!(A, B, C, D, E, F);
+impl_exclusive_components!(A, B, C, D, E, F, G);
+impl_exclusive_components!(A, B, C, D, E, F, G, H);
+
+/// Adds multiple checks to an [`App`] using a declarative syntax.
+///
+/// # Usage
+///
+/// Each rule has the form `Kind: <conditions> => <policy>;`, where each condition is one of:
+/// - `requires A` or `requires (A, B, ...)`: The check fails if any of the given components are missing.
+/// - `forbids A` or `forbids (A, B, ...)`: The check fails if any of the given components are present.
+///
+/// If multiple conditions are given, the check fails if any of them fail.
+///
+/// The policy may be `invalid`, `purge`, `panic`, or any expression which evaluates to a [`Policy`].
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Apple;
+///
+/// #[derive(Component)]
+/// struct Fresh;
+///
+/// #[derive(Component)]
+/// struct Crate;
+///
+/// #[derive(Component)]
+/// struct Lid;
+///
+/// #[derive(Component, Default)]
+/// struct Label;
+///
+/// let mut app = App::new();
+/// checks!(app, {
+///     Apple: requires Fresh => purge;
+///     Crate: requires (Lid, Label) => repair_insert_default::<Label>();
+/// });
+/// ```
+#[macro_export]
+macro_rules! checks {
+    ($app:expr, { $($body:tt)* }) => {{
+        use $crate::Check as _;
+        $crate::__checks!($app; $($body)*);
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __checks {
+    ($app:expr;) => {};
+    ($app:expr; $kind:ty : $($rule:ident $types:tt)+ => invalid; $($rest:tt)*) => {
+        $crate::__checks!($app; $kind : $($rule $types)+ => $crate::invalid(); $($rest)*);
+    };
+    ($app:expr; $kind:ty : $($rule:ident $types:tt)+ => purge; $($rest:tt)*) => {
+        $crate::__checks!($app; $kind : $($rule $types)+ => $crate::purge(); $($rest)*);
+    };
+    ($app:expr; $kind:ty : $($rule:ident $types:tt)+ => panic; $($rest:tt)*) => {
+        $crate::__checks!($app; $kind : $($rule $types)+ => $crate::panic(); $($rest)*);
+    };
+    ($app:expr; $kind:ty : $($rule:ident $types:tt)+ => $policy:expr; $($rest:tt)*) => {
+        $app.check::<$kind, $crate::__check_filter!($($rule $types)+)>($policy);
+        $crate::__checks!($app; $($rest)*);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __check_filter {
+    ($rule:ident $types:tt) => {
+        $crate::__check_condition!($rule $types)
+    };
+    ($($rule:ident $types:tt)+) => {
+        $crate::__private::Or<($($crate::__check_condition!($rule $types),)+)>
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __check_condition {
+    (requires ($($t:ty),+ $(,)?)) => {
+        $crate::__private::Or<($($crate::__private::Without<$t>,)+)>
+    };
+    (requires $t:ty) => {
+        $crate::__private::Without<$t>
+    };
+    (forbids ($($t:ty),+ $(,)?)) => {
+        $crate::__private::Or<($($crate::__private::With<$t>,)+)>
+    };
+    (forbids $t:ty) => {
+        $crate::__private::With<$t>
+    };
+}
+
+fn check_system<T: Kind, F: CheckFilter>(app: &mut SubApp, config: CheckConfig) -> SystemConfigs {
+    check_system_with::<T, F>(app, config, CheckSpec::filter::<F>())
+}
+
+/// Returns the number of children of the given `entity` with component `C`.
+fn count_children<C: Component>(entity: EntityRef, world: &World) -> usize {
+    entity.get::<Children>().map_or(0, |children| {
+        children
+            .iter()
+            .filter(|child| {
+                world
+                    .get_entity(**child)
+                    .is_ok_and(|child| child.contains::<C>())
+            })
+            .count()
+    })
+}
+
+/// Returns the lowest entity in the hierarchy cycle which contains the given `entity`, if any.
+fn find_cycle(entity: Entity, world: &World) -> Option<Entity> {
+    let mut visited = HashSet::new();
+    let mut lowest = entity;
+    let mut current = entity;
+    while let Some(parent) = world.get::<Parent>(current).map(Parent::get) {
+        if parent == entity {
+            return Some(lowest);
+        }
+        if !visited.insert(parent) {
+            // NOTE: Entity is a descendant of a cycle, but not a part of it.
+            return None;
+        }
+        lowest = lowest.min(parent);
+        current = parent;
+    }
+    None
+}
+
+/// Describes the failure condition of a check, in addition to its [`CheckFilter`].
+struct CheckSpec {
+    /// Short description of the failure condition, used as the default failure reason.
+    description: String,
+    /// An additional condition which must be true for an instance to fail the check.
+    condition: Option<Condition>,
+}
+
+impl CheckSpec {
+    /// Returns a spec for a check which fails only based on its [`CheckFilter`].
+    fn filter<F: CheckFilter>() -> Self {
+        Self {
+            description: moonshine_util::get_short_name(std::any::type_name::<F>()),
+            condition: None,
+        }
+    }
+}
+
+/// A custom condition which returns true if an instance fails a check.
+type Condition = Arc<dyn Fn(EntityRef, &World) -> bool + Send + Sync>;
+
+fn condition(f: impl Fn(EntityRef, &World) -> bool + Send + Sync + 'static) -> Condition {
+    Arc::new(f)
+}
+
+/// A cached [`QueryState`] which may be used within a [`Condition`], which only has access to a `&World`.
+///
+/// Conditions may be evaluated outside of any check system (see [`ApplyChecks`]), so any state they depend on
+/// must be queried from the world when they are evaluated.
+struct ConditionQuery<D: ReadOnlyQueryData, F: QueryFilter = ()> {
+    state: RwLock<Option<(WorldId, QueryState<D, F>)>>,
+}
+
+impl<D: ReadOnlyQueryData, F: QueryFilter> Default for ConditionQuery<D, F> {
+    fn default() -> Self {
+        Self {
+            state: RwLock::new(None),
+        }
+    }
+}
+
+impl<D: ReadOnlyQueryData, F: QueryFilter> ConditionQuery<D, F> {
+    /// Calls `f` with the query state of the given world.
+    ///
+    /// Returns `None` if the query components are not registered, and so the query matches no entities.
+    fn with<R>(&self, world: &World, f: impl FnOnce(&QueryState<D, F>) -> R) -> Option<R> {
+        {
+            let mut state = self.state.write().unwrap();
+            match &mut *state {
+                Some((id, state)) if *id == world.id() => state.update_archetypes(world),
+                _ => *state = Some((world.id(), world.try_query_filtered::<D, F>()?)),
+            }
+        }
+        let state = self.state.read().unwrap();
+        state.as_ref().map(|(_, state)| f(state))
+    }
+}
+
+/// Adds a check to its schedule, which is [`check_schedule`] unless the check overrides it.
+fn add_check<T: Kind, F: CheckFilter>(app: &mut SubApp, config: CheckConfig, spec: CheckSpec) {
+    let schedule = config.schedule.unwrap_or_else(|| check_schedule(app));
+    let system = check_system_with::<T, F>(app, config, spec);
+    add_check_systems(app, schedule, system);
+}
+
+/// Adds the given systems to the schedule, unless checks are disabled.
+pub(crate) fn add_check_systems<M>(
+    app: &mut SubApp,
+    schedule: impl ScheduleLabel,
+    systems: impl IntoSystemConfigs<M>,
+) {
+    // NOTE: All check systems are added through here, so the `disable` feature only needs to be handled once.
+    if cfg!(feature = "disable") {
+        return;
+    }
+    app.add_systems(schedule, systems);
+}
+
+fn check_system_with<T: Kind, F: CheckFilter>(
+    app: &mut SubApp,
+    config: CheckConfig,
+    spec: CheckSpec,
+) -> SystemConfigs {
+    let CheckSpec {
+        description,
+        condition,
+    } = spec;
+    let CheckConfig {
+        policy,
+        mut hooks,
+        revalidate,
+        watches,
+        log_level,
+        formatter,
+        inherit,
+        lenient,
+        labels,
+        group,
+        before,
+        after,
+        severity,
+        schedule,
+        gate,
+    } = config;
+    let schedule = schedule.unwrap_or_else(|| check_schedule(app));
+    let repair_log_level = log_level.map(|level| {
+        if level == Level::ERROR {
+            Level::WARN
+        } else {
+            level
+        }
+    });
+    if !hooks.is_empty() {
+        app.add_event::<OutcomeSnapshot>();
+    }
+    if let Some(period) = revalidate {
+        add_check_systems(
+            app,
+            schedule,
+            revalidate_system::<T>(period)
+                .run_if(not(resource_exists::<DryRun>))
+                .after(LoadSystem::Load)
+                .before(CheckSystems),
+        );
+    }
+    let kind_name = moonshine_util::get_short_name(std::any::type_name::<T>());
+    let check_name = format!("{kind_name}: {description}");
+    let index = register::<T, F>(
+        app,
+        check_name.clone(),
+        condition.clone(),
+        Some(policy.clone()),
+        labels,
+        group,
+        severity,
+    );
+    let mut registry = app.world_mut().resource_mut::<CheckRegistry>();
+    let entry = &mut registry.checks[index];
+    entry.schedule = schedule;
+    entry.gate = gate;
+    entry.description = description.clone();
+    entry.log_level = log_level;
+    entry.formatter = formatter.clone();
+    entry.lenient = lenient;
+    if app.world().contains_resource::<ShortCircuit<T>>() {
+        order_after_previous(app, index);
+    }
+    if !watches.is_empty() {
+        let watched = Watched::default();
+        for watch in watches {
+            add_check_systems(
+                app,
+                schedule,
+                watch(watched.clone())
+                    .after(LoadSystem::Load)
+                    .in_set(WatchSet(index)),
+            );
+        }
+        add_check_systems(
+            app,
+            schedule,
+            watch_system::<T>(watched)
+                .run_if(not(resource_exists::<DryRun>))
+                .after(WatchSet(index))
+                .before(CheckSystems),
+        );
+    }
+    let accepted = Accepted::default();
+    if condition.is_none() && gate.is_none() {
+        // NOTE: Custom conditions may depend on other entities, so structural changes can't be detected.
+        // Gated checks only apply to loaded data, so they must not be applied to any changes after the load.
+        add_check_systems(
+            app,
+            schedule,
+            recheck_system::<T, F>(accepted.clone())
+                .run_if(check_enabled(index))
+                .run_if(not(resource_exists::<DryRun>))
+                .after(LoadSystem::Load)
+                .before(CheckSystems),
+        );
+    }
+    let mut reported = HashSet::new();
+    let fails = move |entity: Entity, check: &Query<(), F>, world: &World| {
+        check.contains(entity)
+            && condition
+                .as_ref()
+                .map_or(true, |condition| condition(world.entity(entity), world))
+    };
+    let system = (move |query: Query<Instance<T>, Pending<T>>,
+                        check: Query<(), F>,
+                        world: &World,
+                        mut commands: Commands| {
+        let span = info_span!(
+            "check",
+            kind = kind_name.as_str(),
+            filter = description.as_str(),
+            count = field::Empty
+        );
+        let _span = span.enter();
+
+        if gate.is_some_and(|gate| !gate.applies(world)) {
+            // NOTE: Instances pass any check which does not apply to them, so they're not evaluated again.
+            if !world.contains_resource::<DryRun>() {
+                let inherited = world.get_resource::<Inherited<T>>().filter(|_| inherit);
+                let checked: Vec<_> = query
+                    .iter()
+                    .chain(inherited.into_iter().flat_map(Inherited::iter))
+                    .map(|instance| (instance.entity(), Checked))
+                    .collect();
+                commands.try_insert_batch(checked);
+            }
+            return;
+        }
+
+        hooks.iter_mut().for_each(SnapshotHook::reset);
+        let mut stats = CheckStats::default();
+        let budget = world.get_resource::<CheckBudget>();
+        let start = Instant::now();
+
+        // Find all instances which pass or fail the check:
+        let mut passed = Vec::new();
+        let mut failed = Vec::new();
+        if let Some(budget) = budget {
+            for (i, instance) in query.iter().enumerate() {
+                if i % CheckBudget::INTERVAL == 0 && budget.is_exhausted(start.elapsed()) {
+                    debug!("check budget is exhausted; remaining instances are deferred.");
+                    budget.defer(query.iter().skip(i).map(|instance| instance.entity()));
+                    break;
+                }
+                if fails(instance.entity(), &check, world) {
+                    failed.push(instance);
+                } else {
+                    passed.push(instance);
+                }
+            }
+        } else if ComputeTaskPool::try_get().is_some() {
+            let mut results = Parallel::<(Vec<Instance<T>>, Vec<Instance<T>>)>::default();
+            query.par_iter().for_each(|instance| {
+                let mut results = results.borrow_local_mut();
+                if fails(instance.entity(), &check, world) {
+                    results.1.push(instance);
+                } else {
+                    results.0.push(instance);
+                }
+            });
+            for (local_passed, local_failed) in results.iter_mut() {
+                passed.append(local_passed);
+                failed.append(local_failed);
+            }
+            // NOTE: Sort to keep the order of commands and logs deterministic.
+            passed.sort_unstable_by_key(|instance| instance.entity());
+            failed.sort_unstable_by_key(|instance| instance.entity());
+        } else {
+            for instance in query.iter() {
+                if fails(instance.entity(), &check, world) {
+                    failed.push(instance);
+                } else {
+                    passed.push(instance);
+                }
+            }
+        }
+
+        if let Some(inherited) = world.get_resource::<Inherited<T>>().filter(|_| inherit) {
+            for instance in inherited.iter() {
+                if fails(instance.entity(), &check, world) {
+                    failed.push(instance);
+                } else {
+                    passed.push(instance);
+                }
+            }
+        }
+
+        if severity == Severity::Warning {
+            // NOTE: Instances which fail a warning are still valid.
+            let dry_run = world.contains_resource::<DryRun>();
+            for instance in failed.drain(..) {
+                if !dry_run || !reported.contains(&instance.entity()) {
+                    let on_failure = world.get_resource::<OnCheckFailure>();
+                    if log_level.is_some() || on_failure.is_some() {
+                        let entity = world.entity(instance.entity());
+                        let reason =
+                            describe(formatter.as_ref(), world, entity, &check_name, &description);
+                        if log_level.is_some() {
+                            warn!("{instance:?} is suspicious: {reason}");
+                        }
+                        if let Some(on_failure) = on_failure {
+                            (on_failure.0)(&CheckFailure {
+                                entity: instance.entity(),
+                                kind: kind_name.clone(),
+                                check: check_name.clone(),
+                                reason,
+                                severity: Severity::Warning,
+                                outcome: Some(Outcome::Valid),
+                            });
+                        }
+                    }
+                    stats.warned += 1;
+                }
+                accept(&accepted, instance.entity());
+                passed.push(instance);
+            }
+        }
+
+        if !world.contains_resource::<DryRun>() {
+            reported.clear();
+        } else {
+            reported.retain(|&entity| world.entities().contains(entity));
+            // NOTE: Report each instance only once, since they're never marked as checked.
+            for instance in passed {
+                if reported.insert(instance.entity()) {
+                    stats.record(Outcome::Valid);
+                }
+            }
+            for instance in failed {
+                if !reported.insert(instance.entity()) {
+                    continue;
+                }
+                let entity = world.entity(instance.entity());
+                let reason = describe(formatter.as_ref(), world, entity, &check_name, &description);
+                match planned_outcome(&policy) {
+                    Some(outcome) => {
+                        log!(log_level, "{instance:?} would be {outcome:?}: {reason}");
+                        stats.record(outcome);
+                        notify(&mut hooks, &check_name, outcome, entity, &mut commands);
+                    }
+                    None => {
+                        log!(log_level, "{instance:?} would panic: {reason}");
+                        stats.record(Outcome::Invalid);
+                    }
+                }
+            }
+            if let Some(budget) = budget {
+                budget.spend(start.elapsed());
+            }
+            span.record("count", stats.checked);
+            if stats.checked > 0 {
+                commands.queue(move |world: &mut World| {
+                    world.resource_mut::<CheckReport>().checks[index].1 = stats;
+                });
+            }
+            return;
+        }
+
+        if !passed.is_empty() {
+            commands.try_insert_batch(
+                passed
+                    .iter()
+                    .map(|instance| (instance.entity(), Checked))
+                    .collect::<Vec<_>>(),
+            );
+        }
+        for instance in passed {
+            if log_level.is_some() {
+                debug!("{instance:?} is valid.");
+            }
+            stats.record(Outcome::Valid);
+            let entity = world.entity(instance.entity());
+            notify(&mut hooks, &check_name, Outcome::Valid, entity, &mut commands);
+        }
+
+        let mut invalidated = Vec::new();
+        let mut purged = Vec::new();
+        let fallback = Policy::Invalid;
+        let panic = Policy::Panic;
+        let strict = !lenient && world.get_resource::<StrictChecks>().is_some_and(|s| s.0);
+        let short_circuit = world.get_resource::<ShortCircuit<T>>();
+        let on_failure = world.get_resource::<OnCheckFailure>();
+        for instance in failed {
+            // NOTE: Claim the instance before applying any policy, since other checks may run in parallel.
+            let exclusive = matches!(
+                planned_outcome(&policy),
+                Some(Outcome::Invalid | Outcome::Purged)
+            );
+            if short_circuit
+                .is_some_and(|short_circuit| !short_circuit.claim(instance.entity(), exclusive))
+            {
+                debug!("{instance:?} has already failed another check.");
+                continue;
+            }
+
+            // NOTE: Snapshots are taken before the policy is applied.
+            let entity = world.entity(instance.entity());
+            let reason = if log_level.is_some()
+                || strict
+                || on_failure.is_some()
+                || world.contains_resource::<ValidationLog>()
+                || matches!(policy, Policy::Panic)
+            {
+                describe(formatter.as_ref(), world, entity, &check_name, &description)
+            } else {
+                String::new()
+            };
+            if let Some(on_failure) = on_failure {
+                (on_failure.0)(&CheckFailure {
+                    entity: instance.entity(),
+                    kind: kind_name.clone(),
+                    check: check_name.clone(),
+                    reason: reason.clone(),
+                    severity: Severity::Error,
+                    outcome: if strict && exclusive {
+                        None
+                    } else {
+                        planned_outcome(&policy)
+                    },
+                });
+            }
+            let mut policy = &policy;
+            // NOTE: Loop to apply the fallback policy of any failed repairs.
+            loop {
+                while let Policy::Dump {
+                    path,
+                    recursive,
+                    then,
+                } = policy
+                {
+                    let path = path.clone();
+                    let recursive = *recursive;
+                    let entity = instance.entity();
+                    commands.queue(move |world: &mut World| {
+                        write_dump(world, entity, &path, recursive);
+                    });
+                    policy = then.as_ref();
+                }
+                if strict
+                    && matches!(
+                        policy,
+                        Policy::Invalid | Policy::Purge | Policy::PurgeAfter(_)
+                    )
+                {
+                    policy = &panic;
+                }
+                match policy {
+                    Policy::Invalid => {
+                        invalidated.push((
+                            instance.entity(),
+                            (Checked, Invalid::new(&check_name, world)),
+                        ));
+                        add_invalid_reason(
+                            instance.entity(),
+                            &check_name,
+                            &description,
+                            world,
+                            &mut commands,
+                        );
+                        log_action(
+                            world,
+                            instance.entity(),
+                            &check_name,
+                            Outcome::Invalid,
+                            format!("{instance:?} is invalid: {reason}"),
+                            &mut commands,
+                        );
+                        log!(log_level, "{instance:?} is invalid: {reason}");
+                        stats.record(Outcome::Invalid);
+                        notify(&mut hooks, &check_name, Outcome::Invalid, entity, &mut commands);
+                    }
+                    Policy::Purge => {
+                        #[cfg(feature = "graveyard")]
+                        if world.contains_resource::<Graveyard>() {
+                            let entity = instance.entity();
+                            let check = check_name.clone();
+                            commands.queue(move |world: &mut World| bury(world, entity, check));
+                        }
+                        purged.push(instance.entity());
+                        record(
+                            world,
+                            entity,
+                            &kind_name,
+                            &check_name,
+                            Outcome::Purged,
+                            &mut commands,
+                        );
+                        log_action(
+                            world,
+                            instance.entity(),
+                            &check_name,
+                            Outcome::Purged,
+                            format!("{instance:?} is purged: {reason}"),
+                            &mut commands,
+                        );
+                        log!(log_level, "{instance:?} is purged: {reason}");
+                        stats.record(Outcome::Purged);
+                        notify(&mut hooks, &check_name, Outcome::Purged, entity, &mut commands);
+                    }
+                    Policy::PurgeAfter(delay) => {
+                        commands.entity(instance.entity()).try_insert((
+                            Checked,
+                            Invalid::new(&check_name, world),
+                            PendingPurge::new(&kind_name, &check_name, *delay, world),
+                        ));
+                        add_invalid_reason(
+                            instance.entity(),
+                            &check_name,
+                            &description,
+                            world,
+                            &mut commands,
+                        );
+                        log_action(
+                            world,
+                            instance.entity(),
+                            &check_name,
+                            Outcome::Invalid,
+                            format!("{instance:?} is invalid and will be purged after {delay:?}: {reason}"),
+                            &mut commands,
+                        );
+                        log!(
+                            log_level,
+                            "{instance:?} is invalid and will be purged after {delay:?}: {reason}"
+                        );
+                        stats.record(Outcome::Invalid);
+                        notify(&mut hooks, &check_name, Outcome::Invalid, entity, &mut commands);
+                    }
+                    Policy::Panic => panic_invalid(entity, world, &reason),
+                    Policy::Repair(fixer) => {
+                        if let Some(mut entity) = commands.get_entity(instance.entity()) {
+                            // Inset `Checked` before fixing to let the fixer remove it if needed
+                            entity.try_insert(Checked);
+                            log!(log_level, "{instance:?} is invalid: {reason}");
+                        } else {
+                            break;
+                        }
+                        if let Err(error) = fixer.try_fix(entity, &mut commands) {
+                            log!(log_level, "{instance:?} could not be repaired: {error}");
+                            stats.repair_failed += 1;
+                            commands.send_event(RepairFailed {
+                                entity: instance.entity(),
+                                check: check_name.clone(),
+                                error,
+                            });
+                            policy = fixer.fallback().unwrap_or(&fallback);
+                            continue;
+                        }
+                        stats.record(Outcome::Repaired);
+                        accept(&accepted, instance.entity());
+                        record(
+                            world,
+                            entity,
+                            &kind_name,
+                            &check_name,
+                            Outcome::Repaired,
+                            &mut commands,
+                        );
+                        notify(&mut hooks, &check_name, Outcome::Repaired, entity, &mut commands);
+                        log_action(
+                            world,
+                            instance.entity(),
+                            &check_name,
+                            Outcome::Repaired,
+                            format!("{instance:?} was repaired: {reason}"),
+                            &mut commands,
+                        );
+                        log!(repair_log_level, "{instance:?} was repaired.");
+                    }
+                    Policy::Dump { .. } => unreachable!(),
+                }
+                break;
+            }
+        }
+
+        if !invalidated.is_empty() {
+            commands.try_insert_batch(invalidated);
+        }
+        if !purged.is_empty() {
+            // NOTE: Despawn all purged instances at once, after any other commands for them.
+            commands.queue(move |world: &mut World| {
+                for entity in purged {
+                    if let Ok(entity) = world.get_entity_mut(entity) {
+                        entity.despawn_recursive();
+                    }
+                }
+            });
+        }
+
+        if let Some(budget) = budget {
+            budget.spend(start.elapsed());
+        }
+
+        #[cfg(feature = "metrics")]
+        export_metrics(&kind_name, &check_name, &stats, start.elapsed());
+
+        span.record("count", stats.checked);
+        if stats.checked > 0 {
+            commands.queue(move |world: &mut World| {
+                world.resource_mut::<CheckReport>().checks[index].1 = stats;
+            });
+        }
+    })
+    .run_if(any_unchecked_or_inherited::<T>)
+    .run_if(check_enabled(index))
+    .after(LoadSystem::Load)
+    .in_set(CheckSystems)
+    .in_set(CheckSet::<T, F>::new())
+    .in_set(CheckIndex(index));
+    // NOTE: Ignore deferred commands, so that all checks are evaluated before any instances are marked as checked.
+    let system = before
+        .into_iter()
+        .fold(system, |system, set| system.before_ignore_deferred(set));
+    after
+        .into_iter()
+        .fold(system, |system, set| system.after_ignore_deferred(set))
+}
+
+/// Registers a new check and returns its index.
+fn register<T: Kind, F: CheckFilter>(
+    app: &mut SubApp,
+    check_name: String,
+    condition: Option<Condition>,
+    policy: Option<Policy>,
+    labels: Vec<String>,
+    group: Option<String>,
+    severity: Severity,
+) -> usize {
+    init(app);
+    let schedule = check_schedule(app);
+    let registry = app.world().resource::<CheckRegistry>();
+    let index = registry.checks.len();
+    let enabled = group
+        .as_ref()
+        .map_or(true, |group| !registry.disabled_groups.contains(group));
+    let identity = condition
+        .as_ref()
+        .map(|condition| Arc::as_ptr(condition) as *const () as usize);
+    let evaluate: Evaluate = match condition {
+        Some(condition) => {
+            let evaluate = evaluate::<T, F>();
+            Arc::new(move |world: &mut World, entity: Entity| {
+                if evaluate(world, entity)? {
+                    return Some(true);
+                }
+                Some(!condition(world.entity(entity), world))
+            })
+        }
+        None => evaluate::<T, F>(),
+    };
+    app.world_mut()
+        .resource_mut::<CheckRegistry>()
+        .checks
+        .push(CheckEntry {
+            name: check_name.clone(),
+            kind: TypeId::of::<T>(),
+            kind_name: std::any::type_name::<T>().into(),
+            filter: TypeId::of::<F>(),
+            filter_name: std::any::type_name::<F>().into(),
+            condition: identity,
+            policy: policy.as_ref().map_or(PolicyKind::Custom, PolicyKind::of),
+            labels,
+            group,
+            schedule,
+            enabled,
+            severity,
+            evaluate,
+            action: policy,
+            skip: skip_check::<T>,
+            pending: pending_instances::<T>(),
+            gate: None,
+            description: moonshine_util::get_short_name(std::any::type_name::<F>()),
+            log_level: Some(Level::ERROR),
+            formatter: None,
+            lenient: false,
+        });
+    app.world_mut()
+        .resource_mut::<CheckReport>()
+        .checks
+        .push((check_name, CheckStats::default()));
+    if app.world().contains_resource::<CheckLedger>() {
+        watch_ledger::<T, F>(app.world_mut());
+    }
+    index
+}
+
+fn check_static_system<T: Kind, F: CheckFilter, P: PolicyImpl>(
+    app: &mut SubApp,
+    policy: P,
+) -> SystemConfigs {
+    let kind_name = moonshine_util::get_short_name(std::any::type_name::<T>());
+    let check_name = check_name::<T, F>();
+    let index = register::<T, F>(
+        app,
+        check_name.clone(),
+        None,
+        None,
+        Vec::new(),
+        None,
+        Severity::Error,
+    );
+    let accepted = Accepted::default();
+    add_check_systems(
+        app,
+        check_schedule(app),
+        recheck_system::<T, F>(accepted.clone())
+            .run_if(check_enabled(index))
+            .run_if(not(resource_exists::<DryRun>))
+            .after(LoadSystem::Load)
+            .before(CheckSystems),
+    );
+    (move |query: Query<Instance<T>, Pending<T>>,
+           check: Query<(), F>,
+           world: &World,
+           mut commands: Commands| {
+        let mut stats = CheckStats::default();
+        for instance in query.iter() {
+            if !check.contains(instance.entity()) {
+                commands.entity(instance.entity()).try_insert(Checked);
+                stats.record(Outcome::Valid);
+                continue;
+            }
+
+            let entity = world.entity(instance.entity());
+            let outcome = policy.apply(entity, &check_name, &mut commands);
+            if matches!(outcome, Outcome::Repaired | Outcome::Purged) {
+                record(
+                    world,
+                    entity,
+                    &kind_name,
+                    &check_name,
+                    outcome,
+                    &mut commands,
+                );
+            }
+            if matches!(outcome, Outcome::Valid | Outcome::Repaired) {
+                accept(&accepted, instance.entity());
+            }
+            stats.record(outcome);
+        }
+
+        if stats.checked > 0 {
+            commands.queue(move |world: &mut World| {
+                world.resource_mut::<CheckReport>().checks[index].1 = stats;
+            });
+        }
+    })
+    .run_if(any_unchecked::<T>)
+    .run_if(check_enabled(index))
+    .after(LoadSystem::Load)
+    .in_set(CheckSystems)
+    .in_set(CheckSet::<T, F>::new())
+    .in_set(CheckIndex(index))
+}
+
+/// Returns a run condition which is true if the registered check with the given index is enabled.
+fn check_enabled(
+    index: usize,
+) -> impl Fn(Res<CheckRegistry>, Option<Res<CheckLedger>>) -> bool + Clone {
+    // NOTE: In marker-free mode, all checks are evaluated by the ledger instead.
+    move |registry: Res<CheckRegistry>, ledger: Option<Res<CheckLedger>>| {
+        ledger.is_none() && registry.checks[index].enabled
+    }
+}
+
+/// Exports the statistics of a single check pass using the [`metrics`] facade.
+#[cfg(feature = "metrics")]
+fn export_metrics(kind: &str, check: &str, stats: &CheckStats, elapsed: Duration) {
+    if stats.checked == 0 {
+        return;
+    }
+    let labels = vec![
+        metrics::Label::new("kind", kind.to_owned()),
+        metrics::Label::new("check", check.to_owned()),
+    ];
+    for (name, count) in [
+        ("moonshine_check_checked_total", stats.checked),
+        ("moonshine_check_invalid_total", stats.invalid),
+        ("moonshine_check_repaired_total", stats.repaired),
+        ("moonshine_check_purged_total", stats.purged),
+        ("moonshine_check_repair_failed_total", stats.repair_failed),
+        ("moonshine_check_warned_total", stats.warned),
+    ] {
+        metrics::counter!(name, labels.clone()).increment(count as u64);
+    }
+    metrics::histogram!("moonshine_check_duration_seconds", labels).record(elapsed.as_secs_f64());
+}
+
+/// Returns true if there are any unchecked instances of `T`, including any inherited instances.
+fn any_unchecked_or_inherited<T: Kind>(
+    query: Query<Instance<T>, Pending<T>>,
+    inherited: Option<Res<Inherited<T>>>,
+) -> bool {
+    !query.is_empty() || inherited.is_some_and(|inherited| inherited.iter().next().is_some())
+}
+
+/// Unchecked instances of other kinds which inherit the checks of kind `T`.
+///
+/// See [`Check::inherit_checks`].
+#[derive(Resource)]
+struct Inherited<T: Kind> {
+    instances: HashMap<TypeId, Vec<Instance<T>>>,
+}
+
+impl<T: Kind> Default for Inherited<T> {
+    fn default() -> Self {
+        Self {
+            instances: HashMap::new(),
+        }
+    }
+}
+
+impl<T: Kind> Inherited<T> {
+    fn iter(&self) -> impl Iterator<Item = Instance<T>> + '_ {
+        self.instances.values().flatten().copied()
+    }
+}
+
+/// Returns true if there are any unchecked instances of `T`.
+fn any_unchecked<T: Kind>(query: Query<Instance<T>, Pending<T>>) -> bool {
+    !query.is_empty()
+}
+
+/// Describes why the given `entity` failed a check, using either the given formatter, the global
+/// [`CheckFormatter`], or just the filter name.
+fn describe(
+    formatter: Option<&FormatFn>,
+    world: &World,
+    entity: EntityRef,
+    check: &str,
+    filter: &str,
+) -> String {
+    let Some(formatter) =
+        formatter.or_else(|| world.get_resource::<CheckFormatter>().map(|f| &f.0))
+    else {
+        return filter.to_owned();
+    };
+
+    with_registry(world, |registry| {
+        formatter(&FailureInfo {
+            entity,
+            world,
+            registry,
+            check,
+            filter,
+        })
+    })
+}
+
+/// Invokes the given function with the type registry of the world, or an empty registry if it doesn't exist.
+fn with_registry<R>(world: &World, f: impl FnOnce(&TypeRegistry) -> R) -> R {
+    match world.get_resource::<AppTypeRegistry>() {
+        Some(registry) => f(&registry.read()),
+        None => f(&TypeRegistry::empty()),
+    }
+}
+
+/// Returns a description of all components of the given `entity`, including their values if reflected.
+fn dump_components(entity: EntityRef, world: &World, registry: &TypeRegistry) -> String {
+    let mut components = Vec::new();
+    for id in entity.archetype().components() {
+        let Some(info) = world.components().get_info(id) else {
+            continue;
+        };
+        let name = moonshine_util::get_short_name(info.name());
+        let value = info
+            .type_id()
+            .and_then(|type_id| registry.get_type_data::<ReflectComponent>(type_id))
+            .and_then(|reflect| reflect.reflect(entity));
+        match value {
+            Some(value) => components.push(format!("{name}: {value:?}")),
+            None => components.push(name),
+        }
+    }
+    format!("[{}]", components.join(", "))
+}
+
+/// Panics with the given `reason` and a description of all components of the given strictly invalid `entity`.
+fn panic_invalid(entity: EntityRef, world: &World, reason: &str) -> ! {
+    let components = with_registry(world, |registry| dump_components(entity, world, registry));
+    panic!(
+        "{} is strictly invalid: {reason}\nComponents: {components}",
+        entity.id()
+    );
+}
+
+/// Marks all entities with any stale [`Entity`] references as invalid.
+fn sweep_stale_references(world: &mut World) {
+    let stale: Vec<(Entity, String)> = with_registry(world, |registry| {
+        world
+            .iter_entities()
+            .filter(|entity| !entity.contains::<Invalid>())
+            .filter_map(|entity| {
+                let components = dangling_components(entity, world, registry);
+                if components.is_empty() {
+                    return None;
+                }
+                let names: Vec<String> = components
+                    .into_iter()
+                    .filter_map(|id| world.components().get_info(id))
+                    .map(|info| moonshine_util::get_short_name(info.name()))
+                    .collect();
+                Some((entity.id(), format!("[{}]", names.join(", "))))
+            })
+            .collect()
+    });
+
+    for (entity, names) in stale {
+        invalidate(world, entity, "Stale reference", &names);
+    }
+}
+
+/// Returns all reflected components of the given `entity` which reference an entity that doesn't exist.
+fn dangling_components(
+    entity: EntityRef,
+    world: &World,
+    registry: &TypeRegistry,
+) -> Vec<ComponentId> {
+    entity
+        .archetype()
+        .components()
+        .filter(|id| {
+            world
+                .components()
+                .get_info(*id)
+                .and_then(|info| info.type_id())
+                .and_then(|type_id| registry.get_type_data::<ReflectComponent>(type_id))
+                .and_then(|reflect| reflect.reflect(entity))
+                .is_some_and(|value| {
+                    any_entity(value.as_partial_reflect(), &mut |target| {
+                        target != Entity::PLACEHOLDER && world.get_entity(target).is_err()
+                    })
+                })
+        })
+        .collect()
+}
+
+/// Returns clones of all reflected components of the given `template` which are missing from the given `entity`.
+fn template_components<T: Kind>(
+    template: EntityRef,
+    entity: EntityRef,
+    world: &World,
+    registry: &TypeRegistry,
+) -> Vec<(ReflectComponent, Box<dyn PartialReflect>)> {
+    let excluded = [
+        TypeId::of::<Template<T>>(),
+        TypeId::of::<Checked>(),
+        TypeId::of::<Invalid>(),
+        TypeId::of::<PendingPurge>(),
+        TypeId::of::<Parent>(),
+        TypeId::of::<Children>(),
+    ];
+    template
+        .archetype()
+        .components()
+        .filter(|id| !entity.contains_id(*id))
+        .filter_map(|id| {
+            let type_id = world.components().get_info(id)?.type_id()?;
+            if excluded.contains(&type_id) {
+                return None;
+            }
+            let reflect_component = registry.get_type_data::<ReflectComponent>(type_id)?;
+            let component = reflect_component.reflect(template)?.clone_value();
+            Some((reflect_component.clone(), component))
+        })
+        .collect()
+}
+
+/// Returns true if the given predicate is true for any [`Entity`] within the given reflected `value`.
+fn any_entity(value: &dyn PartialReflect, predicate: &mut dyn FnMut(Entity) -> bool) -> bool {
+    if let Some(entity) = value
+        .try_as_reflect()
+        .and_then(|value| value.downcast_ref::<Entity>())
+    {
+        return predicate(*entity);
+    }
+
+    match value.reflect_ref() {
+        ReflectRef::Struct(value) => value
+            .iter_fields()
+            .any(|field| any_entity(field, predicate)),
+        ReflectRef::TupleStruct(value) => value
+            .iter_fields()
+            .any(|field| any_entity(field, predicate)),
+        ReflectRef::Tuple(value) => value
+            .iter_fields()
+            .any(|field| any_entity(field, predicate)),
+        ReflectRef::List(value) => value.iter().any(|item| any_entity(item, predicate)),
+        ReflectRef::Array(value) => value.iter().any(|item| any_entity(item, predicate)),
+        ReflectRef::Map(value) => value
+            .iter()
+            .any(|(key, item)| any_entity(key, predicate) || any_entity(item, predicate)),
+        ReflectRef::Set(value) => value.iter().any(|item| any_entity(item, predicate)),
+        ReflectRef::Enum(value) => value
+            .iter_fields()
+            .any(|field| any_entity(field.value(), predicate)),
+        _ => false,
+    }
+}
+
+/// Saves the given `entity` (and optionally all of its descendants) into a scene file within the directory at `path`.
+#[cfg(all(feature = "dump", not(target_arch = "wasm32")))]
+fn write_dump(world: &mut World, entity: Entity, path: &Path, recursive: bool) {
+    use bevy_scene::DynamicSceneBuilder;
+
+    let entities = if recursive {
+        with_descendants(world, entity)
+    } else {
+        vec![entity]
+    };
+
+    let scene = DynamicSceneBuilder::from_world(world)
+        .extract_entities(entities.into_iter())
+        .build();
+
+    let file_path = path.join(format!("{entity}.scn.ron"));
+    let result = with_registry(world, |registry| scene.serialize(registry))
+        .map_err(|error| error.to_string())
+        .and_then(|data| {
+            std::fs::create_dir_all(path)
+                .and_then(|_| std::fs::write(&file_path, data))
+                .map_err(|error| error.to_string())
+        });
+    match result {
+        Ok(()) => warn!("{entity} was dumped into {file_path:?}"),
+        Err(error) => error!("failed to dump {entity} into {file_path:?}: {error}"),
+    }
+}
+
+#[cfg(not(all(feature = "dump", not(target_arch = "wasm32"))))]
+fn write_dump(_world: &mut World, entity: Entity, path: &Path, _recursive: bool) {
+    warn!(
+        "{entity} was not dumped into {path:?}: the `dump` feature is not enabled for this target"
+    );
+}
+
+/// Returns the given `entity` followed by all of its descendants.
+#[cfg(any(feature = "dump", feature = "graveyard"))]
+fn with_descendants(world: &World, entity: Entity) -> Vec<Entity> {
+    use bevy_hierarchy::Children;
+
+    let mut entities = vec![entity];
+    let mut index = 0;
+    while index < entities.len() {
+        if let Some(children) = world.get::<Children>(entities[index]) {
+            entities.extend(children.iter().copied());
+        }
+        index += 1;
+    }
+    entities
+}
+
+fn init(app: &mut SubApp) {
+    if app.world().contains_resource::<CheckRegistry>() {
+        return;
+    }
+
+    let schedule = check_schedule(app);
+    app.register_type::<Checked>()
+        .register_type::<Invalid>()
+        .register_type::<InvalidReasons>()
+        .register_type::<PendingPurge>()
+        .register_type::<SkipChecks>()
+        .init_resource::<CheckRegistry>()
+        .init_resource::<CheckReport>()
+        .init_resource::<LoadPass>()
+        .add_event::<RepairFailed>()
+        .add_event::<ValidationOverload>();
+    add_check_systems(
+        app,
+        schedule,
+        (
+            check_threshold
+                .after(CheckSystems)
+                .run_if(resource_exists::<ValidationThreshold>),
+            release_deferred
+                .after(CheckSystems)
+                .run_if(resource_exists::<CheckBudget>),
+            purge_pending
+                .run_if(not(resource_exists::<DryRun>))
+                .after(LoadSystem::Load)
+                .before(CheckSystems),
+            begin_pass.after(LoadSystem::Load).before(CheckSystems),
+            strip_loaded_markers
+                .after(LoadSystem::Load)
+                .before(CheckSystems)
+                .run_if(resource_exists_and_changed::<Loaded>),
+        ),
+    );
+    add_check_systems(app, Startup, detect_conflicts);
+}
+
+/// Removes all [`check_markers`] from loaded entities, in case they were included in the save data.
+///
+/// This ensures loaded entities are always checked, regardless of how they were saved.
+fn strip_loaded_markers(
+    query: Query<
+        Entity,
+        (
+            With<Save>,
+            Or<(
+                With<Checked>,
+                With<Invalid>,
+                With<InvalidReasons>,
+                With<PendingPurge>,
+            )>,
+        ),
+    >,
+    mut commands: Commands,
+) {
+    for entity in query.iter() {
+        commands
+            .entity(entity)
+            .remove::<(Checked, Invalid, InvalidReasons, PendingPurge)>();
+    }
+}
+
+fn begin_pass(
+    mut report: ResMut<CheckReport>,
+    budget: Option<Res<CheckBudget>>,
+    loaded: Option<Res<Loaded>>,
+    mut load_pass: ResMut<LoadPass>,
+) {
+    load_pass.0 = loaded.is_some_and(|loaded| loaded.is_changed());
+    for (_, stats) in report.checks.iter_mut() {
+        *stats = CheckStats::default();
+    }
+    if let Some(budget) = budget {
+        budget.reset();
+    }
+}
+
+fn revalidate_system<T: Kind>(period: Duration) -> SystemConfigs {
+    let mut cursor = 0;
+    let mut budget = 0.0;
+    (move |query: Query<Instance<T>, Valid>, time: Option<Res<Time>>, mut commands: Commands| {
+        let count = query.iter().count();
+        if count == 0 {
+            return;
+        }
+
+        if period.is_zero() {
+            budget = count as f64;
+        } else {
+            // NOTE: Without any `Time`, instances are never revalidated.
+            let delta = time.map_or(0.0, |time| time.delta_secs_f64());
+            budget += count as f64 * delta / period.as_secs_f64();
+        }
+
+        let n = (budget.floor() as usize).min(count);
+        budget -= n as f64;
+        for instance in query.iter().chain(query.iter()).skip(cursor % count).take(n) {
+            commands.entity(instance.entity()).remove::<Checked>();
+        }
+        cursor = (cursor + n) % count;
+    })
+    .into_configs()
+}
+
+/// A [`SystemSet`] which contains the check system of the registered check with the given index.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, SystemSet)]
+struct CheckIndex(usize);
+
+/// Orders the registered check with the given index after the previous check of the same kind in its schedule.
+fn order_after_previous(app: &mut SubApp, index: usize) {
+    let registry = app.world().resource::<CheckRegistry>();
+    let check = &registry.checks[index];
+    let schedule = check.schedule;
+    let Some(previous) = registry.checks[..index]
+        .iter()
+        .rposition(|other| other.kind == check.kind && other.schedule == schedule)
+    else {
+        return;
+    };
+    app.configure_sets(
+        schedule,
+        CheckIndex(index).after_ignore_deferred(CheckIndex(previous)),
+    );
+}
+
+/// A [`SystemSet`] which contains all [`Watch`] systems of the registered check with the given index.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, SystemSet)]
+struct WatchSet(usize);
+
+/// Returns a system which checks all [`Watched`] instances of `T` again.
+fn watch_system<T: Kind>(watched: Watched) -> impl FnMut(Query<Instance<T>>, Commands) {
+    move |query: Query<Instance<T>>, mut commands: Commands| {
+        for entity in watched.lock().unwrap().drain() {
+            if let Ok(instance) = query.get(entity) {
+                debug!("{instance:?} has changed and must be checked again.");
+                commands.entity(instance.entity()).check_again();
+            }
+        }
+    }
+}
+
+/// Instances which failed a check but remain valid (such as repaired instances, or instances which only
+/// failed a warning), with the [`ArchetypeId`] they had after the policy of the check was applied.
+type Accepted = Arc<Mutex<EntityHashMap<Option<ArchetypeId>>>>;
+
+/// Records the given entity as [`Accepted`] by a check.
+fn accept(accepted: &Accepted, entity: Entity) {
+    // NOTE: The archetype is not known until the policy is applied, so it's recorded by the next recheck.
+    accepted.lock().unwrap().insert(entity, None);
+}
+
+/// Returns a system which checks valid instances of `T` again if they start failing the check filter `F`.
+///
+/// Since `F` may only start matching an instance after a structural change, only instances whose archetype
+/// changed since they were last accepted by the check are checked again. This ensures instances which still
+/// fail after a repair are not repaired repeatedly.
+fn recheck_system<T: Kind, F: CheckFilter>(
+    accepted: Accepted,
+) -> impl FnMut(Query<Instance<T>, (Valid, F)>, &Entities, Commands) {
+    move |query: Query<Instance<T>, (Valid, F)>, entities: &Entities, mut commands: Commands| {
+        let mut accepted = accepted.lock().unwrap();
+        accepted.retain(|entity, _| query.contains(*entity));
+        for instance in query.iter() {
+            let archetype = entities
+                .get(instance.entity())
+                .map(|location| location.archetype_id);
+            match accepted.get_mut(&instance.entity()) {
+                Some(accepted @ None) => {
+                    *accepted = archetype;
+                    continue;
+                }
+                Some(accepted) if *accepted == archetype => continue,
+                _ => {}
+            }
+            debug!("{instance:?} has changed and must be checked again.");
+            commands.entity(instance.entity()).check_again();
+        }
+    }
+}
+
+/// Returns `None` if `entity` is not an instance of `T`, otherwise returns `true` if it passes the check.
+/// Returns the [`Evaluate`] function of a check of `T` with filter `F`.
+///
+/// The query states are cached per world, so each check only initializes them once.
+fn evaluate<T: Kind, F: CheckFilter>() -> Evaluate {
+    type States<T, F> = (WorldId, QueryState<Instance<T>>, QueryState<(), F>);
+    let states: Mutex<Option<States<T, F>>> = Mutex::default();
+    Arc::new(move |world: &mut World, entity: Entity| {
+        let mut states = states.lock().unwrap();
+        if !states.as_ref().is_some_and(|(id, ..)| *id == world.id()) {
+            *states = Some((world.id(), QueryState::new(world), QueryState::new(world)));
+        }
+        let (_, instance, filter) = states.as_mut().unwrap();
+        instance.get(world, entity).ok()?;
+        Some(filter.get(world, entity).is_err())
+    })
+}
+
+fn check_name<T: Kind, F: CheckFilter>() -> String {
+    format!(
+        "{}: {}",
+        moonshine_util::get_short_name(std::any::type_name::<T>()),
+        moonshine_util::get_short_name(std::any::type_name::<F>())
+    )
+}
+
+/// A [`Resource`] which contains the schema version of the last loaded save data.
+///
+/// See [`migrate_before`](Check::migrate_before) for details.
+///
+/// # Usage
+///
+/// Save data written by [`moonshine_save`] does not include any schema version, so the version
+/// must be stored by the application (for example, as a component of a saved entity) and copied
+/// into this resource before [`LoadSystem::Load`] completes.
+#[derive(Resource, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Default)]
+pub struct SaveVersion(pub u32);
+
+/// The condition under which a check applies, if not to all instances.
+#[derive(Clone, Copy, Debug)]
+enum Gate {
+    /// The check only applies to loaded data.
+    ///
+    /// See [`check_on_load`](Check::check_on_load) for details.
+    Load,
+    /// The check only applies to save data older than the given version.
+    ///
+    /// See [`migrate_before`](Check::migrate_before) for details.
+    Migration(u32),
+}
+
+impl Gate {
+    /// Returns true if the check applies to all unchecked instances during the current check pass.
+    fn applies(self, world: &World) -> bool {
+        let loaded = world.get_resource::<LoadPass>().is_some_and(|pass| pass.0);
+        match self {
+            Gate::Load => loaded,
+            Gate::Migration(version) => {
+                loaded
+                    && world
+                        .get_resource::<SaveVersion>()
+                        .map_or(0, |current| current.0)
+                        < version
+            }
+        }
+    }
+}
+
+/// A [`Resource`] which is true during any check pass in which data was loaded.
+#[derive(Resource, Default)]
+struct LoadPass(bool);
+
+pub trait CheckFilter: 'static + QueryFilter + Send + Sync {}
+
+impl<F> CheckFilter for F where F: 'static + QueryFilter + Send + Sync {}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, SystemSet)]
+pub struct CheckSystems;
+
+/// A [`SystemSet`] which contains all check systems of kind `T` with filter `F`.
+///
+/// # Usage
+///
+/// Every check is also in [`CheckSystems`]. Use this set to order a system relative to a specific check,
+/// rather than all checks.
+///
+/// Note that checks with a custom condition (such as [`check_parent`](Check::check_parent)) use `()` as their filter.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Apple;
+///
+/// #[derive(Component)]
+/// struct Fresh;
+///
+/// fn eat_apples() {
+///     // ...
+/// }
+///
+/// let mut app = App::new();
+/// app.check::<Apple, Without<Fresh>>(purge())
+///     .add_systems(PreUpdate, eat_apples.after(CheckSet::<Apple, Without<Fresh>>::new()));
+/// ```
+#[derive(SystemSet)]
+pub struct CheckSet<T: Kind, F: CheckFilter>(PhantomData<(T, F)>);
+
+impl<T: Kind, F: CheckFilter> CheckSet<T, F> {
+    /// Creates a new [`CheckSet`].
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Kind, F: CheckFilter> Default for CheckSet<T, F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Kind, F: CheckFilter> Copy for CheckSet<T, F> {}
+
+impl<T: Kind, F: CheckFilter> Clone for CheckSet<T, F> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Kind, F: CheckFilter> Debug for CheckSet<T, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CheckSet<{}>", check_name::<T, F>())
+    }
+}
+
+impl<T: Kind, F: CheckFilter> Hash for CheckSet<T, F> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        TypeId::of::<Self>().hash(state);
+    }
+}
+
+impl<T: Kind, F: CheckFilter> PartialEq for CheckSet<T, F> {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl<T: Kind, F: CheckFilter> Eq for CheckSet<T, F> {}
+
+/// A [`Resource`] which contains all instances of `T` which were invalidated or purged during the current check pass.
+///
+/// See [`short_circuit_checks`](Check::short_circuit_checks) for details.
+#[derive(Resource)]
+struct ShortCircuit<T: Kind> {
+    failed: Mutex<HashSet<Entity>>,
+    marker: PhantomData<T>,
+}
+
+impl<T: Kind> Default for ShortCircuit<T> {
+    fn default() -> Self {
+        Self {
+            failed: Mutex::default(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Kind> ShortCircuit<T> {
+    /// Returns false if the given entity has already failed another check.
+    ///
+    /// If `exclusive` is true, the entity is also marked as failed.
+    fn claim(&self, entity: Entity, exclusive: bool) -> bool {
+        let mut failed = self.failed.lock().unwrap();
+        if failed.contains(&entity) {
+            return false;
+        }
+        if exclusive {
+            failed.insert(entity);
+        }
+        true
+    }
+
+    fn reset(&self) {
+        self.failed.lock().unwrap().clear();
+    }
+}
+
+/// The full configuration of a single [`Check`], consisting of its [`Policy`] and any additional options.
+///
+/// Any [`Policy`] may be used where a [`CheckConfig`] is expected.
+/// See [`ConfigureCheck`] for available options.
+pub struct CheckConfig {
+    policy: Policy,
+    hooks: Vec<SnapshotHook>,
+    revalidate: Option<Duration>,
+    watches: Vec<Watch>,
+    log_level: Option<Level>,
+    formatter: Option<FormatFn>,
+    inherit: bool,
+    lenient: bool,
+    labels: Vec<String>,
+    group: Option<String>,
+    before: Vec<InternedSystemSet>,
+    after: Vec<InternedSystemSet>,
+    severity: Severity,
+    schedule: Option<InternedScheduleLabel>,
+    gate: Option<Gate>,
+}
+
+/// Returns a system which collects all checked entities with a changed component into the given set.
+type Watch = fn(Watched) -> SystemConfigs;
+
+/// All checked entities with any watched components which changed since the last check pass.
+type Watched = Arc<Mutex<EntityHashSet>>;
+
+impl From<Policy> for CheckConfig {
+    fn from(policy: Policy) -> Self {
+        Self {
+            policy,
+            hooks: Vec::new(),
+            revalidate: None,
+            watches: Vec::new(),
+            log_level: Some(Level::ERROR),
+            formatter: None,
+            inherit: true,
+            lenient: false,
+            labels: Vec::new(),
+            group: None,
+            before: Vec::new(),
+            after: Vec::new(),
+            severity: Severity::Error,
+            schedule: None,
+            gate: None,
+        }
+    }
+}
+
+/// An extension trait used to configure a [`Check`] beyond its [`Policy`].
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Apple;
+///
+/// #[derive(Component)]
+/// struct Fresh;
+///
+/// #[derive(Component, Reflect)]
+/// struct Age(u32);
+///
+/// let mut app = App::new();
+/// app.check::<Apple, Without<Fresh>>(
+///     purge().with_snapshot(SnapshotHook::on([Outcome::Purged]).capture::<Age>()),
+/// );
+/// ```
+pub trait ConfigureCheck: Into<CheckConfig> {
+    /// Adds a [`SnapshotHook`] to this check.
+    fn with_snapshot(self, hook: SnapshotHook) -> CheckConfig {
+        let mut config = self.into();
+        config.hooks.push(hook);
+        config
+    }
+
+    /// Periodically re-checks valid instances so that each one is checked again roughly once per `period`.
+    ///
+    /// # Usage
+    ///
+    /// By default, an instance is only checked once. Use this to detect invariants which may be broken at runtime.
+    ///
+    /// Instances are re-checked on a rolling basis, rather than all at once, to spread the cost across frames.
+    /// Note that re-checking an instance re-evaluates *all* checks registered for it.
+    fn revalidate_every(self, period: Duration) -> CheckConfig {
+        let mut config = self.into();
+        config.revalidate = Some(period);
+        config
+    }
+
+    /// Sets the [`Level`] at which failures of this check are logged.
+    ///
+    /// By default, failures are logged as errors. Repairs are always logged at most as warnings.
+    fn log_level(self, level: Level) -> CheckConfig {
+        let mut config = self.into();
+        config.log_level = Some(level);
+        config
+    }
+
+    /// Disables all logging for this check.
+    ///
+    /// # Usage
+    ///
+    /// Use this for checks which are expected to fail frequently by design, such as repairs of old save data.
+    fn silent(self) -> CheckConfig {
+        let mut config = self.into();
+        config.log_level = None;
+        config
+    }
+
+    /// Sets a custom formatter used to describe failures of this check.
+    ///
+    /// This overrides the global [`CheckFormatter`], if any. See [`FailureInfo`] for details.
+    fn format_with(
+        self,
+        f: impl Fn(&FailureInfo) -> String + Send + Sync + 'static,
+    ) -> CheckConfig {
+        let mut config = self.into();
+        config.formatter = Some(Arc::new(f));
+        config
+    }
+
+    /// Prevents this check from applying to any kinds which inherit checks from the checked kind.
+    ///
+    /// See [`inherit_checks`](Check::inherit_checks) for details.
+    fn no_inherit(self) -> CheckConfig {
+        let mut config = self.into();
+        config.inherit = false;
+        config
+    }
+
+    /// Exempts this check from [`StrictChecks`], so that its policy is always applied as is.
+    fn lenient(self) -> CheckConfig {
+        let mut config = self.into();
+        config.lenient = true;
+        config
+    }
+
+    /// Adds a label to this check, as listed in the [`CheckRegistry`].
+    fn with_label(self, label: impl Into<String>) -> CheckConfig {
+        let mut config = self.into();
+        config.labels.push(label.into());
+        config
+    }
+
+    /// Adds this check to a named group, which may be enabled or disabled as a whole.
+    ///
+    /// # Usage
+    ///
+    /// Groups are useful to toggle entire categories of checks, such as content validation in an editor.
+    /// Use [`CheckPlugin::disable_group`] to disable a group when the app starts, or
+    /// [`CheckRegistry::set_group_enabled`] to toggle it at runtime.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Apple;
+    ///
+    /// #[derive(Component)]
+    /// struct Fresh;
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins(CheckPlugin::default().disable_group("content"))
+    ///     .check::<Apple, Without<Fresh>>(invalid().in_check_group("content"));
+    /// ```
+    fn in_check_group(self, group: impl Into<String>) -> CheckConfig {
+        let mut config = self.into();
+        config.group = Some(group.into());
+        config
+    }
+
+    /// Sets the [`Severity`] of this check.
+    ///
+    /// # Usage
+    ///
+    /// By default, all checks are errors. Instances which fail a warning are logged and counted in the
+    /// [`CheckReport`], but the policy is not applied and they are still marked as valid.
+    /// This is useful for invariants which are suspicious, but do not break the application.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Apple;
+    ///
+    /// #[derive(Component)]
+    /// struct Fresh;
+    ///
+    /// let mut app = App::new();
+    /// app.check::<Apple, Without<Fresh>>(invalid().severity(Severity::Warning));
+    /// ```
+    fn severity(self, severity: Severity) -> CheckConfig {
+        let mut config = self.into();
+        config.severity = severity;
+        config
+    }
+
+    /// Evaluates this check before all checks of kind `U` with filter `G` in the same check pass.
+    ///
+    /// # Usage
+    ///
+    /// By default, the order in which checks are evaluated is undefined. Use this to ensure the policy of this check
+    /// is applied before the policy of another check, such as a repair which the other check may overwrite.
+    ///
+    /// Both checks are evaluated against the same state of the world, before any of their policies are applied.
+    /// This means every instance is still evaluated by the other check, even if it is repaired by this check.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Apple;
+    ///
+    /// #[derive(Component, Default)]
+    /// struct Fresh;
+    ///
+    /// #[derive(Component)]
+    /// struct Basket;
+    ///
+    /// let mut app = App::new();
+    /// app.check::<Apple, Without<Fresh>>(
+    ///     repair_insert_default::<Fresh>().check_before::<Basket, ()>(),
+    /// );
+    /// ```
+    fn check_before<U: Kind, G: CheckFilter>(self) -> CheckConfig {
+        let mut config = self.into();
+        config.before.push(CheckSet::<U, G>::new().intern());
+        config
+    }
+
+    /// Evaluates this check after all checks of kind `U` with filter `G` in the same check pass.
+    ///
+    /// See [`check_before`](ConfigureCheck::check_before) for details.
+    fn check_after<U: Kind, G: CheckFilter>(self) -> CheckConfig {
+        let mut config = self.into();
+        config.after.push(CheckSet::<U, G>::new().intern());
+        config
+    }
+
+    /// Re-checks instances whenever the component `T` is changed.
+    ///
+    /// # Usage
+    ///
+    /// By default, an instance is only checked once. Use this for invariants which depend on the value of
+    /// some components, and may be broken long after the instance was first checked.
+    ///
+    /// This may be called multiple times to watch multiple components.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Weapon;
+    ///
+    /// #[derive(Component)]
+    /// struct Ammo(u32);
+    ///
+    /// #[derive(Component)]
+    /// struct Capacity(u32);
+    ///
+    /// let mut app = App::new();
+    /// app.check::<Weapon, Without<Capacity>>(invalid().watch::<Ammo>().watch::<Capacity>());
+    /// ```
+    fn watch<T: Component>(self) -> CheckConfig {
+        let mut config = self.into();
+        config.watches.push(|watched| {
+            (move |query: Query<Entity, (Changed<T>, With<Checked>)>| {
+                if !query.is_empty() {
+                    watched.lock().unwrap().extend(query.iter());
+                }
+            })
+            .into_configs()
+        });
+        config
+    }
+}
+
+impl<T: Into<CheckConfig>> ConfigureCheck for T {}
+
+/// The result of a [`Check`] on a single instance.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+pub enum Outcome {
+    /// The instance passed the check.
+    Valid,
+    /// The instance was marked as invalid.
+    Invalid,
+    /// The instance was despawned.
+    Purged,
+    /// The instance was repaired.
+    Repaired,
+}
+
+/// The severity of a [`Check`]. See [`ConfigureCheck::severity`] for details.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Severity {
+    /// Failed instances are logged and reported, but are still valid.
+    Warning,
+    /// Failed instances are handled by the check [`Policy`].
+    #[default]
+    Error,
+}
+
+/// An extension trait used to force an [`Entity`] to be checked again.
+pub trait CheckAgain {
+    fn check_again(self) -> Self;
+}
+
+impl CheckAgain for &mut EntityCommands<'_> {
+    fn check_again(self) -> Self {
+        self.remove::<(Checked, Invalid, InvalidReasons)>()
+    }
+}
+
+impl CheckAgain for &mut EntityWorldMut<'_> {
+    fn check_again(self) -> Self {
+        self.remove::<(Checked, Invalid, InvalidReasons)>()
+    }
+}
+
+/// An extension trait used to find when an [`Entity`] was last checked.
+pub trait CheckedAt {
+    /// Returns the change tick at which the given entity was last checked, or `None` if it is not checked.
+    ///
+    /// # Usage
+    ///
+    /// This is useful to find stale entities, or to debug the order in which entities are checked.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Apple;
+    ///
+    /// #[derive(Component)]
+    /// struct Fresh;
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins(MinimalPlugins)
+    ///     .check::<Apple, Without<Fresh>>(invalid());
+    ///
+    /// let entity = app.world_mut().spawn(Apple).id();
+    /// assert!(app.world().checked_at(entity).is_none());
+    ///
+    /// app.update();
+    /// assert!(app.world().checked_at(entity).is_some());
+    /// ```
+    fn checked_at(&self, entity: Entity) -> Option<Tick>;
+}
+
+impl CheckedAt for World {
+    fn checked_at(&self, entity: Entity) -> Option<Tick> {
+        // NOTE: `Checked` is inserted (and therefore changed) every time the entity is checked.
+        self.get_entity(entity)
+            .ok()?
+            .get_change_ticks::<Checked>()
+            .map(|ticks| ticks.last_changed_tick())
+    }
+}
+
+/// An extension trait used to force all instances of a [`Kind`] to be checked again.
+///
+/// # Usage
+///
+/// This is useful when some global state which affects the validity of all instances of a kind has changed,
+/// such as a gameplay rule or a configuration resource.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Apple;
+///
+/// fn on_rules_changed(mut commands: Commands) {
+///     commands.check_again_all::<Apple>();
+/// }
+/// ```
+pub trait CheckAgainAll {
+    fn check_again_all<T: Kind>(&mut self) -> &mut Self;
+}
+
+impl CheckAgainAll for World {
+    fn check_again_all<T: Kind>(&mut self) -> &mut Self {
+        let instances: Vec<Instance<T>> = self
+            .query_filtered::<Instance<T>, With<Checked>>()
+            .iter(self)
+            .collect();
+        for instance in instances {
+            self.entity_mut(instance.entity()).check_again();
+        }
+        self
+    }
+}
+
+impl CheckAgainAll for Commands<'_, '_> {
+    fn check_again_all<T: Kind>(&mut self) -> &mut Self {
+        self.queue(|world: &mut World| {
+            world.check_again_all::<T>();
+        });
+        self
+    }
+}
+
+/// Returns the [`TypeId`]s of all components used internally to mark the validation state of an entity.
+///
+/// # Usage
+///
+/// These components are registered for reflection so they may be displayed in inspectors and scene tools.
+/// Any of these components which are loaded with a [`Save`] entity are removed after [`LoadSystem::Load`],
+/// so loaded entities are always checked. However, they should still be excluded from any save data:
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy::scene::SceneFilter;
+///
+/// let filter = moonshine_check::check_markers()
+///     .into_iter()
+///     .fold(SceneFilter::allow_all(), SceneFilter::deny_by_id);
+/// ```
+pub fn check_markers() -> [TypeId; 4] {
+    [
+        TypeId::of::<Checked>(),
+        TypeId::of::<Invalid>(),
+        TypeId::of::<InvalidReasons>(),
+        TypeId::of::<PendingPurge>(),
+    ]
+}
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, Default)]
+#[component(on_insert = reset_repair_progress)]
+struct Checked;
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, Default)]
+struct Invalid {
+    /// Name of the check which invalidated the entity, or the reason if it was invalidated manually.
+    reason: String,
+    /// Elapsed [`Time`] when the entity was invalidated.
+    time: Duration,
+}
+
+impl Invalid {
+    fn new(reason: impl Into<String>, world: &World) -> Self {
+        Self {
+            reason: reason.into(),
+            time: world
+                .get_resource::<Time>()
+                .map(|time| time.elapsed())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "disable")))]
+mod tests {
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use bevy::log::tracing_subscriber::{
+        layer::{Context, Layer},
+        registry::LookupSpan,
+    };
+    use bevy::prelude::*;
+    use bevy_ecs::schedule::ExecutorKind;
+    use bevy_utils::tracing::{
+        field::{Field, Visit},
+        span,
+    };
+
+    use super::*;
+
+    #[derive(Component, Clone)]
+    struct Foo;
+
+    #[derive(Component, Clone)]
+    struct Bar;
+
+    #[test]
+    fn test_valid() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(panic());
+
+        let entity = app.world_mut().spawn((Foo, Bar)).id();
+        app.update();
+
+        assert!(app.world().entity(entity).contains::<Checked>());
+        assert!(!app.world().entity(entity).contains::<Invalid>());
+    }
+
+    #[test]
+    fn test_invalid() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid());
+
+        let entity = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        assert!(app.world().entity(entity).contains::<Checked>());
+        assert!(app.world().entity(entity).contains::<Invalid>());
+    }
+
+    #[test]
+    fn test_purge() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(purge());
+
+        let entity = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        assert!(app.world().get_entity(entity).is_err());
+    }
+
+    #[test]
+    fn test_escalate_invalid() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid())
+            .escalate_invalid(Duration::ZERO, WatchdogPolicy::Purge);
+
+        let entity = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        assert!(app.world().get_entity(entity).is_err());
+
+        let events = app.world().resource::<Events<InvalidTimeout>>();
+        assert_eq!(events.iter_current_update_events().count(), 1);
+    }
+
+    #[test]
+    fn test_dry_run_escalate_invalid() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(DryRun)
+            .escalate_invalid(Duration::ZERO, WatchdogPolicy::Purge);
+
+        let invalid = Invalid::new("Test", app.world());
+        let entity = app.world_mut().spawn((Foo, Checked, invalid)).id();
+        app.update();
+
+        assert!(app.world().get_entity(entity).is_ok());
+
+        let events = app.world().resource::<Events<InvalidTimeout>>();
+        assert_eq!(events.iter_current_update_events().count(), 1);
+    }
+
+    #[test]
+    fn test_validation_journal_static() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(ValidationJournal::new(8))
+            .check_static::<Foo, Without<Bar>, _>(PurgePolicy)
+            .check_count::<Bar>(1.., CountPolicy::spawn_missing(1, Bar));
+
+        let entity = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        let journal = app.world().resource::<ValidationJournal>();
+        assert_eq!(journal.entries_for(entity).count(), 1);
+        assert_eq!(journal.entries_for(Entity::PLACEHOLDER).count(), 1);
+    }
+
+    #[test]
+    fn test_validation_journal_escalate_invalid() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(ValidationJournal::new(8))
+            .check::<Foo, Without<Bar>>(invalid())
+            .escalate_invalid(Duration::ZERO, WatchdogPolicy::Purge);
+
+        let entity = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        let journal = app.world().resource::<ValidationJournal>();
+        let entry = journal.entries_for(entity).next().unwrap();
+        assert_eq!(entry.outcome, Outcome::Purged);
+    }
+
+    #[test]
+    fn test_validation_threshold() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(ValidationThreshold::per_frame(1).exit_on_overload())
+            .check::<Foo, Without<Bar>>(invalid());
+
+        app.world_mut().spawn(Foo);
+        app.update();
+
+        assert!(app.world().resource::<Events<AppExit>>().is_empty());
+
+        app.world_mut().spawn(Foo);
+        app.world_mut().spawn(Foo);
+        app.update();
+
+        let overloads = app.world().resource::<Events<ValidationOverload>>();
+        assert_eq!(
+            overloads.iter_current_update_events().next().unwrap().count,
+            2
+        );
+        assert!(!app.world().resource::<Events<AppExit>>().is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_strict_checks() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(StrictChecks(true))
+            .check::<Foo, Without<Bar>>(invalid());
+
+        app.world_mut().spawn(Foo);
+        app.update();
+    }
+
+    #[test]
+    fn test_strict_checks_lenient() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(StrictChecks(true))
+            .check::<Foo, Without<Bar>>(invalid().lenient());
+
+        let entity = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        assert!(app.world().entity(entity).contains::<Invalid>());
+    }
+
+    #[test]
+    fn test_debug_check() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .debug_check::<Foo, Without<Bar>>(invalid());
+
+        let entity = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        let checked = cfg!(debug_assertions) || cfg!(feature = "debug_checks");
+        assert_eq!(app.world().entity(entity).contains::<Invalid>(), checked);
+    }
+
+    #[test]
+    fn test_dry_run() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<DryRun>()
+            .check::<Foo, Without<Bar>>(purge());
+
+        let entity = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        assert!(!app.world().entity(entity).contains::<Checked>());
+        assert_eq!(app.world().resource::<CheckReport>().total().purged, 1);
+
+        app.update();
+
+        assert!(app.world().get_entity(entity).is_ok());
+        assert_eq!(app.world().resource::<CheckReport>().total().purged, 0);
+
+        app.world_mut().remove_resource::<DryRun>();
+        app.update();
+
+        assert!(app.world().get_entity(entity).is_err());
+    }
+
+    #[test]
+    fn test_validation_journal() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(ValidationJournal::new(1).with_snapshots())
+            .check::<Foo, Without<Bar>>(repair_insert(Bar));
+
+        let a = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        let b = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        let journal = app.world().resource::<ValidationJournal>();
+        assert_eq!(journal.len(), 1);
+        assert_eq!(journal.entries_for(a).count(), 0);
+
+        let entry = journal.entries_for(b).next().unwrap();
+        assert_eq!(entry.outcome, Outcome::Repaired);
+        assert_eq!(entry.kind, "Foo");
+        assert!(entry.snapshot.as_ref().unwrap().contains("Foo"));
+    }
+
+    #[test]
+    #[cfg(feature = "serialize")]
+    fn test_report_to_json() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid());
+
+        app.world_mut().spawn(Foo);
+        app.update();
+
+        let json = app.world().resource::<CheckReport>().to_json().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let (name, stats) = (&value["checks"][0][0], &value["checks"][0][1]);
+        assert_eq!(name, check_name::<Foo, Without<Bar>>().as_str());
+        assert_eq!(stats["invalid"], 1);
+    }
+
+    #[test]
+    fn test_audit_on_startup() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, CheckPlugin::default().audit_on_startup()))
+            .check::<Foo, Without<Bar>>(invalid());
+
+        let entity = app.world_mut().spawn((Foo, Checked)).id();
+        app.update();
+
+        assert!(app.world().entity(entity).contains::<Invalid>());
+    }
+
+    #[test]
+    fn test_audit() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid());
+
+        let valid = app.world_mut().spawn((Foo, Bar)).id();
+        app.update();
+        let invalid = app.world_mut().spawn(Foo).id();
+        app.world_mut().spawn(Bar);
+
+        let report = app.world_mut().audit();
+        let audit = report.get(&check_name::<Foo, Without<Bar>>()).unwrap();
+        assert_eq!(audit.passed, vec![valid]);
+        assert_eq!(audit.failed, vec![invalid]);
+        assert!(!report.is_valid());
+        assert!(!app.world().entity(invalid).contains::<Checked>());
+    }
+
+    #[test]
+    fn test_registry() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid().with_label("test"));
+
+        let name = check_name::<Foo, Without<Bar>>();
+        let registry = app.world().resource::<CheckRegistry>();
+        let check = registry.get(&name).unwrap();
+        assert_eq!(check.kind(), TypeId::of::<Foo>());
+        assert_eq!(check.policy(), PolicyKind::Invalid);
+        assert_eq!(check.labels(), ["test"]);
+        assert_eq!(check.schedule(), PreUpdate.intern());
+
+        app.world_mut()
+            .resource_mut::<CheckRegistry>()
+            .set_enabled(&name, false);
+        let entity = app.world_mut().spawn(Foo).id();
+        app.update();
+        assert!(!app.world().entity(entity).contains::<Checked>());
+
+        app.world_mut()
+            .resource_mut::<CheckRegistry>()
+            .set_enabled(&name, true);
+        app.update();
+        assert!(app.world().entity(entity).contains::<Invalid>());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_strict_registration() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, CheckPlugin::default().strict_registration()))
+            .check::<Foo, Without<Bar>>(invalid())
+            .check::<Foo, Without<Bar>>(purge());
+        app.update();
+    }
+
+    #[test]
+    fn test_strict_registration_conditions() {
+        #[derive(Component)]
+        struct Baz;
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, CheckPlugin::default().strict_registration()))
+            .check_parent::<Foo, Bar>(invalid())
+            .check_parent::<Foo, Baz>(invalid())
+            .check_children::<Foo, Bar>(1, invalid())
+            .check_children::<Foo, Bar>(2, invalid());
+        app.update();
+    }
+
+    #[test]
+    fn test_conflicting_repairs() {
+        let Policy::Repair(fixer) = repair_insert(Bar) else {
+            unreachable!();
+        };
+        let Policy::Repair(other) = repair_insert(Bar) else {
+            unreachable!();
+        };
+        assert!(fixer.ptr_eq(&fixer.clone()));
+        assert!(!fixer.ptr_eq(&other));
+    }
+
+    #[test]
+    fn test_check_group() {
+        #[derive(Component)]
+        struct Baz;
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, CheckPlugin::default().disable_group("a")))
+            .check::<Foo, Without<Bar>>(invalid().in_check_group("a"))
+            .check::<Baz, Without<Bar>>(invalid().in_check_group("b"));
+
+        let foo = app.world_mut().spawn(Foo).id();
+        let baz = app.world_mut().spawn(Baz).id();
+        app.update();
+        assert!(!app.world().entity(foo).contains::<Checked>());
+        assert!(app.world().entity(baz).contains::<Invalid>());
+
+        app.world_mut()
+            .resource_mut::<CheckRegistry>()
+            .set_group_enabled("a", true);
+        app.update();
+        assert!(app.world().entity(foo).contains::<Invalid>());
+    }
+
+    #[test]
+    fn test_check_before() {
+        #[derive(Component)]
+        struct Baz;
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Baz>>(invalid())
+            .check::<Foo, Without<Bar>>(repair_insert(Bar).check_before::<Foo, Without<Baz>>());
+
+        let entity = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        // NOTE: The repair must not prevent the other check from failing.
+        assert!(app.world().entity(entity).contains::<Bar>());
+        assert!(app.world().entity(entity).contains::<Invalid>());
+    }
+
+    #[test]
+    fn test_check_after() {
+        #[derive(Component, PartialEq, Debug)]
+        struct Baz(u32);
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Baz>>(repair(|entity: EntityRef, commands: &mut Commands| {
+                commands.entity(entity.id()).insert(Baz(1));
+            }))
+            .check::<Foo, Without<Bar>>(
+                repair(|entity: EntityRef, commands: &mut Commands| {
+                    commands.entity(entity.id()).insert((Bar, Baz(2)));
+                })
+                .check_after::<Foo, Without<Baz>>(),
+            );
+
+        let entity = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        // NOTE: Policies are applied in order, so the later repair wins.
+        assert!(app.world().entity(entity).contains::<Bar>());
+        assert_eq!(app.world().entity(entity).get::<Baz>(), Some(&Baz(2)));
+    }
+
+    #[test]
+    fn test_check_set() {
+        #[derive(Resource, Default)]
+        struct Seen(bool);
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<Seen>()
+            .check::<Foo, Without<Bar>>(invalid())
+            .add_systems(
+                PreUpdate,
+                (|query: Query<(), (With<Foo>, With<Invalid>)>, mut seen: ResMut<Seen>| {
+                    seen.0 |= !query.is_empty();
+                })
+                .after(CheckSet::<Foo, Without<Bar>>::new()),
+            );
+
+        app.world_mut().spawn(Foo);
+        app.update();
+
+        assert!(app.world().resource::<Seen>().0);
+    }
+
+    #[test]
+    fn test_short_circuit() {
+        #[derive(Component)]
+        struct Baz;
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid())
+            .check::<Foo, Without<Baz>>(invalid())
+            .short_circuit_checks::<Foo>();
+
+        app.world_mut().spawn(Foo);
+        app.update();
+
+        let report = app.world().resource::<CheckReport>();
+        assert_eq!(report.total().invalid, 1);
+    }
+
+    #[test]
+    fn test_short_circuit_order() {
+        #[derive(Component)]
+        struct Baz;
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .short_circuit_checks::<Foo>()
+            .check::<Foo, Without<Bar>>(invalid())
+            .check::<Foo, Without<Baz>>(purge())
+            .check::<Foo, Without<Baz>>(purge());
+
+        let entities: Vec<Entity> = (0..16).map(|_| app.world_mut().spawn(Foo).id()).collect();
+        app.update();
+
+        // NOTE: The first registered check always wins.
+        for entity in entities {
+            let entity = app.world().entity(entity);
+            assert!(entity.contains::<Invalid>());
+        }
+        let report = app.world().resource::<CheckReport>();
+        assert_eq!(report.total().purged, 0);
+    }
+
+    #[test]
+    fn test_valid_query_data() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid());
+
+        let valid = app.world_mut().spawn((Foo, Bar)).id();
+        let invalid = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        let world = app.world_mut();
+        let entities: Vec<Entity> = world
+            .query::<Valid<Instance<Foo>>>()
+            .iter(world)
+            .map(|instance| instance.entity())
+            .collect();
+        assert_eq!(entities, vec![valid]);
+
+        #[cfg(debug_assertions)]
+        {
+            assert!(Valid::explain(world.entity(invalid)).is_some());
+            assert!(Valid::explain(world.entity(valid)).is_none());
+        }
+    }
+
+    #[test]
+    fn test_validated() {
+        use bevy_ecs::system::RunSystemOnce;
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid());
+
+        let valid = app.world_mut().spawn((Foo, Bar)).id();
+        app.world_mut().spawn(Foo);
+        app.update();
+
+        let instance = app
+            .world_mut()
+            .run_system_once(|validated: Validated<Foo>| {
+                assert_eq!(validated.len(), 1);
+                validated.single()
+            })
+            .unwrap();
+        assert_eq!(instance.entity(), valid);
+    }
+
+    #[test]
+    fn test_valid_instances() {
+        use bevy_ecs::system::RunSystemOnce;
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid());
+
+        let valid = app.world_mut().spawn((Foo, Bar)).id();
+        let invalid = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        app.world_mut()
+            .run_system_once(move |query: ValidInstances<Foo>| {
+                let set = query.collect_set();
+                assert_eq!(set.len(), 1);
+                assert!(query.contains_instance(set.into_iter().next().unwrap()));
+                assert!(query.contains(valid));
+                assert!(!query.contains(invalid));
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn test_public_filters() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid());
+
+        let valid = app.world_mut().spawn((Foo, Bar)).id();
+        let invalid = app.world_mut().spawn(Foo).id();
+        app.update();
+        let unchecked = app.world_mut().spawn(Foo).id();
+
+        let world = app.world_mut();
+        let mut checked = world.query_filtered::<Entity, IsChecked>();
+        assert_eq!(checked.iter(world).count(), 2);
+        assert!(checked.get(world, valid).is_ok());
+        let mut invalids = world.query_filtered::<Entity, IsInvalid>();
+        assert_eq!(invalids.single(world), invalid);
+        let mut unchecked_query = world.query_filtered::<Entity, (With<Foo>, Unchecked)>();
+        assert_eq!(unchecked_query.single(world), unchecked);
+    }
+
+    #[test]
+    fn test_reflect_markers() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid());
+
+        let registry = app.world().resource::<AppTypeRegistry>().read();
+        assert!(registry.contains(TypeId::of::<Checked>()));
+        assert!(registry.contains(TypeId::of::<Invalid>()));
+        assert!(registry.contains(TypeId::of::<PendingPurge>()));
+    }
+
+    #[test]
+    fn test_invalid_reasons() {
+        #[derive(Component)]
+        struct Baz;
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid())
+            .check::<Foo, Without<Baz>>(invalid());
+
+        let entity = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        let reasons = app.world().get::<InvalidReasons>(entity).unwrap();
+        assert_eq!(reasons.len(), 2);
+        assert!(reasons
+            .iter()
+            .any(|reason| reason.check == check_name::<Foo, Without<Baz>>()));
+
+        app.world_mut().entity_mut(entity).check_again();
+        assert!(!app.world().entity(entity).contains::<InvalidReasons>());
+    }
+
+    #[test]
+    fn test_checked_at() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid());
+
+        let entity = app.world_mut().spawn(Foo).id();
+        assert!(app.world().checked_at(entity).is_none());
+
+        app.update();
+        let first = app.world().checked_at(entity).unwrap();
+
+        app.world_mut().entity_mut(entity).check_again();
+        app.update();
+        let second = app.world().checked_at(entity).unwrap();
+        assert!(second.is_newer_than(first, app.world().read_change_tick()));
+    }
+
+    #[test]
+    fn test_skip_checks() {
+        #[derive(Component)]
+        struct Baz;
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid())
+            .check::<Baz, Without<Bar>>(invalid());
+
+        let skipped = app.world_mut().spawn((Foo, SkipChecks)).id();
+        let partial = app
+            .world_mut()
+            .spawn((Foo, Baz, SkipCheck::<Foo>::default()))
+            .id();
+        app.update();
+
+        assert!(!app.world().entity(skipped).contains::<Checked>());
+        assert!(app.world().entity(partial).contains::<Invalid>());
+        assert_eq!(app.world().resource::<CheckReport>().total().invalid, 1);
+
+        app.world_mut().entity_mut(skipped).remove::<SkipChecks>();
+        app.update();
+        assert!(app.world().entity(skipped).contains::<Invalid>());
+    }
+
+    #[test]
+    fn test_severity_warning() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(purge().severity(Severity::Warning));
+
+        let entity = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        let world = app.world_mut();
+        assert!(world
+            .query_filtered::<(), Valid>()
+            .get(world, entity)
+            .is_ok());
+        let stats = world.resource::<CheckReport>().total();
+        assert_eq!(stats.warned, 1);
+        assert_eq!(stats.passed, 1);
+    }
+
+    #[test]
+    fn test_severity_warning_once() {
+        let warned = Arc::new(AtomicU64::new(0));
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(OnCheckFailure::new({
+                let warned = warned.clone();
+                move |_| {
+                    warned.fetch_add(1, Ordering::Relaxed);
+                }
+            }))
+            .check::<Foo, Without<Bar>>(invalid().severity(Severity::Warning));
+
+        app.world_mut().spawn(Foo);
+        for _ in 0..4 {
+            app.update();
+        }
+
+        // NOTE: Instances which only fail a warning must not be checked (and warned) again every frame.
+        assert_eq!(warned.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_on_check_failure() {
+        let failures = Arc::new(Mutex::new(Vec::new()));
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(OnCheckFailure::new({
+                let failures = failures.clone();
+                move |failure| failures.lock().unwrap().push(failure.clone())
+            }))
+            .check::<Foo, Without<Bar>>(purge());
+
+        let entity = app.world_mut().spawn(Foo).id();
+        app.world_mut().spawn((Foo, Bar));
+        app.update();
+
+        let failures = failures.lock().unwrap();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].entity, entity);
+        assert_eq!(failures[0].outcome, Some(Outcome::Purged));
+    }
+
+    #[test]
+    fn test_validation_log() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(ValidationLog::new(1))
+            .check::<Foo, Without<Bar>>(invalid());
+
+        app.world_mut().spawn(Foo);
+        app.update();
+
+        let log = app.world().resource::<ValidationLog>();
+        assert_eq!(log.total(), 1);
+        assert_eq!(log.since(0).count(), 1);
+        assert_eq!(log.since(1).count(), 0);
+
+        app.world_mut().spawn(Foo);
+        app.update();
+
+        let log = app.world().resource::<ValidationLog>();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log.total(), 2);
+        assert_eq!(log.iter().next().unwrap().outcome, Outcome::Invalid);
+    }
+
+    #[test]
+    fn test_spawn_checked() {
+        #[derive(Component)]
+        struct Baz;
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(purge())
+            .check::<Baz, Without<Bar>>(invalid());
+
+        let purged = app.world_mut().spawn_checked(Foo);
+        let invalid = app.world_mut().spawn_checked(Baz);
+        let valid = app.world_mut().spawn_checked((Foo, Bar));
+
+        assert!(app.world().get_entity(purged).is_err());
+        assert!(app.world().entity(invalid).contains::<Invalid>());
+        assert!(app.world().entity(valid).contains::<Checked>());
+    }
+
+    #[test]
+    fn test_spawn_checked_dry_run() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(DryRun)
+            .check::<Foo, Without<Bar>>(purge());
+
+        let entity = app.world_mut().spawn_checked(Foo);
+
+        assert!(app.world().get_entity(entity).is_ok());
+        assert!(!app.world().entity(entity).contains::<Checked>());
+        let report = app.world().resource::<CheckReport>();
+        assert_eq!(report.total().purged, 1);
+    }
+
+    #[test]
+    fn test_spawn_checked_pipeline() {
+        let failures = Arc::new(Mutex::new(Vec::new()));
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(ValidationLog::new(8))
+            .insert_resource(ValidationJournal::new(8))
+            .insert_resource(OnCheckFailure::new({
+                let failures = failures.clone();
+                move |failure| failures.lock().unwrap().push(failure.outcome)
+            }))
+            .check::<Foo, Without<Bar>>(invalid())
+            .check::<Bar, Without<Foo>>(repair_insert(Foo));
+
+        let invalid = app.world_mut().spawn_checked(Foo);
+        let repaired = app.world_mut().spawn_checked(Bar);
+
+        let reasons = app.world().get::<InvalidReasons>(invalid).unwrap();
+        assert_eq!(reasons.iter().next().unwrap().filter, "Without<Bar>");
+        assert!(app.world().entity(repaired).contains::<Foo>());
+        assert_eq!(
+            *failures.lock().unwrap(),
+            [Some(Outcome::Invalid), Some(Outcome::Repaired)]
+        );
+        assert_eq!(app.world().resource::<ValidationLog>().len(), 2);
+        assert_eq!(app.world().resource::<ValidationJournal>().len(), 1);
+        let report = app.world().resource::<CheckReport>();
+        assert_eq!(report.total().invalid, 1);
+        assert_eq!(report.total().repaired, 1);
+    }
+
+    #[test]
+    fn test_validate_bundle() {
+        #[derive(Component, Clone)]
+        struct Baz;
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid())
+            .check::<Baz, Without<Bar>>(invalid());
+
+        let errors = app
+            .world()
+            .validate_bundle::<Foo, _>(&(Foo, Baz))
+            .unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].entity, Entity::PLACEHOLDER);
+        assert!(app.world().validate_bundle::<Foo, _>(&(Foo, Bar)).is_ok());
+    }
+
+    #[test]
+    fn test_flush_checks() {
+        #[derive(Resource)]
+        struct Spawned(Entity);
+
+        fn spawn(mut commands: Commands) {
+            let entity = commands.spawn(Foo).id();
+            commands.insert_resource(Spawned(entity));
+        }
+
+        fn observe(spawned: Res<Spawned>, query: Query<(), IsInvalid>) {
+            assert!(query.contains(spawned.0));
+        }
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, CheckPlugin::default().flush_checks()))
+            .check::<Foo, Without<Bar>>(invalid())
+            .add_systems(
+                Update,
+                (spawn.before(CheckFlush), observe.after(CheckFlush)),
+            );
+
+        app.update();
+    }
+
+    #[test]
+    fn test_in_schedule() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, CheckPlugin::default().in_schedule(First)))
+            .check::<Foo, Without<Bar>>(purge());
+
+        let entity = app.world_mut().spawn(Foo).id();
+        app.world_mut().run_schedule(First);
+
+        assert!(app.world().get_entity(entity).is_err());
+        assert_eq!(
+            app.world()
+                .resource::<CheckRegistry>()
+                .iter()
+                .next()
+                .unwrap()
+                .schedule(),
+            First.intern()
+        );
+    }
+
+    #[test]
+    fn test_check_fixed() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check_fixed::<Foo, Without<Bar>>(invalid());
+
+        let entity = app.world_mut().spawn(Foo).id();
+        app.world_mut().run_schedule(PreUpdate);
+
+        assert!(!app.world().entity(entity).contains::<Checked>());
+
+        app.world_mut().run_schedule(FixedPreUpdate);
+
+        assert!(app.world().entity(entity).contains::<Invalid>());
+        assert_eq!(
+            app.world()
+                .resource::<CheckRegistry>()
+                .iter()
+                .next()
+                .unwrap()
+                .schedule(),
+            FixedPreUpdate.intern()
+        );
+    }
+
+    #[test]
+    fn test_sub_app() {
+        let mut sub_app = SubApp::new();
+        CheckPlugin::default()
+            .in_schedule(Update)
+            .configure(&mut sub_app);
+        sub_app.check::<Foo, Without<Bar>>(invalid());
+
+        let entity = sub_app.world_mut().spawn(Foo).id();
+        sub_app.world_mut().run_schedule(Update);
+
+        assert!(sub_app.world().entity(entity).contains::<Invalid>());
+    }
+
+    #[test]
+    fn test_apply_checks() {
+        let mut strict = CheckRegistry::default();
+        strict.add::<Foo, Without<Bar>>(purge());
+
+        let mut lenient = CheckRegistry::default();
+        lenient.add::<Foo, Without<Bar>>(invalid());
+
+        let mut a = World::new();
+        a.insert_resource(strict.clone());
+        let mut b = World::new();
+        b.insert_resource(lenient);
+
+        let purged = a.spawn(Foo).id();
+        let invalid = b.spawn(Foo).id();
+        a.apply_checks();
+        b.apply_checks();
+
+        assert!(a.get_entity(purged).is_err());
+        assert!(b.entity(invalid).contains::<Invalid>());
+        assert_eq!(strict.len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "conflicting policies")]
+    fn test_registry_strict_registration() {
+        let mut registry = CheckRegistry::default().strict_registration();
+        registry.add::<Foo, Without<Bar>>(invalid());
+        registry.add::<Foo, Without<Bar>>(purge());
+    }
+
+    #[test]
+    fn test_registry_clone() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid())
+            .check_static::<Foo, Without<Bar>, _>(InvalidPolicy);
+
+        // NOTE: Statically dispatched checks can't be applied without the app, so they're not cloned.
+        let registry = app.world().resource::<CheckRegistry>().clone();
+        assert_eq!(registry.len(), 1);
+
+        let mut world = World::new();
+        world.insert_resource(registry);
+        let entity = world.spawn((Foo, Bar)).id();
+        world.apply_checks();
+
+        assert!(world.entity(entity).contains::<Checked>());
+    }
+
+    #[test]
+    fn test_apply_checks_again() {
+        #[derive(Component)]
+        struct Baz;
+
+        let mut registry = CheckRegistry::default();
+        registry.add::<Foo, Without<Bar>>(invalid());
+
+        let mut world = World::new();
+        world.insert_resource(registry);
+
+        let unrelated = world.spawn(Bar).id();
+        let a = world.spawn(Foo).id();
+        world.apply_checks();
+        // NOTE: Spawn into a new archetype to ensure the cached query state is updated.
+        let b = world.spawn((Foo, Baz)).id();
+        world.apply_checks();
+
+        assert!(world.entity(a).contains::<Invalid>());
+        assert!(world.entity(b).contains::<Invalid>());
+        assert!(!world.entity(unrelated).contains::<Checked>());
+    }
+
+    #[test]
+    fn test_marker_free() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, CheckPlugin::default().marker_free()))
+            .check::<Foo, Without<Bar>>(invalid());
+
+        let invalid = app.world_mut().spawn(Foo).id();
+        let valid = app.world_mut().spawn((Foo, Bar)).id();
+        app.update();
+
+        let ledger = app.world().resource::<CheckLedger>();
+        assert!(ledger.is_invalid(invalid));
+        assert!(ledger.is_valid(valid));
+        assert_eq!(
+            ledger.filter_valid([invalid, valid]).collect::<Vec<_>>(),
+            [valid]
+        );
+        assert!(!app.world().entity(invalid).contains::<Checked>());
+        assert!(!app.world().entity(valid).contains::<Checked>());
+
+        app.world_mut().despawn(invalid);
+        app.update();
+
+        assert!(!app.world().resource::<CheckLedger>().is_checked(invalid));
+    }
+
+    #[test]
+    fn test_marker_free_check_again() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, CheckPlugin::default().marker_free()))
+            .check::<Foo, Without<Bar>>(invalid());
+
+        let entity = app.world_mut().spawn_empty().id();
+        app.update();
+
+        assert!(!app.world().resource::<CheckLedger>().is_checked(entity));
+
+        app.world_mut().entity_mut(entity).insert(Foo);
+        app.update();
+
+        assert!(app.world().resource::<CheckLedger>().is_invalid(entity));
+
+        app.world_mut().entity_mut(entity).insert(Bar);
+        app.update();
+
+        assert!(app.world().resource::<CheckLedger>().is_valid(entity));
+    }
+
+    #[test]
+    fn test_check_coverage() {
+        use bevy_reflect::TypePath;
+
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct Baz;
+
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct Qux;
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .register_type::<Baz>()
+            .register_type::<Qux>()
+            .check::<Baz, Without<Bar>>(invalid());
+
+        let report = app.world().check_coverage();
+        let prefix = module_path!();
+        assert!(report.checked().contains(&Baz::type_path()));
+        assert_eq!(
+            report.unchecked_in(prefix).collect::<Vec<_>>(),
+            [Qux::type_path()]
+        );
+        assert!(!report
+            .unchecked()
+            .iter()
+            .any(|path| path.ends_with("::Checked")));
+    }
+
+    #[cfg(feature = "remote")]
+    #[test]
+    fn test_remote_revalidate() {
+        use bevy_ecs::system::RunSystemOnce;
+        use serde_json::json;
+
+        use crate::remote::process_revalidate_request;
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid());
+
+        let entity = app.world_mut().spawn(Foo).id();
+        let despawned = app.world_mut().spawn(Foo).id();
+        app.update();
+        app.world_mut().despawn(despawned);
+
+        let params = json!({ "entities": [entity.to_bits(), despawned.to_bits()] });
+        let result = app
+            .world_mut()
+            .run_system_once_with(Some(params), process_revalidate_request)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result, json!({ "count": 1 }));
+        assert!(!app.world().entity(entity).contains::<Checked>());
+
+        // NOTE: Zero is never a valid entity, since its generation is zero.
+        let params = json!({ "entities": [0] });
+        let error = app
+            .world_mut()
+            .run_system_once_with(Some(params), process_revalidate_request)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(error.code, bevy_remote::error_codes::INVALID_PARAMS);
+    }
+
+    #[cfg(feature = "remote")]
+    #[test]
+    fn test_remote_list_invalid() {
+        use bevy_ecs::system::RunSystemOnce;
+
+        use crate::remote::process_list_invalid_request;
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid());
+
+        let invalid = app.world_mut().spawn(Foo).id();
+        app.world_mut().spawn((Foo, Bar));
+        app.update();
+
+        let result = app
+            .world_mut()
+            .run_system_once_with(None, process_list_invalid_request)
+            .unwrap()
+            .unwrap();
+        let entities = result.as_array().unwrap();
+        assert_eq!(entities.len(), 1);
+        assert_eq!(entities[0]["entity"], invalid.to_bits());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_in_schedule_after_checks() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid())
+            .add_plugins(CheckPlugin::default().in_schedule(First));
+    }
+
+    #[test]
+    fn test_strip_loaded_markers() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid());
+
+        // NOTE: Simulate an entity which was saved after it was checked.
+        let entity = app.world_mut().spawn((Foo, Save, Checked)).id();
+        app.world_mut().insert_resource(Loaded {
+            entity_map: Default::default(),
+        });
+        app.update();
+
+        assert!(app.world().entity(entity).contains::<Invalid>());
+    }
+
+    #[test]
+    fn test_recheck_after_repair() {
+        #[derive(Component)]
+        struct Baz;
+
+        let repairs = Arc::new(AtomicU64::new(0));
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(repair({
+                let repairs = repairs.clone();
+                move |_: EntityRef, _: &mut Commands| {
+                    // NOTE: This repair never fixes the instance.
+                    repairs.fetch_add(1, Ordering::Relaxed);
+                }
+            }));
+
+        let entity = app.world_mut().spawn(Foo).id();
+        for _ in 0..4 {
+            app.update();
+        }
+
+        assert_eq!(repairs.load(Ordering::Relaxed), 1);
+
+        app.world_mut().entity_mut(entity).insert(Baz);
+        app.update();
+
+        assert_eq!(repairs.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_recheck_skips_load_checks() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check_on_load::<Foo, Without<Bar>>(invalid());
+
+        let entity = app.world_mut().spawn((Foo, Bar)).id();
+        app.world_mut().insert_resource(Loaded {
+            entity_map: Default::default(),
+        });
+        app.update();
+
+        app.world_mut().entity_mut(entity).remove::<Bar>();
+        app.update();
+        app.update();
+
+        assert!(!app.world().entity(entity).contains::<Invalid>());
+    }
+
+    #[test]
+    #[cfg(all(feature = "dump", not(target_arch = "wasm32")))]
+    fn test_dump() {
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct Baz;
+
+        let path =
+            std::env::temp_dir().join(format!("moonshine_check_dump_{}", std::process::id()));
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .register_type::<Baz>()
+            .check::<Foo, Without<Bar>>(dump(path.join("flat"), false))
+            .check::<Baz, Without<Bar>>(dump_and_purge(path.join("recursive"), true));
+
+        let foo = app.world_mut().spawn(Foo).with_child(Baz).id();
+        let baz = app.world_mut().spawn(Baz).with_child(Baz).id();
+        app.update();
+
+        assert!(app.world().entity(foo).contains::<Invalid>());
+        assert!(app.world().get_entity(baz).is_err());
+
+        let flat =
+            std::fs::read_to_string(path.join("flat").join(format!("{foo}.scn.ron"))).unwrap();
+        let recursive =
+            std::fs::read_to_string(path.join("recursive").join(format!("{baz}.scn.ron"))).unwrap();
+        assert_eq!(flat.matches("Baz").count(), 0);
+        assert_eq!(recursive.matches("Baz").count(), 2);
+
+        std::fs::remove_dir_all(path).unwrap();
+    }
+
+    #[test]
+    fn test_dump_policy_kind() {
+        // NOTE: Dump policies exist regardless of features, so matches on them are always exhaustive.
+        let policy = Policy::Dump {
+            path: PathBuf::new(),
+            recursive: false,
+            then: Box::new(Policy::Purge),
+        };
+        assert_eq!(PolicyKind::of(&policy), PolicyKind::Dump);
+        assert_eq!(planned_outcome(&policy), Some(Outcome::Purged));
+    }
+
+    /// Records all spans and events emitted on the current thread while running a closure.
+    #[derive(Clone, Default)]
+    struct Capture {
+        spans: Arc<Mutex<Vec<(span::Id, &'static str, Fields)>>>,
+        events: Arc<Mutex<Vec<(Level, String)>>>,
+    }
+
+    impl Capture {
+        fn run<R>(&self, f: impl FnOnce() -> R) -> R {
+            use bevy::log::tracing_subscriber::{layer::SubscriberExt, registry};
+
+            bevy_utils::tracing::subscriber::with_default(registry().with(self.clone()), f)
+        }
+
+        fn span(&self, name: &str, kind: &str) -> Option<Fields> {
+            let spans = self.spans.lock().unwrap();
+            spans
+                .iter()
+                .find(|(_, span, fields)| *span == name && fields.get("kind") == Some(kind))
+                .map(|(.., fields)| fields.clone())
+        }
+
+        fn events(&self, message: &str) -> Vec<Level> {
+            let events = self.events.lock().unwrap();
+            events
+                .iter()
+                .filter(|(_, event)| event.contains(message))
+                .map(|(level, _)| *level)
+                .collect()
+        }
+    }
+
+    impl<S> Layer<S> for Capture
+    where
+        S: bevy_utils::tracing::Subscriber + for<'a> LookupSpan<'a>,
+    {
+        fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, _: Context<'_, S>) {
+            let mut fields = Fields::default();
+            attrs.record(&mut fields);
+            let name = attrs.metadata().name();
+            self.spans.lock().unwrap().push((id.clone(), name, fields));
+        }
+
+        fn on_record(&self, id: &span::Id, values: &span::Record<'_>, _: Context<'_, S>) {
+            let mut spans = self.spans.lock().unwrap();
+            if let Some((.., fields)) = spans.iter_mut().rev().find(|(span, ..)| span == id) {
+                values.record(fields);
+            }
+        }
+
+        fn on_event(&self, event: &bevy_utils::tracing::Event<'_>, _: Context<'_, S>) {
+            let mut fields = Fields::default();
+            event.record(&mut fields);
+            let message = fields.get("message").unwrap_or_default().to_owned();
+            let level = *event.metadata().level();
+            self.events.lock().unwrap().push((level, message));
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct Fields(Vec<(&'static str, String)>);
+
+    impl Fields {
+        fn get(&self, name: &str) -> Option<&str> {
+            self.0
+                .iter()
+                .rev()
+                .find(|(field, _)| *field == name)
+                .map(|(_, value)| value.as_str())
+        }
+    }
+
+    impl Visit for Fields {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.push((field.name(), value.to_owned()));
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0.push((field.name(), format!("{value:?}")));
+        }
+    }
+
+    fn single_threaded(schedule: &mut Schedule) {
+        // NOTE: Spans and events are only captured on the current thread.
+        schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+    }
+
+    #[test]
+    fn test_check_span() {
+        let capture = Capture::default();
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .edit_schedule(PreUpdate, single_threaded)
+            .check::<Foo, Without<Bar>>(invalid());
+
+        app.world_mut().spawn(Foo);
+        app.world_mut().spawn((Foo, Bar));
+        capture.run(|| app.update());
+
+        let span = capture.span("check", "Foo").unwrap();
+        assert!(span.get("filter").unwrap().contains("Without<"));
+        assert_eq!(span.get("count"), Some("2"));
+    }
+
+    #[test]
+    fn test_log_level() {
+        #[derive(Component)]
+        struct Baz;
+
+        let capture = Capture::default();
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .edit_schedule(PreUpdate, single_threaded)
+            .check::<Foo, Without<Bar>>(invalid().log_level(Level::WARN))
+            .check::<Baz, Without<Bar>>(invalid().silent());
+
+        let foo = app.world_mut().spawn(Foo).id();
+        let baz = app.world_mut().spawn(Baz).id();
+        capture.run(|| app.update());
+
+        assert!(app.world().entity(foo).contains::<Invalid>());
+        assert!(app.world().entity(baz).contains::<Invalid>());
+        // NOTE: Only the failure of `Foo` is logged, since the `Baz` check is silent.
+        assert_eq!(capture.events("is invalid"), [Level::WARN]);
+    }
+
+    #[cfg(feature = "graveyard")]
+    #[test]
+    fn test_graveyard() {
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct Health(u32);
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .register_type::<Health>()
+            .insert_resource(Graveyard::new(2))
+            .check::<Foo, Without<Bar>>(purge());
+
+        let entity = app
+            .world_mut()
+            .spawn((Foo, Health(7)))
+            .with_children(|parent| {
+                parent.spawn(Health(3));
+            })
+            .id();
+        app.update();
+
+        assert!(app.world().get_entity(entity).is_err());
+
+        let graveyard = app.world().resource::<Graveyard>();
+        assert_eq!(graveyard.len(), 1);
+        let grave = graveyard.iter().next().unwrap();
+        assert_eq!(grave.entity, entity);
+        assert_eq!(grave.check, check_name::<Foo, Without<Bar>>());
+        assert_eq!(grave.scene.entities.len(), 2);
+        let health = grave
+            .scene
+            .entities
+            .iter()
+            .find(|dead| dead.entity == entity)
+            .and_then(|dead| {
+                dead.components
+                    .iter()
+                    .find_map(|component| component.try_downcast_ref::<Health>())
+            })
+            .unwrap();
+        assert_eq!(health.0, 7);
+
+        // NOTE: Only the most recent graves are kept.
+        app.world_mut().spawn(Foo);
+        app.world_mut().spawn(Foo);
+        app.update();
+
+        let graveyard = app.world().resource::<Graveyard>();
+        assert_eq!(graveyard.len(), 2);
+        assert!(graveyard.iter().all(|grave| grave.entity != entity));
+    }
+
+    #[test]
+    fn test_parallel() {
+        let failures = Arc::new(Mutex::new(Vec::new()));
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(OnCheckFailure::new({
+                let failures = failures.clone();
+                move |failure| failures.lock().unwrap().push(failure.entity)
+            }))
+            .check::<Foo, Without<Bar>>(invalid());
+
+        let entities: Vec<Entity> = (0..1000)
+            .map(|i| {
+                if i % 2 == 0 {
+                    app.world_mut().spawn(Foo).id()
+                } else {
+                    app.world_mut().spawn((Foo, Bar)).id()
+                }
+            })
+            .collect();
+        app.update();
+
+        // NOTE: Instances are only evaluated in parallel if the compute task pool exists.
+        assert!(ComputeTaskPool::try_get().is_some());
+        for (i, entity) in entities.iter().enumerate() {
+            let entity = app.world().entity(*entity);
+            assert!(entity.contains::<Checked>());
+            assert_eq!(entity.contains::<Invalid>(), i % 2 == 0);
+        }
+
+        let report = app.world().resource::<CheckReport>();
+        let stats = report.get(&check_name::<Foo, Without<Bar>>()).unwrap();
+        assert_eq!(stats.checked, 1000);
+        assert_eq!(stats.invalid, 500);
+
+        // NOTE: Failures are reported in a deterministic order, regardless of evaluation order.
+        let failures = failures.lock().unwrap();
+        assert_eq!(failures.len(), 500);
+        assert!(failures.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_batched_policies() {
+        #[derive(Component)]
+        struct Baz;
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid())
+            .check::<Baz, Without<Bar>>(purge());
+
+        let invalid: Vec<Entity> = (0..1000).map(|_| app.world_mut().spawn(Foo).id()).collect();
+        let purged: Vec<Entity> = (0..1000)
+            .map(|_| {
+                app.world_mut()
+                    .spawn(Baz)
+                    .with_children(|parent| {
+                        parent.spawn_empty();
+                    })
+                    .id()
+            })
+            .collect();
+        let children: Vec<Entity> = purged
+            .iter()
+            .map(|entity| app.world().get::<Children>(*entity).unwrap()[0])
+            .collect();
+        app.update();
+
+        for entity in invalid {
+            let entity = app.world().entity(entity);
+            assert!(entity.contains::<Checked>());
+            assert!(entity.contains::<Invalid>());
+        }
+        for entity in purged.into_iter().chain(children) {
+            assert!(app.world().get_entity(entity).is_err());
+        }
+    }
+
+    #[test]
+    fn test_check_later_instances() {
+        #[derive(Component)]
+        struct Baz;
+
+        kind!(Baz is Foo);
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid())
+            .inherit_checks::<Baz, Foo>();
+
+        let valid = app.world_mut().spawn((Foo, Bar)).id();
+        app.update();
+
+        // NOTE: Check systems are skipped while there are no unchecked instances.
+        for _ in 0..3 {
+            app.update();
+        }
+        assert!(!app.world().entity(valid).contains::<Invalid>());
+
+        let foo = app.world_mut().spawn(Foo).id();
+        let baz = app.world_mut().spawn(Baz).id();
+        app.update();
+
+        assert!(app.world().entity(foo).contains::<Invalid>());
+        assert!(app.world().entity(baz).contains::<Invalid>());
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_metrics() {
+        use metrics::{
+            Counter, CounterFn, Gauge, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder,
+            SharedString, Unit,
+        };
+
+        type Values = Arc<Mutex<HashMap<String, f64>>>;
+
+        /// Sums all values recorded for each metric, by name.
+        #[derive(Default)]
+        struct TestRecorder(Values);
+
+        struct TestMetric(String, Values);
+
+        impl TestMetric {
+            fn add(&self, value: f64) {
+                *self.1.lock().unwrap().entry(self.0.clone()).or_default() += value;
+            }
+        }
+
+        impl CounterFn for TestMetric {
+            fn increment(&self, value: u64) {
+                self.add(value as f64);
+            }
+
+            fn absolute(&self, _value: u64) {
+                unimplemented!()
+            }
+        }
+
+        impl HistogramFn for TestMetric {
+            fn record(&self, _value: f64) {
+                // NOTE: Count the samples, since durations are not deterministic.
+                self.add(1.0);
+            }
+        }
+
+        impl TestRecorder {
+            fn metric(&self, key: &Key) -> Arc<TestMetric> {
+                Arc::new(TestMetric(key.name().to_owned(), self.0.clone()))
+            }
+        }
+
+        impl Recorder for TestRecorder {
+            fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+            fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+            fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+            fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+                Counter::from_arc(self.metric(key))
+            }
+
+            fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+                Gauge::noop()
+            }
+
+            fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+                Histogram::from_arc(self.metric(key))
+            }
+        }
+
+        let recorder = TestRecorder::default();
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .edit_schedule(PreUpdate, single_threaded)
+            .check::<Foo, Without<Bar>>(invalid());
+
+        app.world_mut().spawn(Foo);
+        app.world_mut().spawn((Foo, Bar));
+        metrics::with_local_recorder(&recorder, || app.update());
+
+        // NOTE: Nothing is exported for passes with no checked instances.
+        metrics::with_local_recorder(&recorder, || app.update());
+
+        let values = recorder.0.lock().unwrap();
+        assert_eq!(values["moonshine_check_checked_total"], 2.0);
+        assert_eq!(values["moonshine_check_invalid_total"], 1.0);
+        assert_eq!(values["moonshine_check_purged_total"], 0.0);
+        assert_eq!(values["moonshine_check_duration_seconds"], 1.0);
+    }
+
+    #[test]
+    fn test_purge_after() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(purge_after_frames(1));
+
+        let entity = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        assert!(app.world().entity(entity).contains::<Invalid>());
+        assert!(app.world().entity(entity).contains::<PendingPurge>());
+
+        app.update();
+
+        assert!(app.world().get_entity(entity).is_ok());
+
+        app.update();
+
+        assert!(app.world().get_entity(entity).is_err());
+    }
+
+    #[test]
+    fn test_dry_run_purge_after() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(purge_after_frames(0));
+
+        let entity = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        assert!(app.world().entity(entity).contains::<PendingPurge>());
+
+        app.world_mut().insert_resource(DryRun);
+        app.update();
+
+        assert!(app.world().get_entity(entity).is_ok());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_panic() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(panic());
+
+        app.world_mut().spawn(Foo);
+        app.update();
+    }
+
+    #[test]
+    #[should_panic(expected = "Components: [Foo]")]
+    fn test_panic_components() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(panic());
+
+        app.world_mut().spawn(Foo);
+        app.update();
+    }
+
+    #[test]
+    fn test_repair() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(repair(|entity: EntityRef, commands: &mut Commands| {
+                commands.entity(entity.id()).insert(Bar);
+            }));
+
+        let entity = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        assert!(app.world().entity(entity).contains::<Bar>());
+        assert!(app.world().entity(entity).contains::<Checked>());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_check_again() {
+        #[derive(Component)]
+        struct Repaired;
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(repair(|entity: EntityRef, commands: &mut Commands| {
+                // Avoid infinite repair loop
+                if entity.contains::<Repaired>() {
+                    panic!("Bar is still missing!");
+                }
+
+                // Oops! Maybe we forget to insert Bar ...
+                // Check again to be sure:
+                commands.entity(entity.id()).insert(Repaired).check_again();
+            }));
+
+        let entity = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        assert!(!app.world().entity(entity).contains::<Bar>());
+        assert!(!app.world().entity(entity).contains::<Checked>());
+
+        app.update(); // Should panic!
+    }
+
+    #[test]
+    fn test_snapshot() {
+        #[derive(Component, Reflect)]
+        struct Baz(u32);
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins).check::<Foo, Without<Bar>>(
+            purge().with_snapshot(SnapshotHook::on([Outcome::Purged]).capture::<Baz>()),
+        );
+
+        app.world_mut().spawn((Foo, Bar, Baz(0)));
+        let entity = app.world_mut().spawn((Foo, Baz(1))).id();
+        app.update();
+
+        let events = app.world().resource::<Events<OutcomeSnapshot>>();
+        let snapshots: Vec<_> = events.iter_current_update_events().collect();
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].entity, entity);
+        assert_eq!(snapshots[0].outcome, Outcome::Purged);
+        let baz = Baz::from_reflect(snapshots[0].components[0].as_ref()).unwrap();
+        assert_eq!(baz.0, 1);
+    }
+
+    #[test]
+    fn test_check_on_load() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check_on_load::<Foo, Without<Bar>>(invalid());
+
+        let spawned = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        assert!(app.world().entity(spawned).contains::<Checked>());
+        assert!(!app.world().entity(spawned).contains::<Invalid>());
+
+        let loaded = app.world_mut().spawn(Foo).id();
+        app.world_mut().insert_resource(Loaded {
+            entity_map: Default::default(),
+        });
+        app.update();
+
+        assert!(app.world().entity(loaded).contains::<Invalid>());
+        assert!(!app.world().entity(spawned).contains::<Invalid>());
+    }
+
+    #[test]
+    fn test_audit_on_startup_with_load_checks() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, CheckPlugin::default().audit_on_startup()))
+            .check_on_load::<Foo, Without<Bar>>(invalid())
+            .migrate_before::<Foo, (With<Foo>, Without<Bar>)>(1, repair_insert(Bar));
+
+        let entity = app.world_mut().spawn((Foo, Checked)).id();
+        app.update();
+        app.update();
+
+        // NOTE: Audited instances must be checked again, even if no data is loaded.
+        assert!(app.world().entity(entity).contains::<Checked>());
+        assert!(!app.world().entity(entity).contains::<Invalid>());
+    }
+
+    #[test]
+    fn test_migrate_before() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .migrate_before::<Foo, Without<Bar>>(3, repair_insert(Bar));
+
+        let old = app.world_mut().spawn(Foo).id();
+        app.world_mut().insert_resource(SaveVersion(2));
+        app.world_mut().insert_resource(Loaded {
+            entity_map: Default::default(),
+        });
+        app.update();
+
+        assert!(app.world().entity(old).contains::<Bar>());
+
+        let new = app.world_mut().spawn(Foo).id();
+        app.world_mut().insert_resource(SaveVersion(3));
+        app.world_mut().insert_resource(Loaded {
+            entity_map: Default::default(),
+        });
+        app.update();
+
+        assert!(!app.world().entity(new).contains::<Bar>());
+        assert!(app.world().entity(new).contains::<Checked>());
+
+        let spawned = app.world_mut().spawn(Foo).id();
+        app.world_mut().insert_resource(SaveVersion(2));
+        app.update();
+
+        // NOTE: Instances which are not loaded are not migrated.
+        assert!(!app.world().entity(spawned).contains::<Bar>());
+        assert!(app.world().entity(spawned).contains::<Checked>());
+    }
+
+    #[test]
+    fn test_revalidate() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid().revalidate_every(Duration::ZERO));
+
+        let entity = app.world_mut().spawn((Foo, Bar)).id();
+        app.update();
+
+        assert!(app.world().entity(entity).contains::<Checked>());
+        assert!(!app.world().entity(entity).contains::<Invalid>());
+
+        app.world_mut().entity_mut(entity).remove::<Bar>();
+        app.update();
+
+        assert!(app.world().entity(entity).contains::<Invalid>());
+    }
+
+    #[test]
+    fn test_watch() {
+        #[derive(Component)]
+        struct Baz(u32);
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid().watch::<Baz>());
+
+        let entity = app.world_mut().spawn((Foo, Bar, Baz(0))).id();
+        app.update();
+
+        assert!(app.world().entity(entity).contains::<Checked>());
+        assert!(!app.world().entity(entity).contains::<Invalid>());
+
+        let mut entity_mut = app.world_mut().entity_mut(entity);
+        entity_mut.remove::<Bar>();
+        entity_mut.get_mut::<Baz>().unwrap().0 = 1;
+        app.update();
+
+        assert!(app.world().entity(entity).contains::<Invalid>());
+    }
+
+    #[test]
+    fn test_recheck_on_removal() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid());
+
+        let entity = app.world_mut().spawn((Foo, Bar)).id();
+        app.update();
+
+        assert!(!app.world().entity(entity).contains::<Invalid>());
+
+        app.world_mut().entity_mut(entity).remove::<Bar>();
+        app.update();
+
+        assert!(app.world().entity(entity).contains::<Invalid>());
+    }
+
+    #[test]
+    fn test_check_again_all() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid());
+
+        let a = app.world_mut().spawn(Foo).id();
+        let b = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        assert!(app.world().entity(a).contains::<Invalid>());
+        assert!(app.world().entity(b).contains::<Invalid>());
+
+        app.world_mut().entity_mut(a).insert(Bar);
+        app.world_mut().entity_mut(b).insert(Bar);
+        app.world_mut().check_again_all::<Foo>();
+        app.update();
+
+        assert!(!app.world().entity(a).contains::<Invalid>());
+        assert!(!app.world().entity(b).contains::<Invalid>());
+        assert!(app.world().entity(a).contains::<Checked>());
+    }
+
+    #[test]
+    fn test_invalid_reason() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid());
+
+        let checked = app.world_mut().spawn(Foo).id();
+        let marked = app.world_mut().spawn((Foo, Bar)).id();
+        app.update();
+        app.world_mut()
+            .entity_mut(marked)
+            .mark_invalid("Foo is corrupted");
+
+        let elapsed = app.world().resource::<Time>().elapsed();
+        let invalid = app.world().get::<Invalid>(checked).unwrap();
+        assert!(invalid.reason.contains("Without<"));
+        assert!(invalid.time <= elapsed);
+        let invalid = app.world().get::<Invalid>(marked).unwrap();
+        assert_eq!(invalid.reason, "Foo is corrupted");
+        assert_eq!(invalid.time, elapsed);
+    }
+
+    #[cfg(feature = "egui")]
+    #[test]
+    fn test_check_panel_closed() {
+        use crate::egui::{CheckPanel, CheckPanelPlugin, SelectedInvalid};
+
+        // NOTE: Without an open panel, no egui context is required.
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, CheckPanelPlugin))
+            .insert_resource(CheckPanel { open: false })
+            .check::<Foo, Without<Bar>>(invalid());
+
+        let entity = app.world_mut().spawn(Foo).id();
+        app.update();
+        app.update();
+
+        assert!(app.world().entity(entity).contains::<Invalid>());
+        assert_eq!(app.world().resource::<SelectedInvalid>().0, None);
+    }
+
+    #[cfg(feature = "egui")]
+    #[test]
+    fn test_check_panel() {
+        use bevy::{input::InputPlugin, window::WindowPlugin};
+        use bevy_egui::EguiPlugin;
+
+        use crate::egui::{CheckPanel, CheckPanelPlugin};
+
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            InputPlugin,
+            WindowPlugin::default(),
+            EguiPlugin,
+            CheckPanelPlugin,
+        ))
+        .check::<Foo, Without<Bar>>(invalid());
+
+        let entity = app.world_mut().spawn((Foo, Name::new("Foo"))).id();
+        app.update();
+        app.update();
+
+        assert!(app.world().entity(entity).contains::<Invalid>());
+        assert!(app.world().resource::<CheckPanel>().open);
+    }
+
+    #[cfg(feature = "gizmos")]
+    #[test]
+    fn test_invalid_gizmos() {
+        use bevy::gizmos::{
+            config::{DefaultGizmoConfigGroup, GizmoConfigStore},
+            gizmos::GizmoStorage,
+        };
+
+        use crate::gizmos::{CheckGizmosPlugin, InvalidGizmos};
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, CheckGizmosPlugin))
+            .init_resource::<GizmoConfigStore>()
+            .init_resource::<GizmoStorage<DefaultGizmoConfigGroup, ()>>()
+            .check::<Foo, Without<Bar>>(invalid());
+        app.world_mut()
+            .resource_mut::<GizmoConfigStore>()
+            .register::<DefaultGizmoConfigGroup>();
+
+        app.world_mut().spawn((Foo, Bar, GlobalTransform::default()));
+        app.update();
+
+        let storage = app
+            .world()
+            .resource::<GizmoStorage<DefaultGizmoConfigGroup, ()>>();
+        assert!(storage.list_positions.is_empty());
+
+        app.world_mut().spawn((Foo, GlobalTransform::default()));
+        app.update();
+
+        let storage = app
+            .world()
+            .resource::<GizmoStorage<DefaultGizmoConfigGroup, ()>>();
+        assert!(!storage.list_positions.is_empty());
+
+        app.world_mut().resource_mut::<InvalidGizmos>().enabled = false;
+        app.world_mut()
+            .resource_mut::<GizmoStorage<DefaultGizmoConfigGroup, ()>>()
+            .list_positions
+            .clear();
+        app.update();
+
+        let storage = app
+            .world()
+            .resource::<GizmoStorage<DefaultGizmoConfigGroup, ()>>();
+        assert!(storage.list_positions.is_empty());
+    }
+
+    #[test]
+    fn test_mark_invalid() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid());
+
+        let entity = app.world_mut().spawn((Foo, Bar)).id();
+        app.update();
+
+        assert!(!app.world().entity(entity).contains::<Invalid>());
+
+        app.world_mut()
+            .entity_mut(entity)
+            .mark_invalid("Foo is corrupted");
+
+        assert!(app.world().entity(entity).contains::<Checked>());
+        assert!(app.world().entity(entity).contains::<Invalid>());
+    }
+
+    #[test]
+    fn test_mark_invalid_failure() {
+        let failures = Arc::new(Mutex::new(Vec::new()));
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(ValidationLog::new(8))
+            .insert_resource(OnCheckFailure::new({
+                let failures = failures.clone();
+                move |failure| failures.lock().unwrap().push(failure.check.clone())
+            }));
+
+        let entity = app.world_mut().spawn(Foo).id();
+        app.world_mut()
+            .commands()
+            .entity(entity)
+            .mark_invalid("Foo is corrupted");
+        app.world_mut().flush();
+
+        assert!(app.world().entity(entity).contains::<Invalid>());
+        assert_eq!(*failures.lock().unwrap(), ["Foo is corrupted"]);
+        let log = app.world().resource::<ValidationLog>();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log.iter().next().unwrap().outcome, Outcome::Invalid);
+    }
+
+    #[test]
+    fn test_mark_invalid_dry_run() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins).insert_resource(DryRun);
+
+        let entity = app.world_mut().spawn(Foo).id();
+        app.world_mut()
+            .entity_mut(entity)
+            .mark_invalid("Foo is corrupted");
+
+        assert!(!app.world().entity(entity).contains::<Invalid>());
+    }
+
+    #[test]
+    fn test_validate_now() {
+        #[derive(Component)]
+        struct Baz;
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid())
+            .check::<Foo, Without<Baz>>(invalid());
+
+        let entity = app.world_mut().spawn((Foo, Bar)).id();
+        let errors = app.world_mut().validate_now(entity).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].check, check_name::<Foo, Without<Baz>>());
+
+        app.world_mut().entity_mut(entity).insert(Baz);
+        assert!(app.world_mut().validate_now(entity).is_ok());
+
+        let other = app.world_mut().spawn(Bar).id();
+        assert!(app.world_mut().validate_now(other).is_ok());
+    }
+
+    #[test]
+    fn test_report() {
+        #[derive(Component)]
+        struct Baz;
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid())
+            .check::<Foo, Without<Baz>>(purge());
+
+        app.world_mut().spawn((Foo, Bar, Baz));
+        app.world_mut().spawn((Foo, Bar));
+        app.world_mut().spawn(Foo);
+        app.update();
+
+        let report = app.world().resource::<CheckReport>();
+        let bar = report.get(&check_name::<Foo, Without<Bar>>()).unwrap();
+        assert_eq!(bar.checked, 3);
+        assert_eq!(bar.passed, 2);
+        assert_eq!(bar.invalid, 1);
+        let baz = report.get(&check_name::<Foo, Without<Baz>>()).unwrap();
+        assert_eq!(baz.checked, 3);
+        assert_eq!(baz.passed, 1);
+        assert_eq!(baz.purged, 2);
+
+        app.update();
+
+        let report = app.world().resource::<CheckReport>();
+        assert_eq!(report.total(), CheckStats::default());
+    }
+
+    #[test]
+    fn test_dump_components() {
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct Baz(u32);
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins).register_type::<Baz>();
+
+        let entity = app.world_mut().spawn((Foo, Baz(7))).id();
+        let world = app.world();
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let dump = dump_components(world.entity(entity), world, &registry);
+        assert!(dump.contains("Foo"));
+        assert!(dump.contains("Baz"));
+        assert!(dump.contains('7'));
+    }
+
+    #[test]
+    fn test_assert_macros() {
+        use crate::test::RunChecks;
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid());
+
+        let a = app.world_mut().spawn((Foo, Bar)).id();
+        let b = app.world_mut().spawn(Foo).id();
+        app.run_checks_once();
+
+        crate::assert_valid!(app.world(), a);
+        crate::assert_invalid!(app.world(), b, "Without<Bar>");
+    }
+
+    #[test]
+    fn test_fuzzer_diverged() {
+        use crate::test::{FuzzError, Fuzzer};
+
+        #[derive(Component, Clone)]
+        struct Baz;
+
+        // These repairs undo each other:
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(repair_replace::<Baz, Bar>(Bar))
+            .check::<Foo, Without<Baz>>(repair_replace::<Bar, Baz>(Baz));
+
+        let result = Fuzzer::new(1).with(Foo).with(Bar).run(&mut app);
+        assert!(matches!(result, Err(FuzzError::Diverged { .. })));
+    }
+
+    #[test]
+    fn test_budget() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(CheckBudget::new(Duration::ZERO))
+            .check::<Foo, Without<Bar>>(invalid());
+
+        let entity = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        assert!(!app.world().entity(entity).contains::<Checked>());
+
+        app.world_mut().remove_resource::<CheckBudget>();
+        app.update();
+
+        assert!(app.world().entity(entity).contains::<Invalid>());
+    }
+
+    #[test]
+    fn test_budget_deferred() {
+        #[derive(Component)]
+        struct Baz;
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(CheckBudget::new(Duration::from_millis(1)))
+            .check::<Foo, Without<Baz>>(invalid());
+        // NOTE: Exhaust the budget with a slow check, so the other check defers the instance.
+        add_check::<Foo, ()>(
+            app.main_mut(),
+            invalid().check_before::<Foo, Without<Baz>>(),
+            CheckSpec {
+                description: "Slow".to_owned(),
+                condition: Some(condition(|_, _| {
+                    std::thread::sleep(Duration::from_millis(2));
+                    false
+                })),
+            },
+        );
+
+        let entity = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        assert!(!app.world().entity(entity).contains::<Checked>());
+
+        app.world_mut().remove_resource::<CheckBudget>();
+        app.update();
+
+        assert!(app.world().entity(entity).contains::<Invalid>());
+    }
+
+    #[test]
+    fn test_check_static() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check_static::<Foo, Without<Bar>, _>(RepairPolicy(
+                |entity: EntityRef, commands: &mut Commands| {
+                    commands.entity(entity.id()).insert(Bar);
+                },
+            ));
+
+        let entity = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        assert!(app.world().entity(entity).contains::<Bar>());
+        assert!(app.world().entity(entity).contains::<Checked>());
+    }
+
+    #[test]
+    #[should_panic(expected = "Components: [Foo")]
+    fn test_check_static_panic() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check_static::<Foo, Without<Bar>, _>(PanicPolicy);
+
+        app.world_mut().spawn(Foo);
+        app.update();
+    }
+
+    #[test]
+    fn test_shared_policy() {
+        #[derive(Component)]
+        struct Baz;
+
+        let policy = repair_insert(Bar);
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(policy.clone())
+            .check::<Baz, Without<Bar>>(policy);
+
+        let foo = app.world_mut().spawn(Foo).id();
+        let baz = app.world_mut().spawn(Baz).id();
+        app.update();
+
+        assert!(app.world().entity(foo).contains::<Bar>());
+        assert!(app.world().entity(baz).contains::<Bar>());
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derive() {
+        #[derive(Component, Check)]
+        #[check(requires(Bar), policy = "purge")]
+        struct Baz;
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins).add_checks_for::<Baz>();
+
+        let valid = app.world_mut().spawn((Baz, Bar)).id();
+        let purged = app.world_mut().spawn(Baz).id();
+        app.update();
+
+        assert!(app.world().entity(valid).contains::<Checked>());
+        assert!(app.world().get_entity(purged).is_err());
+    }
+
+    #[test]
+    fn test_checks_macro() {
+        #[derive(Component)]
+        struct Baz;
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        crate::checks!(app, {
+            Foo: requires Bar => purge;
+            Baz: requires Bar forbids Foo => repair_insert(Bar);
+        });
+
+        let foo = app.world_mut().spawn(Foo).id();
+        let baz = app.world_mut().spawn(Baz).id();
+        app.update();
+
+        assert!(app.world().get_entity(foo).is_err());
+        assert!(app.world().entity(baz).contains::<Bar>());
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn test_check_definitions() {
+        use bevy_reflect::TypePath;
+
+        use crate::config::{parse_check_definitions, LoadChecks};
+
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct Baz;
+
+        #[derive(Component, Reflect, Default)]
+        #[reflect(Component, Default)]
+        struct Qux;
+
+        let definitions = parse_check_definitions(&format!(
+            r#"[(kind: "{}", requires: ["{}"], policy: RepairInsertDefault("{}"))]"#,
+            Baz::type_path(),
+            Qux::type_path(),
+            Qux::type_path(),
+        ))
+        .unwrap();
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .register_type::<Baz>()
+            .register_type::<Qux>()
+            .add_check_definitions(definitions);
+
+        let entity = app.world_mut().spawn(Baz).id();
+        app.update();
+
+        assert!(app.world().entity(entity).contains::<Qux>());
+        assert!(app.world().entity(entity).contains::<Checked>());
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn test_component_check() {
+        use crate::config::{ComponentCheck, DynamicPolicy, LoadChecks};
+
+        let mut app = App::new();
+        let foo = app.world_mut().register_component::<Foo>();
+        let bar = app.world_mut().register_component::<Bar>();
+        app.add_plugins(MinimalPlugins)
+            .add_component_check(ComponentCheck {
+                kind: foo,
+                requires: vec![bar],
+                forbids: vec![],
+                policy: DynamicPolicy::Purge,
+            });
+
+        let valid = app.world_mut().spawn((Foo, Bar)).id();
+        let purged = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        assert!(app.world().entity(valid).contains::<Checked>());
+        assert!(app.world().get_entity(purged).is_err());
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn test_check_definitions_and_compiled_checks() {
+        use crate::config::{ComponentCheck, DynamicPolicy, LoadChecks};
+
+        let mut app = App::new();
+        let foo = app.world_mut().register_component::<Foo>();
+        let bar = app.world_mut().register_component::<Bar>();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, With<Bar>>(invalid())
+            .add_component_check(ComponentCheck {
+                kind: foo,
+                requires: vec![bar],
+                forbids: vec![],
+                policy: DynamicPolicy::Purge,
+            });
+
+        // NOTE: This entity passes the data-driven check, but must still be invalidated by the compiled check.
+        let invalid = app.world_mut().spawn((Foo, Bar)).id();
+        let purged = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        assert!(app.world().entity(invalid).contains::<Invalid>());
+        assert!(app.world().get_entity(purged).is_err());
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn test_check_definitions_spawn_checked() {
+        use crate::config::{ComponentCheck, DynamicPolicy, LoadChecks};
+
+        let mut app = App::new();
+        let foo = app.world_mut().register_component::<Foo>();
+        let bar = app.world_mut().register_component::<Bar>();
+        app.add_plugins(MinimalPlugins)
+            .add_component_check(ComponentCheck {
+                kind: foo,
+                requires: vec![bar],
+                forbids: vec![],
+                policy: DynamicPolicy::Invalid,
+            });
+
+        // NOTE: Data-driven checks are registered once they're resolved.
+        app.update();
+        assert_eq!(app.world().resource::<CheckRegistry>().len(), 1);
+
+        let entity = app.world_mut().commands().spawn_checked(Foo);
+        app.world_mut().flush();
+
+        assert!(app.world().entity(entity).contains::<Invalid>());
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn test_load_checks() {
+        use bevy_asset::AssetPlugin;
+        use bevy_reflect::TypePath;
+
+        use crate::config::LoadChecks;
+
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct Baz;
+
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct Qux;
+
+        let root = std::env::temp_dir().join(format!("moonshine_check_{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(
+            root.join("test.checks.ron"),
+            format!(
+                r#"[(kind: "{}", requires: ["{}"], policy: Purge)]"#,
+                Baz::type_path(),
+                Qux::type_path(),
+            ),
+        )
+        .unwrap();
+
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin {
+                file_path: root.to_string_lossy().into_owned(),
+                ..default()
+            },
+        ))
+        .register_type::<Baz>()
+        .register_type::<Qux>()
+        .load_checks("test.checks.ron");
+
+        for _ in 0..100 {
+            app.update();
+            if !app.world().resource::<CheckRegistry>().is_empty() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        let valid = app.world_mut().spawn((Baz, Qux)).id();
+        let purged = app.world_mut().spawn(Baz).id();
+        app.update();
+
+        assert!(app.world().entity(valid).contains::<Checked>());
+        assert!(app.world().get_entity(purged).is_err());
+    }
+
+    #[cfg(feature = "scripting")]
+    #[test]
+    fn test_scripted_checks() {
+        use bevy_reflect::TypePath;
+
+        use crate::config::{CheckDefinition, DynamicPolicy};
+        use crate::scripting::{ScriptedChecks, ScriptedChecksPlugin};
+
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct Baz;
+
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct Qux;
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, ScriptedChecksPlugin))
+            .register_type::<Baz>()
+            .register_type::<Qux>();
+
+        app.world_mut()
+            .resource_mut::<ScriptedChecks>()
+            .add_with_fixer(
+                CheckDefinition {
+                    kind: Baz::type_path().to_string(),
+                    requires: vec![Qux::type_path().to_string()],
+                    forbids: vec![],
+                    policy: DynamicPolicy::Invalid,
+                },
+                |world: &mut World, entity: Entity| {
+                    world.entity_mut(entity).insert(Qux);
+                },
+            );
+
+        let entity = app.world_mut().spawn(Baz).id();
+        app.update();
+
+        assert!(app.world().entity(entity).contains::<Qux>());
+        assert!(app.world().entity(entity).contains::<Checked>());
+    }
+
+    #[cfg(feature = "scripting")]
+    #[test]
+    fn test_scripted_and_compiled_checks() {
+        use bevy_reflect::TypePath;
+
+        use crate::config::{CheckDefinition, DynamicPolicy};
+        use crate::scripting::{ScriptedChecks, ScriptedChecksPlugin};
+
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct Baz;
+
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct Qux;
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, ScriptedChecksPlugin))
+            .register_type::<Baz>()
+            .register_type::<Qux>()
+            .check::<Baz, Without<Foo>>(invalid());
+
+        app.world_mut()
+            .resource_mut::<ScriptedChecks>()
+            .add(CheckDefinition {
+                kind: Baz::type_path().to_string(),
+                requires: vec![Qux::type_path().to_string()],
+                forbids: vec![],
+                policy: DynamicPolicy::Purge,
+            });
+
+        // NOTE: This entity passes the compiled check, but must still be purged by the scripted check.
+        let purged = app.world_mut().spawn((Baz, Foo)).id();
+        // NOTE: This entity passes the scripted check, but must still be invalidated by the compiled check.
+        let invalid = app.world_mut().spawn((Baz, Qux)).id();
+        let valid = app.world_mut().spawn((Baz, Foo, Qux)).id();
+        app.update();
+
+        assert!(app.world().get_entity(purged).is_err());
+        assert!(app.world().entity(invalid).contains::<Invalid>());
+        assert!(app.world().entity(valid).contains::<Checked>());
+        assert!(!app.world().entity(valid).contains::<Invalid>());
+    }
+
+    #[cfg(feature = "presets")]
+    #[test]
+    fn test_core_presets() {
+        use crate::presets::core::CoreChecksPlugin;
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, CoreChecksPlugin));
+
+        let entity = app
+            .world_mut()
+            .spawn(Transform::from_xyz(f32::NAN, 0.0, 0.0))
+            .id();
+        app.update();
+
+        assert_eq!(
+            *app.world().entity(entity).get::<Transform>().unwrap(),
+            Transform::IDENTITY
+        );
+        assert!(app.world().entity(entity).contains::<GlobalTransform>());
+        assert_eq!(app.world().resource::<CheckReport>().total().repaired, 1);
+    }
+
+    #[cfg(feature = "presets")]
+    #[test]
+    fn test_core_presets_dry_run() {
+        use crate::presets::core::CoreChecksPlugin;
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, CoreChecksPlugin))
+            .insert_resource(DryRun);
+
+        let entity = app
+            .world_mut()
+            .spawn(Transform::from_xyz(f32::NAN, 0.0, 0.0))
+            .id();
+        app.update();
+
+        let transform = app.world().entity(entity).get::<Transform>().unwrap();
+        assert!(transform.translation.x.is_nan());
+    }
+
+    #[cfg(feature = "presets_avian")]
+    #[test]
+    fn test_avian_presets() {
+        use avian3d::math::Vector;
+        use avian3d::prelude::*;
+
+        use crate::presets::avian::AvianChecksPlugin;
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AvianChecksPlugin));
+
+        let body = app.world_mut().spawn(RigidBody::Dynamic).id();
+        let moving = app
+            .world_mut()
+            .spawn((LinearVelocity(Vector::NAN), AngularVelocity(Vector::X)))
+            .id();
+        app.update();
+
+        assert!(app.world().entity(body).contains::<Invalid>());
+        let entity = app.world().entity(moving);
+        assert_eq!(
+            *entity.get::<LinearVelocity>().unwrap(),
+            LinearVelocity::ZERO
+        );
+        assert_eq!(
+            *entity.get::<AngularVelocity>().unwrap(),
+            AngularVelocity(Vector::X)
+        );
+    }
+
+    #[cfg(feature = "presets_rapier")]
+    #[test]
+    fn test_rapier_presets() {
+        use bevy_rapier3d::math::Vect;
+        use bevy_rapier3d::prelude::*;
+
+        use crate::presets::rapier::RapierChecksPlugin;
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, RapierChecksPlugin));
+
+        let body = app.world_mut().spawn(RigidBody::Dynamic).id();
+        let moving = app.world_mut().spawn(Velocity::linear(Vect::NAN)).id();
+        app.update();
+
+        assert!(app.world().entity(body).contains::<Invalid>());
+        assert_eq!(
+            *app.world().entity(moving).get::<Velocity>().unwrap(),
+            Velocity::zero()
+        );
+    }
+
+    #[cfg(feature = "presets_ui")]
+    #[test]
+    fn test_ui_presets() {
+        use crate::presets::ui::UiChecksPlugin;
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, UiChecksPlugin));
+
+        let parent = app.world_mut().spawn_empty().id();
+        let node = app
+            .world_mut()
+            .spawn(Node::default())
+            .set_parent(parent)
+            .id();
+        let span = app
+            .world_mut()
+            .spawn(bevy_text::TextSpan::default())
+            .set_parent(parent)
+            .id();
+        app.update();
+
+        assert!(app.world().entity(node).get::<Parent>().is_none());
+        assert!(app.world().get_entity(span).is_err());
+        assert_eq!(app.world().resource::<CheckReport>().total().purged, 1);
+    }
+
+    #[test]
+    fn test_check_parent() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+
+        let fallback = app.world_mut().spawn(Bar).id();
+        app.check_parent::<Foo, Bar>(repair_reparent(fallback));
+
+        let parent = app.world_mut().spawn(Bar).id();
+        let valid = app.world_mut().spawn(Foo).set_parent(parent).id();
+        let orphan = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        assert_eq!(
+            app.world().entity(valid).get::<Parent>().unwrap().get(),
+            parent
+        );
+        assert_eq!(
+            app.world().entity(orphan).get::<Parent>().unwrap().get(),
+            fallback
+        );
+    }
+
+    #[test]
+    fn test_check_children() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check_children::<Foo, Bar>(2, repair_spawn_children::<Bar, _>(2, Bar));
+
+        let entity = app.world_mut().spawn(Foo).with_child(Bar).id();
+        app.update();
+
+        let children = app.world().entity(entity).get::<Children>().unwrap();
+        assert_eq!(children.len(), 2);
+    }
+
+    #[test]
+    fn test_check_dangling_parents() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check_dangling_parents(repair_orphan());
+
+        let parent = app.world_mut().spawn_empty().id();
+        let entity = app.world_mut().spawn_empty().set_parent(parent).id();
+        // NOTE: Despawn without updating the hierarchy to leave a dangling reference.
+        app.world_mut().despawn(parent);
+        app.update();
+
+        assert!(app.world().entity(entity).get::<Parent>().is_none());
+    }
+
+    #[test]
+    fn test_check_hierarchy_cycles() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check_hierarchy_cycles(repair_orphan());
+
+        let a = app.world_mut().spawn_empty().id();
+        let b = app.world_mut().spawn_empty().set_parent(a).id();
+        app.world_mut().entity_mut(a).set_parent(b);
+        app.update();
+
+        assert!(app.world().entity(a).get::<Parent>().is_none());
+        assert_eq!(app.world().entity(b).get::<Parent>().unwrap().get(), a);
+    }
+
+    #[test]
+    fn test_check_max_depth() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check_max_depth::<Foo>(1, invalid());
+
+        let root = app.world_mut().spawn(Foo).id();
+        let child = app.world_mut().spawn(Foo).set_parent(root).id();
+        let grandchild = app.world_mut().spawn(Foo).set_parent(child).id();
+        app.update();
+
+        assert!(!app.world().entity(root).contains::<Invalid>());
+        assert!(!app.world().entity(child).contains::<Invalid>());
+        assert!(app.world().entity(grandchild).contains::<Invalid>());
+    }
+
+    #[cfg(feature = "presets")]
+    #[test]
+    fn test_hierarchy_presets() {
+        use crate::presets::hierarchy::HierarchyChecksPlugin;
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, HierarchyChecksPlugin));
+
+        let parent = app.world_mut().spawn_empty().id();
+        let child = app.world_mut().spawn_empty().set_parent(parent).id();
+        // NOTE: Remove `Children` directly to break the link.
+        app.world_mut().entity_mut(parent).remove::<Children>();
+        app.update();
+
+        let children = app.world().entity(parent).get::<Children>().unwrap();
+        assert_eq!(&children[..], &[child]);
+    }
+
+    #[test]
+    fn test_check_unique() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins).check_unique::<Foo>(purge());
+
+        let first = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        let second = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        assert!(app.world().get_entity(first).is_ok());
+        assert!(app.world().get_entity(second).is_err());
+    }
+
+    #[test]
+    fn test_check_unique_now() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins).check_unique::<Foo>(purge());
+
+        let first = app.world_mut().spawn(Foo).id();
+        let second = app.world_mut().spawn(Foo).id();
+        app.world_mut().apply_checks();
+
+        // NOTE: The check must not depend on any state computed by the check systems.
+        assert!(app.world().get_entity(first).is_ok());
+        assert!(app.world().get_entity(second).is_err());
+    }
+
+    #[test]
+    fn test_check_count() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check_count::<Foo>(2.., CountPolicy::spawn_missing(2, Foo));
+
+        app.world_mut().spawn(Foo);
+        app.update();
+
+        let violations = app.world().resource::<Events<CountViolation>>();
+        assert_eq!(violations.len(), 1);
+
+        let count = app.world_mut().query::<&Foo>().iter(app.world()).count();
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn test_dry_run_count() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(DryRun)
+            .check_count::<Foo>(2.., CountPolicy::spawn_missing(2, Foo));
+
+        app.world_mut().spawn(Foo);
+        app.update();
+
+        let violations = app.world().resource::<Events<CountViolation>>();
+        assert_eq!(violations.len(), 1);
+
+        let count = app.world_mut().query::<&Foo>().iter(app.world()).count();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_check_exclusive() {
+        #[derive(Component)]
+        struct Baz;
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check_exclusive::<Foo, (Bar, Baz)>(repair_exclusive::<(Bar, Baz)>());
+
+        let entity = app.world_mut().spawn((Foo, Bar, Baz)).id();
+        app.update();
+
+        assert!(app.world().entity(entity).contains::<Bar>());
+        assert!(!app.world().entity(entity).contains::<Baz>());
+    }
+
+    #[test]
+    fn test_check_pair() {
+        #[derive(Component, Clone, PartialEq, PartialOrd, Debug)]
+        struct Health(f32);
+
+        #[derive(Component)]
+        struct MaxHealth(f32);
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check_pair::<Health, MaxHealth>(
+                |health, max| health.0 <= max.0,
+                repair_clamp::<Health, MaxHealth>(|max| Health(max.0)),
+            );
+
+        let entity = app
+            .world_mut()
+            .spawn((Health(150.0), MaxHealth(100.0)))
+            .id();
+        app.update();
+
+        assert_eq!(
+            app.world().entity(entity).get::<Health>(),
+            Some(&Health(100.0))
+        );
+    }
+
+    #[test]
+    fn test_check_unique_value() {
+        #[derive(Component)]
+        struct Id(u32);
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check_unique_value::<Foo, Id, u32>(|id| id.0, invalid());
+
+        let a = app.world_mut().spawn((Foo, Id(1))).id();
+        let b = app.world_mut().spawn((Foo, Id(2))).id();
+        let c = app.world_mut().spawn((Foo, Id(1))).id();
+        app.update();
+
+        assert!(!app.world().entity(a).contains::<Invalid>());
+        assert!(!app.world().entity(b).contains::<Invalid>());
+        assert!(app.world().entity(c).contains::<Invalid>());
+
+        let d = app.world_mut().spawn((Foo, Id(2))).id();
+        let e = app.world_mut().spawn((Foo, Id(3))).id();
+        app.world_mut().apply_checks();
+
+        assert!(app.world().entity(d).contains::<Invalid>());
+        assert!(!app.world().entity(e).contains::<Invalid>());
+    }
+
+    #[test]
+    fn test_check_entity_refs() {
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct Target(Entity);
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .register_type::<Target>()
+            .check_entity_refs::<Foo>(repair_remove_dangling());
+
+        let target = app.world_mut().spawn_empty().id();
+        let valid = app.world_mut().spawn((Foo, Target(target))).id();
+        let dangling = app
+            .world_mut()
+            .spawn((Foo, Target(Entity::PLACEHOLDER)))
+            .id();
+        app.update();
+
+        assert!(app.world().entity(valid).contains::<Target>());
+        assert!(!app.world().entity(dangling).contains::<Target>());
+    }
+
+    #[test]
+    fn test_check_instance_field() {
+        #[derive(Component, Clone)]
+        struct Target(Option<Instance<Bar>>);
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check_instance_field::<Foo, Target, Bar>(
+                |target| target.0,
+                repair_instance_field::<Target>(|target| target.0 = None),
+            );
+
+        let bar = app.world_mut().spawn(Bar).id();
+        let not_bar = app.world_mut().spawn_empty().id();
+        // SAFE: `bar` is a `Bar`, and `not_bar` is intentionally invalid for testing.
+        let valid = app
+            .world_mut()
+            .spawn((
+                Foo,
+                Target(Some(unsafe { Instance::from_entity_unchecked(bar) })),
+            ))
+            .id();
+        let invalid = app
+            .world_mut()
+            .spawn((
+                Foo,
+                Target(Some(unsafe { Instance::from_entity_unchecked(not_bar) })),
+            ))
+            .id();
+        app.update();
+
+        assert!(app
+            .world()
+            .entity(valid)
+            .get::<Target>()
+            .unwrap()
+            .0
+            .is_some());
+        assert!(app
+            .world()
+            .entity(invalid)
+            .get::<Target>()
+            .unwrap()
+            .0
+            .is_none());
+
+        // SAFE: `not_bar` is intentionally invalid for testing.
+        let invalid = app
+            .world_mut()
+            .spawn((
+                Foo,
+                Target(Some(unsafe { Instance::from_entity_unchecked(not_bar) })),
+            ))
+            .id();
+        app.world_mut().apply_checks();
+
+        assert!(app
+            .world()
+            .entity(invalid)
+            .get::<Target>()
+            .unwrap()
+            .0
+            .is_none());
+    }
+
+    #[test]
+    fn test_sweep_stale_references() {
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct Target(Entity);
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .register_type::<Target>()
+            .sweep_stale_references(Duration::ZERO);
+
+        let target = app.world_mut().spawn_empty().id();
+        let entity = app.world_mut().spawn(Target(target)).id();
+        app.update();
+        assert!(!app.world().entity(entity).contains::<Invalid>());
+
+        app.world_mut().despawn(target);
+        app.update();
+        assert!(app.world().entity(entity).contains::<Invalid>());
+        let reasons = app.world().entity(entity).get::<InvalidReasons>().unwrap();
+        assert_eq!(reasons.iter().next().unwrap().check, "Stale reference");
+    }
+
+    #[test]
+    fn test_sweep_stale_references_placeholder() {
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct Target(Entity);
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .register_type::<Target>()
+            .sweep_stale_references(Duration::ZERO);
+
+        let entity = app.world_mut().spawn(Target(Entity::PLACEHOLDER)).id();
+        app.update();
+        assert!(!app.world().entity(entity).contains::<Invalid>());
+    }
+
+    #[test]
+    fn test_sweep_stale_references_dry_run() {
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct Target(Entity);
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .register_type::<Target>()
+            .insert_resource(DryRun)
+            .sweep_stale_references(Duration::ZERO);
+
+        let target = app.world_mut().spawn_empty().id();
+        let entity = app.world_mut().spawn(Target(target)).id();
+        app.world_mut().despawn(target);
+        app.update();
+        assert!(!app.world().entity(entity).contains::<Invalid>());
+    }
+
+    #[test]
+    fn test_check_required() {
+        #[derive(Component, Default)]
+        struct Fresh;
+
+        #[derive(Component)]
+        #[require(Fresh)]
+        struct Apple;
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check_required::<Apple>(repair_required::<Apple>());
+
+        let entity = app.world_mut().spawn(Apple).id();
+        // NOTE: Remove the required component to simulate an entity loaded from old save data.
+        app.world_mut().entity_mut(entity).remove::<Fresh>();
+        app.update();
+
+        assert!(app.world().entity(entity).contains::<Fresh>());
+    }
+
+    #[test]
+    fn test_inherit_checks() {
+        #[derive(Component)]
+        struct Baz;
+
+        kind!(Baz is Foo);
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(purge())
+            .inherit_checks::<Baz, Foo>();
+
+        let entity = app.world_mut().spawn(Baz).id();
+        app.update();
+
+        assert!(app.world().get_entity(entity).is_err());
+    }
+
+    #[test]
+    fn test_require() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins).require::<Foo, Bar>(purge());
+
+        let valid = app.world_mut().spawn((Foo, Bar)).id();
+        let purged = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        assert!(app.world().get_entity(valid).is_ok());
+        assert!(app.world().get_entity(purged).is_err());
+    }
+
+    #[test]
+    fn test_repair_insert_bundle() {
+        #[derive(Component, Clone, Default, PartialEq, Debug)]
+        struct Baz(u32);
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(repair_insert_bundle((Bar, Baz(1))));
+
+        let entity = app.world_mut().spawn((Foo, Baz(0))).id();
+        app.update();
+
+        assert!(app.world().entity(entity).contains::<Bar>());
+        assert_eq!(app.world().entity(entity).get::<Baz>(), Some(&Baz(1)));
+    }
+
+    #[test]
+    fn test_repair_modify() {
+        #[derive(Component, PartialEq, Debug)]
+        struct Health(u32);
 
-/// A fixer to be used with a [`Policy::Repair`] to try and fix an invalid instance.
-pub struct Fixer(Box<dyn Fix>);
+        #[derive(Resource, Default)]
+        struct Inserted(usize);
 
-impl Fixer {
-    pub fn new(f: impl Fix) -> Self {
-        Self(Box::new(f))
-    }
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<Inserted>()
+            .check::<Foo, Without<Bar>>(repair_modify(|health: &mut Health| health.0 = 100));
 
-    pub fn fix(&self, entity: EntityRef, commands: &mut Commands) {
-        self.0.fix(entity, commands)
-    }
-}
+        app.world_mut().add_observer(
+            |_: Trigger<OnInsert, Health>, mut inserted: ResMut<Inserted>| {
+                inserted.0 += 1;
+            },
+        );
 
-pub trait Fix: 'static + Send + Sync {
-    fn fix(&self, entity: EntityRef, commands: &mut Commands);
-}
+        let entity = app.world_mut().spawn((Foo, Health(0))).id();
+        app.update();
 
-impl<F: Fn(EntityRef, &mut Commands)> Fix for F
-where
-    F: 'static + Send + Sync,
-{
-    fn fix(&self, entity: EntityRef, commands: &mut Commands) {
-        self(entity, commands)
+        assert_eq!(
+            app.world().entity(entity).get::<Health>(),
+            Some(&Health(100))
+        );
+        assert_eq!(app.world().resource::<Inserted>().0, 1);
     }
-}
 
-/// Returns a [`Policy`] which despawns matching instances and all of their children.
-///
-/// # Usage
-///
-/// Use this policy with [`Valid`] to allow systems to safely ignore invalid entities.
-///
-/// # Example
-/// ```
-/// use bevy::prelude::*;
-/// use moonshine_check::prelude::*;
-///
-/// #[derive(Bundle, Default)]
-/// struct AB {
-///     a: A,
-///     b: B,
-/// }
-///
-/// #[derive(Component, Default)]
-/// struct A;
-///
-/// #[derive(Component, Default)]
-/// struct B;
-///
-/// let mut app = App::new();
-/// app.add_plugins(MinimalPlugins)
-///     .check::<A, Without<B>>(invalid())
-///     .add_systems(Update, update_valid);
-///
-/// app.world_mut().spawn(AB::default()); // OK!
-/// app.world_mut().spawn(A); // Bug! `B` is missing!
-/// app.update();
-///
-/// fn update_valid(items: Query<Entity, (With<A>, Valid)>, query: Query<&B>) {
-///     for entity in items.iter() {
-///         // Guaranteed:
-///         assert!(query.contains(entity));
-///     }
-/// }
-/// ```
-pub fn invalid() -> Policy {
-    Policy::Invalid
-}
+    #[test]
+    fn test_repair_default_fields() {
+        #[derive(Component, Reflect, PartialEq, Debug)]
+        struct Stats {
+            health: f32,
+            stamina: f32,
+        }
 
-/// Returns a [`Policy`] which despawns matching instances and all of their children.
-///
-/// # Usage
-///
-/// Use this policy to remove invalid entities from the world.
-///
-/// # Example
-/// ```
-/// use bevy::prelude::*;
-/// use moonshine_check::prelude::*;
-///
-/// #[derive(Bundle, Default)]
-/// struct AB {
-///     a: A,
-///     b: B,
-/// }
-///
-/// #[derive(Component, Default)]
-/// struct A;
-///
-/// #[derive(Component, Default)]
-/// struct B;
-///
-/// let mut app = App::new();
-/// app.add_plugins(MinimalPlugins)
-///     .check::<A, Without<B>>(purge())
-///     .add_systems(Update, update);
-///
-/// app.world_mut().spawn(AB::default()); // OK!
-/// app.world_mut().spawn(A); // Bug! `B` is missing!
-/// app.update();
-///
-/// fn update(items: Query<Entity, With<A>>, query: Query<&B>) {
-///     for entity in items.iter() {
-///         // Guaranteed:
-///         assert!(query.contains(entity));
-///     }
-/// }
-/// ```
-pub fn purge() -> Policy {
-    Policy::Purge
-}
+        impl Default for Stats {
+            fn default() -> Self {
+                Self {
+                    health: 100.0,
+                    stamina: 100.0,
+                }
+            }
+        }
 
-/// Returns a [`Policy`] which despawns matching instances and all of their children.
-///
-/// # Usage
-///
-/// Use this policy if you want to [`panic!`] on invalid entities.
-///
-/// In general, you should avoid using this policy as it can make your application unstable.
-/// It is recommended to use [`invalid`] or [`purge`] instead, especially in a production environment.
-///
-/// # Example
-/// ```should_panic
-/// use bevy::prelude::*;
-/// use moonshine_check::prelude::*;
-///
-/// #[derive(Bundle, Default)]
-/// struct AB {
-///     a: A,
-///     b: B,
-/// }
-///
-/// #[derive(Component, Default)]
-/// struct A;
-///
-/// #[derive(Component, Default)]
-/// struct B;
-///
-/// let mut app = App::new();
-/// app.add_plugins(MinimalPlugins)
-///     .check::<A, Without<B>>(panic())
-///     .add_systems(Update, update);
-///
-/// app.world_mut().spawn(AB::default()); // OK!
-/// app.world_mut().spawn(A); // Bug! `B` is missing!
-/// app.update();
-///
-/// fn update(items: Query<Entity, With<A>>, query: Query<&B>) {
-///     // Guaranteed:
-///     unreachable!();
-/// }
-/// ```
-pub fn panic() -> Policy {
-    Policy::Panic
-}
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(repair_default_fields::<Stats>(["health", "stamina"]));
 
-/// Returns a [`Policy`] which tries to repair matching instances.
-///
-/// # Usage
-///
-/// Use this policy if the matching instances can be repaired by inserting or removing components.
-/// This is especially useful to handle backwards compatibility when loading from saved data.
-///
-/// # Example
-/// ```
-/// use bevy::prelude::*;
-/// use moonshine_check::prelude::*;
-///
-/// #[derive(Bundle, Default)]
-/// struct AB {
-///    a: A,
-///    b: B,
-/// }
-///
-/// #[derive(Component, Default)]
-/// struct A;
-///
-/// #[derive(Component, Default)]
-/// struct B;
-///
-/// let mut app = App::new();
-/// app.add_plugins(MinimalPlugins)
-///     .check::<A, Without<B>>(repair(|entity: EntityRef, commands: &mut Commands| {
-///         commands.entity(entity.id()).insert(B);
-///     }));
-///
-/// app.world_mut().spawn(A); // Bug! `B` is missing!
-/// app.update();
-///
-/// fn update(items: Query<Entity, With<A>>, query: Query<&B>) {
-///     for entity in items.iter() {
-///         // Guaranteed:
-///         assert!(query.contains(entity));
-///     }
-/// }
-pub fn repair(f: impl Fix) -> Policy {
-    Policy::Repair(Fixer::new(f))
-}
+        let entity = app
+            .world_mut()
+            .spawn((
+                Foo,
+                Stats {
+                    health: 5.0,
+                    stamina: f32::NAN,
+                },
+            ))
+            .id();
+        app.update();
 
-pub fn repair_insert<T: Component + Clone>(component: T) -> Policy {
-    repair(move |entity: EntityRef, commands: &mut Commands| {
-        commands.entity(entity.id()).insert(component.clone());
-    })
-}
+        // NOTE: Valid fields are preserved, even if listed.
+        assert_eq!(
+            app.world().entity(entity).get::<Stats>(),
+            Some(&Stats {
+                health: 5.0,
+                stamina: 100.0
+            })
+        );
+    }
 
-pub fn repair_insert_default<T: Component + Default>() -> Policy {
-    repair(move |entity: EntityRef, commands: &mut Commands| {
-        commands.entity(entity.id()).insert(T::default());
-    })
-}
+    #[test]
+    fn test_repair_default_fields_where() {
+        #[derive(Component, Reflect, PartialEq, Debug)]
+        struct Stats {
+            health: u32,
+            stamina: u32,
+        }
 
-pub fn repair_replace<T: Component, U: Component + Clone>(component: U) -> Policy {
-    repair(move |entity: EntityRef, commands: &mut Commands| {
-        commands
-            .entity(entity.id())
-            .remove::<T>()
-            .insert(component.clone());
-    })
-}
+        impl Default for Stats {
+            fn default() -> Self {
+                Self {
+                    health: 100,
+                    stamina: 100,
+                }
+            }
+        }
 
-pub fn repair_replace_default<T: Component, U: Component + Default>() -> Policy {
-    repair(move |entity: EntityRef, commands: &mut Commands| {
-        commands
-            .entity(entity.id())
-            .remove::<T>()
-            .insert(U::default());
-    })
-}
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(repair_default_fields_where::<Stats>(
+                ["health", "stamina"],
+                |_, value| value.try_downcast_ref::<u32>() == Some(&0),
+            ));
 
-pub fn repair_replace_with<T: Component, U: Component, F>(f: F) -> Policy
-where
-    F: 'static + Fn(&T) -> U + Send + Sync,
-{
-    repair(move |entity: EntityRef, commands: &mut Commands| {
-        let component = entity.get::<T>().unwrap();
-        commands
-            .entity(entity.id())
-            .remove::<T>()
-            .insert(f(component));
-    })
-}
+        let entity = app
+            .world_mut()
+            .spawn((
+                Foo,
+                Stats {
+                    health: 5,
+                    stamina: 0,
+                },
+            ))
+            .id();
+        app.update();
 
-pub fn repair_remove<T: Component>() -> Policy {
-    repair(move |entity: EntityRef, commands: &mut Commands| {
-        commands.entity(entity.id()).remove::<T>();
-    })
-}
+        assert_eq!(
+            app.world().entity(entity).get::<Stats>(),
+            Some(&Stats {
+                health: 5,
+                stamina: 100
+            })
+        );
+    }
 
-/// A [`QueryFilter`] which indicates that an [`Entity`] has been checked and is valid.
-///
-/// See [`invalid`] for a usage example.
-#[derive(QueryFilter)]
-pub struct Valid(With<Checked>, Without<Invalid>);
+    #[test]
+    fn test_repair_clone_from() {
+        #[derive(Component, Reflect, PartialEq, Debug)]
+        #[reflect(Component)]
+        struct Health(u32);
 
-/// An extension trait used to force an [`Entity`] to be checked again.
-pub trait CheckAgain {
-    fn check_again(self) -> Self;
-}
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .register_type::<Health>()
+            .check::<Foo, Without<Health>>(repair_clone_from::<Foo>());
 
-impl CheckAgain for &mut EntityCommands<'_> {
-    fn check_again(self) -> Self {
-        self.remove::<Checked>().remove::<Invalid>()
-    }
-}
+        app.world_mut()
+            .spawn((Template::<Foo>::default(), Health(50), Bar));
+        let entity = app.world_mut().spawn(Foo).id();
+        app.update();
 
-impl CheckAgain for &mut EntityWorldMut<'_> {
-    fn check_again(self) -> Self {
-        self.remove::<Checked>().remove::<Invalid>()
+        assert_eq!(
+            app.world().entity(entity).get::<Health>(),
+            Some(&Health(50))
+        );
+        assert!(!app.world().entity(entity).contains::<Bar>());
+        assert!(!app.world().entity(entity).contains::<Template<Foo>>());
     }
-}
 
-type Unchecked = Without<Checked>;
-
-#[derive(Component)]
-struct Checked;
-
-#[derive(Component)]
-struct Invalid;
+    #[test]
+    fn test_repair_insert_with() {
+        #[derive(Component, PartialEq, Debug)]
+        struct Id(u64);
 
-#[cfg(test)]
-mod tests {
-    use bevy::prelude::*;
+        #[derive(Component, PartialEq, Debug)]
+        struct Owner(Entity);
 
-    use super::*;
+        let next = Arc::new(AtomicU64::new(0));
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Id>>(repair_insert_with(move || {
+                Id(next.fetch_add(1, Ordering::Relaxed))
+            }))
+            .check::<Foo, Without<Owner>>(repair_insert_from(|entity: EntityRef| {
+                Owner(entity.id())
+            }));
 
-    #[derive(Component)]
-    struct Foo;
+        let a = app.world_mut().spawn(Foo).id();
+        let b = app.world_mut().spawn(Foo).id();
+        app.update();
 
-    #[derive(Component)]
-    struct Bar;
+        let a_id = app.world().entity(a).get::<Id>().unwrap().0;
+        let b_id = app.world().entity(b).get::<Id>().unwrap().0;
+        assert_ne!(a_id, b_id);
+        assert_eq!(app.world().entity(a).get::<Owner>(), Some(&Owner(a)));
+        assert_eq!(app.world().entity(b).get::<Owner>(), Some(&Owner(b)));
+    }
 
     #[test]
-    fn test_valid() {
+    fn test_try_repair() {
         let mut app = App::new();
         app.add_plugins(MinimalPlugins)
-            .check::<Foo, Without<Bar>>(panic());
+            .check::<Foo, Without<Bar>>(try_repair(
+                |_: EntityRef, _: &mut Commands| Err("Bar is unavailable".into()),
+                purge(),
+            ));
 
-        let entity = app.world_mut().spawn((Foo, Bar)).id();
+        let entity = app.world_mut().spawn(Foo).id();
         app.update();
 
-        assert!(app.world().entity(entity).contains::<Checked>());
-        assert!(!app.world().entity(entity).contains::<Invalid>());
+        assert!(app.world().get_entity(entity).is_err());
+
+        let report = app.world().resource::<CheckReport>();
+        assert_eq!(report.total().repair_failed, 1);
+        assert_eq!(report.total().purged, 1);
+
+        let events = app.world().resource::<Events<RepairFailed>>();
+        let event = events.iter_current_update_events().next().unwrap();
+        assert_eq!(event.entity, entity);
+        assert_eq!(event.error.reason(), "Bar is unavailable");
     }
 
     #[test]
-    fn test_invalid() {
+    fn test_repair_world() {
+        #[derive(Resource, Default)]
+        struct Repairs(usize);
+
         let mut app = App::new();
         app.add_plugins(MinimalPlugins)
-            .check::<Foo, Without<Bar>>(invalid());
+            .init_resource::<Repairs>()
+            .check::<Foo, Without<Children>>(repair_world(|entity: Entity, world: &mut World| {
+                world.resource_mut::<Repairs>().0 += 1;
+                let child = world.spawn(Bar).id();
+                world.entity_mut(entity).add_child(child);
+            }));
 
         let entity = app.world_mut().spawn(Foo).id();
         app.update();
 
+        assert_eq!(app.world().resource::<Repairs>().0, 1);
+        assert_eq!(
+            app.world().entity(entity).get::<Children>().unwrap().len(),
+            1
+        );
         assert!(app.world().entity(entity).contains::<Checked>());
-        assert!(app.world().entity(entity).contains::<Invalid>());
     }
 
     #[test]
-    fn test_purge() {
+    fn test_repair_with_param() {
+        #[derive(Component, PartialEq, Debug)]
+        struct Health(u32);
+
+        #[derive(Resource)]
+        struct MaxHealth(u32);
+
+        fn insert_health(entity: Entity, (max, mut commands): (Res<MaxHealth>, Commands)) {
+            commands.entity(entity).insert(Health(max.0));
+        }
+
         let mut app = App::new();
         app.add_plugins(MinimalPlugins)
-            .check::<Foo, Without<Bar>>(purge());
+            .insert_resource(MaxHealth(100))
+            .check::<Foo, Without<Health>>(repair_with_param(insert_health));
 
         let entity = app.world_mut().spawn(Foo).id();
         app.update();
 
-        assert!(app.world().get_entity(entity).is_err());
+        assert_eq!(
+            app.world().entity(entity).get::<Health>(),
+            Some(&Health(100))
+        );
     }
 
     #[test]
-    #[should_panic]
-    fn test_panic() {
+    fn test_repair_with_param_closure() {
+        #[derive(Component, PartialEq, Debug)]
+        struct Health(u32);
+
+        #[derive(Resource)]
+        struct MaxHealth(u32);
+
         let mut app = App::new();
         app.add_plugins(MinimalPlugins)
-            .check::<Foo, Without<Bar>>(panic());
+            .insert_resource(MaxHealth(100))
+            .check::<Foo, Without<Health>>(repair_with_param(
+                |entity: Entity, (max, mut commands): (Res<MaxHealth>, Commands)| {
+                    commands.entity(entity).insert(Health(max.0));
+                },
+            ));
 
-        app.world_mut().spawn(Foo);
+        let entity = app.world_mut().spawn(Foo).id();
         app.update();
+
+        assert_eq!(
+            app.world().entity(entity).get::<Health>(),
+            Some(&Health(100))
+        );
     }
 
     #[test]
-    fn test_repair() {
+    fn test_repair_pipeline() {
+        #[derive(Component)]
+        struct Baz;
+
         let mut app = App::new();
         app.add_plugins(MinimalPlugins)
-            .check::<Foo, Without<Bar>>(repair(|entity: EntityRef, commands: &mut Commands| {
-                commands.entity(entity.id()).insert(Bar);
-            }));
+            .check::<Foo, Without<Bar>>(repair_pipeline([
+                Fixer::new(|entity: EntityRef, commands: &mut Commands| {
+                    commands.entity(entity.id()).insert(Baz);
+                }),
+                Fixer::new(|entity: EntityRef, commands: &mut Commands| {
+                    commands.entity(entity.id()).insert(Bar);
+                }),
+            ]));
 
         let entity = app.world_mut().spawn(Foo).id();
         app.update();
 
+        assert!(app.world().entity(entity).contains::<Baz>());
+        assert!(!app.world().entity(entity).contains::<Bar>());
+        assert!(!app.world().entity(entity).contains::<Checked>());
+
+        app.update();
+
+        // NOTE: The instance is evaluated again after the final step.
         assert!(app.world().entity(entity).contains::<Bar>());
+        assert!(!app.world().entity(entity).contains::<Checked>());
+
+        app.update();
+
         assert!(app.world().entity(entity).contains::<Checked>());
+        assert!(!app.world().entity(entity).contains::<Invalid>());
+        assert!(!app.world().entity(entity).contains::<RepairProgress>());
     }
 
     #[test]
-    #[should_panic]
-    fn test_check_again() {
+    fn test_repair_pipeline_exhausted() {
         #[derive(Component)]
-        struct Repaired;
+        struct Baz;
 
         let mut app = App::new();
         app.add_plugins(MinimalPlugins)
-            .check::<Foo, Without<Bar>>(repair(|entity: EntityRef, commands: &mut Commands| {
-                // Avoid infinite repair loop
-                if entity.contains::<Repaired>() {
-                    panic!("Bar is still missing!");
-                }
-
-                // Oops! Maybe we forget to insert Bar ...
-                // Check again to be sure:
-                commands.entity(entity.id()).insert(Repaired).check_again();
-            }));
+            .check::<Foo, Without<Bar>>(repair_pipeline([Fixer::new(
+                |entity: EntityRef, commands: &mut Commands| {
+                    commands.entity(entity.id()).insert(Baz);
+                },
+            )]));
 
         let entity = app.world_mut().spawn(Foo).id();
         app.update();
-
-        assert!(!app.world().entity(entity).contains::<Bar>());
         assert!(!app.world().entity(entity).contains::<Checked>());
 
-        app.update(); // Should panic!
+        app.update();
+        assert!(app.world().entity(entity).contains::<Invalid>());
+        assert!(!app.world().entity(entity).contains::<RepairProgress>());
+
+        // NOTE: The pipeline starts over if the instance fails again.
+        app.world_mut()
+            .entity_mut(entity)
+            .check_again()
+            .remove::<Baz>();
+        app.update();
+        assert!(app.world().entity(entity).contains::<Baz>());
+        assert!(!app.world().entity(entity).contains::<Invalid>());
+    }
+
+    #[test]
+    fn test_check_bundle() {
+        #[derive(Component, Default, PartialEq, Debug)]
+        struct Health(u32);
+
+        #[derive(Component, Default)]
+        struct Inventory;
+
+        #[derive(Bundle, Default)]
+        struct FooBundle {
+            health: Health,
+            inventory: Inventory,
+        }
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check_bundle::<Foo, FooBundle>(repair_complete_bundle::<FooBundle>());
+
+        let entity = app.world_mut().spawn((Foo, Health(10))).id();
+        app.update();
+
+        assert_eq!(
+            app.world().entity(entity).get::<Health>(),
+            Some(&Health(10))
+        );
+        assert!(app.world().entity(entity).contains::<Inventory>());
     }
 
     #[test]
@@ -531,3 +7293,54 @@ mod tests {
         app.update();
     }
 }
+
+#[cfg(all(test, feature = "disable"))]
+mod disable_tests {
+    use bevy::prelude::*;
+
+    use super::*;
+
+    #[derive(Component)]
+    struct Foo;
+
+    #[derive(Component)]
+    struct Bar;
+
+    #[test]
+    fn test_disable() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, CheckPlugin::default().audit_on_startup()))
+            .check::<Foo, Without<Bar>>(panic())
+            .check_unique::<Foo>(panic())
+            .check_count::<Foo>(..1, CountPolicy::Panic);
+
+        let a = app.world_mut().spawn(Foo).id();
+        let b = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        let world = app.world_mut();
+        assert!(!world.entity(a).contains::<Checked>());
+        assert!(!world.entity(b).contains::<Checked>());
+
+        let valid: Vec<Entity> = world
+            .query_filtered::<Entity, (With<Foo>, Valid)>()
+            .iter(world)
+            .collect();
+        assert_eq!(valid.len(), 2);
+        assert!(valid.contains(&a) && valid.contains(&b));
+    }
+
+    #[test]
+    fn test_disable_valid_data() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .check::<Foo, Without<Bar>>(invalid());
+
+        let entity = app.world_mut().spawn(Foo).id();
+        app.update();
+
+        let world = app.world_mut();
+        let valid = world.query::<Valid<Instance<Foo>>>().get(world, entity);
+        assert!(valid.is_ok());
+    }
+}
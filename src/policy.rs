@@ -0,0 +1,496 @@
+//! Policies applied to instances which fail a check.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use bevy_ecs::{prelude::*, reflect::ReflectComponent, system::SystemChangeTick};
+use bevy_hierarchy::DespawnRecursiveExt;
+use bevy_reflect::Reflect;
+use bevy_time::Time;
+use bevy_utils::tracing::{debug, error, warn};
+
+#[cfg(feature = "graveyard")]
+use crate::{bury, Graveyard};
+use crate::{
+    panic_invalid, Checked, Fix, Fixer, Invalid, JournalEntry, Outcome, ValidationJournal,
+    ValidationLog, ValidationLogEntry,
+};
+
+/// Returns the [`Outcome`] of the given [`Policy`] if it were applied, or `None` if it would panic.
+pub(crate) fn planned_outcome(policy: &Policy) -> Option<Outcome> {
+    match policy {
+        Policy::Invalid => Some(Outcome::Invalid),
+        Policy::Purge | Policy::PurgeAfter(_) => Some(Outcome::Purged),
+        Policy::Panic => None,
+        Policy::Repair(_) => Some(Outcome::Repaired),
+        Policy::Dump { then, .. } => planned_outcome(then),
+    }
+}
+
+/// An action to be invoked if a [`Check`](crate::Check) *passes*.
+///
+/// See [`invalid`], [`purge`], [`panic`], and [`repair`](crate::repair) for details.
+///
+/// Policies are cheap to clone, so a single policy may be shared across multiple checks.
+#[derive(Clone)]
+pub enum Policy {
+    /// Mark the instance as invalid.
+    Invalid,
+    /// Despawn the instance and all of its children.
+    Purge,
+    /// Mark the instance as invalid, and despawn it and all of its children after a delay.
+    ///
+    /// See [`purge_after`] and [`purge_after_frames`] for details.
+    PurgeAfter(PurgeDelay),
+    /// Panic!
+    Panic,
+    /// Try to repair the instance with a given [`Fixer`].
+    Repair(Fixer),
+    /// Save the instance into a scene file, and then apply another policy.
+    ///
+    /// Scene files are only written with the `dump` feature on native targets. Otherwise, only the
+    /// other policy is applied. See [`dump`] and [`dump_and_purge`] for details.
+    Dump {
+        /// Path to the directory where scene files are saved.
+        path: PathBuf,
+        /// If true, all descendants of the instance are also saved.
+        recursive: bool,
+        /// The policy to apply after saving the instance.
+        then: Box<Policy>,
+    },
+}
+
+/// An action to be invoked if a [`check_count`](crate::Check::check_count) requirement is violated.
+pub enum CountPolicy {
+    /// Only log an error and send a [`CountViolation`] event.
+    Report,
+    /// Panic!
+    Panic,
+    /// Try to repair the world with a given function, which receives the current instance count.
+    Repair(Box<dyn Fn(usize, &mut Commands) + Send + Sync>),
+}
+
+impl CountPolicy {
+    /// Returns a [`CountPolicy`] which repairs the world with the given function.
+    pub fn repair(f: impl Fn(usize, &mut Commands) + Send + Sync + 'static) -> Self {
+        Self::Repair(Box::new(f))
+    }
+
+    /// Returns a [`CountPolicy`] which spawns new instances with the given bundle until there are at least `min_count`.
+    pub fn spawn_missing(min_count: usize, bundle: impl Bundle + Clone) -> Self {
+        Self::repair(move |count, commands| {
+            for _ in count..min_count {
+                commands.spawn(bundle.clone());
+            }
+        })
+    }
+}
+
+/// An action to be invoked when an entity has been invalid for too long.
+///
+/// See [`escalate_invalid`](crate::Check::escalate_invalid) for details.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WatchdogPolicy {
+    /// Only log a warning and send an [`InvalidTimeout`] event.
+    Report,
+    /// Despawn the entity and all of its children.
+    Purge,
+    /// Panic!
+    Panic,
+}
+
+/// An [`Event`] sent when an entity has been invalid for too long.
+///
+/// See [`escalate_invalid`](crate::Check::escalate_invalid) for details.
+#[derive(Event, Clone, Debug)]
+pub struct InvalidTimeout {
+    /// The invalid entity.
+    pub entity: Entity,
+    /// The reason why the entity is invalid.
+    pub reason: String,
+    /// How long the entity has been invalid.
+    pub age: Duration,
+}
+
+/// An [`Event`] sent when a [`check_count`](crate::Check::check_count) requirement is violated.
+#[derive(Event, Clone, Debug)]
+pub struct CountViolation {
+    /// Name of the violated check.
+    pub check: String,
+    /// Number of instances when the check was violated.
+    pub count: usize,
+}
+
+/// A statically dispatched policy. See [`Check::check_static`](crate::Check::check_static).
+pub trait PolicyImpl: 'static + Send + Sync {
+    /// Applies this policy to an instance which failed the given check, and returns its [`Outcome`].
+    fn apply(&self, entity: EntityRef, check: &str, commands: &mut Commands) -> Outcome;
+}
+
+/// A statically dispatched equivalent of [`invalid`].
+pub struct InvalidPolicy;
+
+impl PolicyImpl for InvalidPolicy {
+    fn apply(&self, entity: EntityRef, check: &str, commands: &mut Commands) -> Outcome {
+        commands.entity(entity.id()).mark_invalid(check);
+        Outcome::Invalid
+    }
+}
+
+/// A statically dispatched equivalent of [`purge`].
+pub struct PurgePolicy;
+
+impl PolicyImpl for PurgePolicy {
+    fn apply(&self, entity: EntityRef, check: &str, commands: &mut Commands) -> Outcome {
+        commands.entity(entity.id()).despawn_recursive();
+        error!("{} is purged: {check}", entity.id());
+        Outcome::Purged
+    }
+}
+
+/// A statically dispatched equivalent of [`panic`].
+///
+/// Since the components of the instance are described from the world, the panic is deferred until the
+/// commands of the check system are applied.
+pub struct PanicPolicy;
+
+impl PolicyImpl for PanicPolicy {
+    fn apply(&self, entity: EntityRef, check: &str, commands: &mut Commands) -> Outcome {
+        let entity = entity.id();
+        let check = check.to_owned();
+        commands.queue(move |world: &mut World| {
+            if let Ok(entity) = world.get_entity(entity) {
+                panic_invalid(entity, world, &check);
+            }
+        });
+        Outcome::Invalid
+    }
+}
+
+/// A statically dispatched equivalent of [`repair`](crate::repair).
+pub struct RepairPolicy<X: Fix>(pub X);
+
+impl<X: Fix> PolicyImpl for RepairPolicy<X> {
+    fn apply(&self, entity: EntityRef, check: &str, commands: &mut Commands) -> Outcome {
+        // Inset `Checked` before fixing to let the fixer remove it if needed
+        commands.entity(entity.id()).try_insert(Checked);
+        error!("{} is invalid: {check}", entity.id());
+        if let Err(error) = self.0.try_fix(entity, commands) {
+            error!("{} could not be repaired: {error}", entity.id());
+            commands.entity(entity.id()).mark_invalid(check);
+            return Outcome::Invalid;
+        }
+        warn!("{} was repaired.", entity.id());
+        Outcome::Repaired
+    }
+}
+
+/// Returns a [`Policy`] which despawns matching instances and all of their children.
+///
+/// # Usage
+///
+/// Use this policy with [`Valid`](crate::Valid) to allow systems to safely ignore invalid entities.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// #[derive(Bundle, Default)]
+/// struct AB {
+///     a: A,
+///     b: B,
+/// }
+///
+/// #[derive(Component, Default)]
+/// struct A;
+///
+/// #[derive(Component, Default)]
+/// struct B;
+///
+/// let mut app = App::new();
+/// app.add_plugins(MinimalPlugins)
+///     .check::<A, Without<B>>(invalid())
+///     .add_systems(Update, update_valid);
+///
+/// app.world_mut().spawn(AB::default()); // OK!
+/// app.world_mut().spawn(A); // Bug! `B` is missing!
+/// app.update();
+///
+/// fn update_valid(items: Query<Entity, (With<A>, Valid)>, query: Query<&B>) {
+///     for entity in items.iter() {
+///         // Guaranteed:
+///         assert!(query.contains(entity));
+///     }
+/// }
+/// ```
+pub fn invalid() -> Policy {
+    Policy::Invalid
+}
+
+/// Returns a [`Policy`] which despawns matching instances and all of their children.
+///
+/// # Usage
+///
+/// Use this policy to remove invalid entities from the world.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// #[derive(Bundle, Default)]
+/// struct AB {
+///     a: A,
+///     b: B,
+/// }
+///
+/// #[derive(Component, Default)]
+/// struct A;
+///
+/// #[derive(Component, Default)]
+/// struct B;
+///
+/// let mut app = App::new();
+/// app.add_plugins(MinimalPlugins)
+///     .check::<A, Without<B>>(purge())
+///     .add_systems(Update, update);
+///
+/// app.world_mut().spawn(AB::default()); // OK!
+/// app.world_mut().spawn(A); // Bug! `B` is missing!
+/// app.update();
+///
+/// fn update(items: Query<Entity, With<A>>, query: Query<&B>) {
+///     for entity in items.iter() {
+///         // Guaranteed:
+///         assert!(query.contains(entity));
+///     }
+/// }
+/// ```
+pub fn purge() -> Policy {
+    Policy::Purge
+}
+
+/// Returns a [`Policy`] which despawns matching instances and all of their children.
+///
+/// # Usage
+///
+/// Use this policy if you want to [`panic!`] on invalid entities.
+///
+/// In general, you should avoid using this policy as it can make your application unstable.
+/// It is recommended to use [`invalid`] or [`purge`] instead, especially in a production environment.
+///
+/// # Example
+/// ```should_panic
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// #[derive(Bundle, Default)]
+/// struct AB {
+///     a: A,
+///     b: B,
+/// }
+///
+/// #[derive(Component, Default)]
+/// struct A;
+///
+/// #[derive(Component, Default)]
+/// struct B;
+///
+/// let mut app = App::new();
+/// app.add_plugins(MinimalPlugins)
+///     .check::<A, Without<B>>(panic())
+///     .add_systems(Update, update);
+///
+/// app.world_mut().spawn(AB::default()); // OK!
+/// app.world_mut().spawn(A); // Bug! `B` is missing!
+/// app.update();
+///
+/// fn update(items: Query<Entity, With<A>>, query: Query<&B>) {
+///     // Guaranteed:
+///     unreachable!();
+/// }
+/// ```
+pub fn panic() -> Policy {
+    Policy::Panic
+}
+
+/// Returns a [`Policy`] which saves matching instances into a scene file, and then marks them as invalid.
+///
+/// # Usage
+///
+/// Each instance is saved into a separate file named after the entity within the directory at the given `path`.
+/// Components are serialized using the type registry, so only reflected components are saved.
+/// If `recursive` is true, all descendants of the instance are saved into the same file.
+///
+/// This is useful to attach the state of invalid entities to bug reports.
+///
+/// See [`invalid`] for more details.
+#[cfg(all(feature = "dump", not(target_arch = "wasm32")))]
+pub fn dump(path: impl Into<PathBuf>, recursive: bool) -> Policy {
+    Policy::Dump {
+        path: path.into(),
+        recursive,
+        then: Box::new(Policy::Invalid),
+    }
+}
+
+/// Returns a [`Policy`] which saves matching instances into a scene file, and then despawns them.
+///
+/// See [`dump`] and [`purge`] for more details.
+#[cfg(all(feature = "dump", not(target_arch = "wasm32")))]
+pub fn dump_and_purge(path: impl Into<PathBuf>, recursive: bool) -> Policy {
+    Policy::Dump {
+        path: path.into(),
+        recursive,
+        then: Box::new(Policy::Purge),
+    }
+}
+
+/// Returns a [`Policy`] which marks matching instances as invalid, and then despawns them and all of their
+/// children once the given `duration` has elapsed.
+///
+/// # Usage
+///
+/// Use this instead of [`purge`] to give other systems (such as UI, networking, or inventories which
+/// reference the instance) a chance to react before the instance is despawned.
+///
+/// Until it is despawned, the instance has a [`PendingPurge`] component.
+/// If the instance is checked again before then (see [`CheckAgain`](crate::CheckAgain)), the purge is canceled.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// #[derive(Component)]
+/// struct A;
+///
+/// #[derive(Component)]
+/// struct B;
+///
+/// let mut app = App::new();
+/// app.check::<A, Without<B>>(purge_after(Duration::from_secs(1)));
+/// ```
+pub fn purge_after(duration: Duration) -> Policy {
+    Policy::PurgeAfter(PurgeDelay::Time(duration))
+}
+
+/// Returns a [`Policy`] which marks matching instances as invalid, and then despawns them and all of their
+/// children after the given number of frames.
+///
+/// See [`purge_after`] for details.
+pub fn purge_after_frames(frames: u32) -> Policy {
+    Policy::PurgeAfter(PurgeDelay::Frames(frames))
+}
+
+/// The delay before an invalid instance is purged. See [`purge_after`] and [`purge_after_frames`].
+#[derive(Reflect, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PurgeDelay {
+    /// Purge after the given [`Time`] has elapsed.
+    Time(Duration),
+    /// Purge after the given number of frames.
+    Frames(u32),
+}
+
+/// A [`Component`] which indicates that an invalid [`Entity`] will be purged soon.
+///
+/// See [`purge_after`] for details.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component)]
+pub struct PendingPurge {
+    /// Name of the kind which failed the check.
+    pub(crate) kind: String,
+    /// Name of the check which scheduled the purge.
+    pub(crate) check: String,
+    /// Elapsed [`Time`] after which the entity is purged, or the number of remaining frames.
+    deadline: PurgeDelay,
+}
+
+impl PendingPurge {
+    pub(crate) fn new(kind: &str, check: &str, delay: PurgeDelay, world: &World) -> Self {
+        let deadline = match delay {
+            PurgeDelay::Time(duration) => PurgeDelay::Time(
+                world
+                    .get_resource::<Time>()
+                    .map(|time| time.elapsed())
+                    .unwrap_or_default()
+                    + duration,
+            ),
+            PurgeDelay::Frames(frames) => PurgeDelay::Frames(frames),
+        };
+        Self {
+            kind: kind.to_owned(),
+            check: check.to_owned(),
+            deadline,
+        }
+    }
+
+    /// Returns the name of the check which scheduled the purge.
+    pub fn check(&self) -> &str {
+        &self.check
+    }
+}
+
+/// Despawns all invalid instances which are due to be purged.
+pub(crate) fn purge_pending(
+    mut query: Query<(Entity, &mut PendingPurge, Has<Invalid>)>,
+    time: Option<Res<Time>>,
+    mut journal: Option<ResMut<ValidationJournal>>,
+    mut log: Option<ResMut<ValidationLog>>,
+    ticks: SystemChangeTick,
+    mut commands: Commands,
+) {
+    let elapsed = time.map(|time| time.elapsed()).unwrap_or_default();
+    for (entity, mut pending, invalid) in query.iter_mut() {
+        if !invalid {
+            debug!("{entity} was checked again; purge is canceled.");
+            commands.entity(entity).remove::<PendingPurge>();
+            continue;
+        }
+
+        let due = match &mut pending.deadline {
+            PurgeDelay::Time(deadline) => elapsed >= *deadline,
+            PurgeDelay::Frames(0) => true,
+            PurgeDelay::Frames(frames) => {
+                *frames -= 1;
+                false
+            }
+        };
+        if !due {
+            continue;
+        }
+
+        #[cfg(feature = "graveyard")]
+        {
+            let check = pending.check.clone();
+            commands.queue(move |world: &mut World| {
+                if world.contains_resource::<Graveyard>() {
+                    bury(world, entity, check);
+                }
+            });
+        }
+        error!("{entity} is purged: {}", pending.check);
+        if let Some(log) = log.as_mut() {
+            log.push(ValidationLogEntry {
+                time: elapsed,
+                entity,
+                check: pending.check.clone(),
+                outcome: Outcome::Purged,
+                message: format!("{entity} is purged: {}", pending.check),
+            });
+        }
+        if let Some(journal) = journal.as_mut() {
+            journal.push(JournalEntry {
+                entity,
+                kind: pending.kind.clone(),
+                check: pending.check.clone(),
+                outcome: Outcome::Purged,
+                tick: ticks.this_run(),
+                snapshot: None,
+            });
+        }
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,54 @@
+//! Checks for [Avian](https://github.com/Jondolf/avian) physics invariants.
+
+use avian3d::prelude::*;
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_transform::prelude::*;
+
+use crate::{add_check, condition, invalid, repair, repair_insert_default, Check, CheckSpec};
+
+/// A [`Plugin`] which adds checks for Avian physics invariants.
+///
+/// # Checks
+///
+/// - [`RigidBody`] requires [`Collider`], or the instance is marked as invalid.
+/// - [`Collider`] requires [`Transform`], repaired with its default value.
+/// - [`LinearVelocity`] and [`AngularVelocity`] with any non-finite values are repaired to zero.
+pub struct AvianChecksPlugin;
+
+impl Plugin for AvianChecksPlugin {
+    fn build(&self, app: &mut App) {
+        app.check::<RigidBody, Without<Collider>>(invalid())
+            .check::<Collider, Without<Transform>>(repair_insert_default::<Transform>());
+        add_check::<LinearVelocity, ()>(
+            app.main_mut(),
+            repair(|entity: EntityRef, commands: &mut Commands| {
+                commands.entity(entity.id()).insert(LinearVelocity::ZERO);
+            })
+            .into(),
+            CheckSpec {
+                description: "LinearVelocity is not finite".to_owned(),
+                condition: Some(condition(|entity: EntityRef, _: &World| {
+                    !entity
+                        .get::<LinearVelocity>()
+                        .is_some_and(|velocity| velocity.is_finite())
+                })),
+            },
+        );
+        add_check::<AngularVelocity, ()>(
+            app.main_mut(),
+            repair(|entity: EntityRef, commands: &mut Commands| {
+                commands.entity(entity.id()).insert(AngularVelocity::ZERO);
+            })
+            .into(),
+            CheckSpec {
+                description: "AngularVelocity is not finite".to_owned(),
+                condition: Some(condition(|entity: EntityRef, _: &World| {
+                    !entity
+                        .get::<AngularVelocity>()
+                        .is_some_and(|velocity| velocity.is_finite())
+                })),
+            },
+        );
+    }
+}
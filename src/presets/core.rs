@@ -0,0 +1,55 @@
+//! Checks for core Bevy invariants.
+
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_render::view::{InheritedVisibility, ViewVisibility, Visibility};
+use bevy_transform::prelude::*;
+
+use crate::{add_check, condition, repair, repair_insert_default, Check, CheckSpec};
+
+/// A [`Plugin`] which adds checks for core Bevy invariants.
+///
+/// # Checks
+///
+/// - [`Transform`] with any non-finite values is repaired to [`Transform::IDENTITY`].
+/// - [`Transform`] requires [`GlobalTransform`], repaired with its default value.
+/// - [`Visibility`] requires [`InheritedVisibility`] and [`ViewVisibility`], repaired with their default values.
+///
+/// These are most useful for validating entities loaded from old save data.
+pub struct CoreChecksPlugin;
+
+impl Plugin for CoreChecksPlugin {
+    fn build(&self, app: &mut App) {
+        app.check::<Transform, Without<GlobalTransform>>(repair_insert_default::<GlobalTransform>())
+            .check::<Visibility, Or<(Without<InheritedVisibility>, Without<ViewVisibility>)>>(
+                repair(|entity: EntityRef, commands: &mut Commands| {
+                    let mut entity_commands = commands.entity(entity.id());
+                    if !entity.contains::<InheritedVisibility>() {
+                        entity_commands.insert(InheritedVisibility::default());
+                    }
+                    if !entity.contains::<ViewVisibility>() {
+                        entity_commands.insert(ViewVisibility::default());
+                    }
+                }),
+            );
+        add_check::<Transform, ()>(
+            app.main_mut(),
+            repair(|entity: EntityRef, commands: &mut Commands| {
+                commands.entity(entity.id()).insert(Transform::IDENTITY);
+            })
+            .into(),
+            CheckSpec {
+                description: "Transform is not finite".to_owned(),
+                condition: Some(condition(|entity: EntityRef, _: &World| {
+                    !entity.get::<Transform>().is_some_and(is_finite)
+                })),
+            },
+        );
+    }
+}
+
+fn is_finite(transform: &Transform) -> bool {
+    transform.translation.is_finite()
+        && transform.rotation.is_finite()
+        && transform.scale.is_finite()
+}
@@ -0,0 +1,105 @@
+//! Checks for [`Parent`] and [`Children`] consistency.
+
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::prelude::*;
+
+use crate::{add_check, condition, repair, CheckSpec, Policy};
+
+/// A [`Plugin`] which adds checks to ensure [`Parent`] and [`Children`] agree with each other.
+///
+/// # Checks
+///
+/// - Every [`Parent`] must exist and list the entity in its [`Children`].
+///   This is repaired by adding the entity to its parent's children, or removing the [`Parent`] if it doesn't exist.
+/// - Every entity in [`Children`] must exist and point back to the entity with its [`Parent`].
+///   This is repaired by rebuilding [`Children`] from all children which point back.
+///
+/// These are most useful for validating deserialized hierarchies.
+pub struct HierarchyChecksPlugin;
+
+impl Plugin for HierarchyChecksPlugin {
+    fn build(&self, app: &mut App) {
+        add_check::<Parent, ()>(
+            app.main_mut(),
+            repair_parent_link().into(),
+            CheckSpec {
+                description: "Parent does not list entity as a child".to_owned(),
+                condition: Some(condition(|entity: EntityRef, world: &World| {
+                    !is_listed_by_parent(entity, world)
+                })),
+            },
+        );
+        add_check::<Children, ()>(
+            app.main_mut(),
+            repair_children_links().into(),
+            CheckSpec {
+                description: "Children do not point back to entity".to_owned(),
+                condition: Some(condition(|entity: EntityRef, world: &World| {
+                    !all_children_point_back(entity, world)
+                })),
+            },
+        );
+    }
+}
+
+fn is_listed_by_parent(entity: EntityRef, world: &World) -> bool {
+    entity.get::<Parent>().is_some_and(|parent| {
+        world
+            .get::<Children>(parent.get())
+            .is_some_and(|children| children.contains(&entity.id()))
+    })
+}
+
+fn all_children_point_back(entity: EntityRef, world: &World) -> bool {
+    entity.get::<Children>().is_some_and(|children| {
+        children
+            .iter()
+            .all(|child| points_back(*child, entity.id(), world))
+    })
+}
+
+fn points_back(child: Entity, parent: Entity, world: &World) -> bool {
+    world
+        .get::<Parent>(child)
+        .is_some_and(|child_parent| child_parent.get() == parent)
+}
+
+fn repair_parent_link() -> Policy {
+    repair(|entity: EntityRef, commands: &mut Commands| {
+        commands
+            .entity(entity.id())
+            .queue(|entity: Entity, world: &mut World| {
+                let Some(parent) = world.get::<Parent>(entity).map(Parent::get) else {
+                    return;
+                };
+                if let Ok(mut parent) = world.get_entity_mut(parent) {
+                    parent.add_child(entity);
+                } else {
+                    // NOTE: Remove `Parent` directly, since the parent doesn't exist.
+                    world.entity_mut(entity).remove::<Parent>();
+                }
+            });
+    })
+}
+
+fn repair_children_links() -> Policy {
+    repair(|entity: EntityRef, commands: &mut Commands| {
+        commands
+            .entity(entity.id())
+            .queue(|entity: Entity, world: &mut World| {
+                let Some(children) = world.get::<Children>(entity) else {
+                    return;
+                };
+                let children: Vec<Entity> = children
+                    .iter()
+                    .copied()
+                    .filter(|child| points_back(*child, entity, world))
+                    .collect();
+                let mut entity = world.entity_mut(entity);
+                // NOTE: Remove `Children` directly, since some children may not exist.
+                entity.remove::<Children>();
+                entity.add_children(&children);
+            });
+    })
+}
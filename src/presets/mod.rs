@@ -0,0 +1,15 @@
+//! Ready-made checks for common invariants.
+//!
+//! Each preset is a [`Plugin`](bevy_app::Plugin) which registers a group of related checks.
+
+pub mod core;
+pub mod hierarchy;
+
+#[cfg(feature = "presets_avian")]
+pub mod avian;
+
+#[cfg(feature = "presets_rapier")]
+pub mod rapier;
+
+#[cfg(feature = "presets_ui")]
+pub mod ui;
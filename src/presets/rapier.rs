@@ -0,0 +1,39 @@
+//! Checks for [Rapier](https://github.com/dimforge/bevy_rapier) physics invariants.
+
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_rapier3d::prelude::*;
+use bevy_transform::prelude::*;
+
+use crate::{add_check, condition, invalid, repair, repair_insert_default, Check, CheckSpec};
+
+/// A [`Plugin`] which adds checks for Rapier physics invariants.
+///
+/// # Checks
+///
+/// - [`RigidBody`] requires [`Collider`], or the instance is marked as invalid.
+/// - [`Collider`] requires [`Transform`], repaired with its default value.
+/// - [`Velocity`] with any non-finite values is repaired to zero.
+pub struct RapierChecksPlugin;
+
+impl Plugin for RapierChecksPlugin {
+    fn build(&self, app: &mut App) {
+        app.check::<RigidBody, Without<Collider>>(invalid())
+            .check::<Collider, Without<Transform>>(repair_insert_default::<Transform>());
+        add_check::<Velocity, ()>(
+            app.main_mut(),
+            repair(|entity: EntityRef, commands: &mut Commands| {
+                commands.entity(entity.id()).insert(Velocity::zero());
+            })
+            .into(),
+            CheckSpec {
+                description: "Velocity is not finite".to_owned(),
+                condition: Some(condition(|entity: EntityRef, _: &World| {
+                    !entity.get::<Velocity>().is_some_and(|velocity| {
+                        velocity.linvel.is_finite() && velocity.angvel.is_finite()
+                    })
+                })),
+            },
+        );
+    }
+}
@@ -0,0 +1,56 @@
+//! Checks for [`bevy_ui`] invariants.
+
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::prelude::*;
+use bevy_text::{TextLayout, TextSpan};
+use bevy_ui::{ComputedNode, Node};
+
+use crate::{add_check, condition, purge, repair, repair_insert_default, Check, CheckSpec};
+
+/// A [`Plugin`] which adds checks for UI invariants.
+///
+/// # Checks
+///
+/// - [`Node`] requires [`ComputedNode`], repaired with its default value.
+/// - [`Node`] must not be a child of a non-UI entity, repaired by removing its parent.
+/// - [`TextSpan`] must be a child of a text entity or another span, or it is purged.
+pub struct UiChecksPlugin;
+
+impl Plugin for UiChecksPlugin {
+    fn build(&self, app: &mut App) {
+        app.check::<Node, Without<ComputedNode>>(repair_insert_default::<ComputedNode>())
+            .check::<TextSpan, Without<Parent>>(purge());
+        add_check::<Node, ()>(
+            app.main_mut(),
+            repair(|entity: EntityRef, commands: &mut Commands| {
+                commands.entity(entity.id()).remove_parent();
+            })
+            .into(),
+            CheckSpec {
+                description: "Node is a child of a non-UI entity".to_owned(),
+                condition: Some(condition(|entity: EntityRef, world: &World| {
+                    entity.get::<Parent>().is_some_and(|parent| {
+                        world
+                            .get_entity(parent.get())
+                            .map_or(true, |parent| !parent.contains::<Node>())
+                    })
+                })),
+            },
+        );
+        add_check::<TextSpan, ()>(
+            app.main_mut(),
+            purge().into(),
+            CheckSpec {
+                description: "TextSpan is a child of a non-text entity".to_owned(),
+                condition: Some(condition(|entity: EntityRef, world: &World| {
+                    entity.get::<Parent>().is_some_and(|parent| {
+                        world.get_entity(parent.get()).map_or(true, |parent| {
+                            !parent.contains::<TextLayout>() && !parent.contains::<TextSpan>()
+                        })
+                    })
+                })),
+            },
+        );
+    }
+}
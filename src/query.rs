@@ -0,0 +1,340 @@
+//! Queries and filters over checked entities.
+
+use std::marker::PhantomData;
+
+use bevy_ecs::{
+    archetype::Archetype,
+    component::{ComponentId, Components, Tick},
+    prelude::*,
+    query::{
+        FilteredAccess, QueryData, QueryFilter, QuerySingleError, ReadOnlyQueryData, WorldQuery,
+    },
+    reflect::ReflectComponent,
+    storage::{Table, TableRow},
+    system::SystemParam,
+    world::unsafe_world_cell::UnsafeWorldCell,
+};
+use bevy_reflect::Reflect;
+use bevy_utils::HashSet;
+use moonshine_kind::prelude::*;
+
+use crate::{Checked, Invalid};
+
+/// A [`QueryFilter`] which indicates that an [`Entity`] has been checked and is valid.
+///
+/// See [`invalid`](crate::invalid) for a usage example.
+///
+/// # Query Data
+///
+/// This type may also wrap any [`QueryData`] to only fetch valid entities:
+///
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+/// use moonshine_kind::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Apple;
+///
+/// fn eat_apples(apples: Query<Valid<Instance<Apple>>>) {
+///     for apple in apples.iter() {
+///         println!("{apple:?} is valid!");
+///     }
+/// }
+/// ```
+pub struct Valid<D: QueryData = ()>(pub(crate) PhantomData<D>);
+
+impl Valid {
+    /// Returns the reason why the given entity is not valid, or `None` if it is valid.
+    ///
+    /// This is only available in debug builds, to help diagnose why an entity is missing from a query.
+    #[cfg(debug_assertions)]
+    pub fn explain(entity: EntityRef) -> Option<String> {
+        if let Some(invalid) = entity.get::<Invalid>() {
+            Some(format!("{} is invalid: {}", entity.id(), invalid.reason))
+        } else if !entity.contains::<Checked>() {
+            Some(format!("{} is not checked yet", entity.id()))
+        } else {
+            None
+        }
+    }
+}
+
+/// A [`SystemParam`] which provides access to all valid instances of kind `T`.
+///
+/// This is equivalent to `Query<Instance<T>, Valid>`, with some convenience methods.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Apple;
+///
+/// fn eat_apples(apples: Validated<Apple>) {
+///     for apple in apples.iter() {
+///         println!("{apple:?} is valid!");
+///     }
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct Validated<'w, 's, T: Kind> {
+    pub(crate) query: Query<'w, 's, Instance<T>, Valid>,
+}
+
+impl<T: Kind> Validated<'_, '_, T> {
+    /// Iterates over all valid instances of `T`.
+    pub fn iter(&self) -> impl Iterator<Item = Instance<T>> + '_ {
+        self.query.iter()
+    }
+
+    /// Returns the instance of `T` associated with the given entity, if it is valid.
+    pub fn get(&self, entity: Entity) -> Option<Instance<T>> {
+        self.query.get(entity).ok()
+    }
+
+    /// Returns true if the given entity is a valid instance of `T`.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.query.contains(entity)
+    }
+
+    /// Returns the only valid instance of `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is not exactly one valid instance of `T`.
+    pub fn single(&self) -> Instance<T> {
+        self.query.single()
+    }
+
+    /// Returns the only valid instance of `T`, or an error if there is not exactly one.
+    pub fn get_single(&self) -> Result<Instance<T>, QuerySingleError> {
+        self.query.get_single()
+    }
+
+    /// Returns the number of valid instances of `T`.
+    pub fn len(&self) -> usize {
+        self.query.iter().count()
+    }
+
+    /// Returns true if there are no valid instances of `T`.
+    pub fn is_empty(&self) -> bool {
+        self.query.is_empty()
+    }
+}
+
+/// A [`Query`] over all valid instances of kind `T`.
+///
+/// See [`InstanceQueryExt`] for additional helpers.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Apple;
+///
+/// fn count_apples(apples: ValidInstances<Apple>) {
+///     let apples = apples.collect_set();
+///     println!("{} valid apples", apples.len());
+/// }
+/// ```
+pub type ValidInstances<'w, 's, T> = Query<'w, 's, Instance<T>, Valid>;
+
+/// An extension trait with helpers for queries over instances of kind `T`, such as [`ValidInstances`].
+pub trait InstanceQueryExt<T: Kind> {
+    /// Collects all instances into a [`HashSet`].
+    fn collect_set(&self) -> HashSet<Instance<T>>;
+
+    /// Returns true if the given instance matches this query.
+    fn contains_instance(&self, instance: Instance<T>) -> bool;
+}
+
+impl<T: Kind, F: QueryFilter> InstanceQueryExt<T> for Query<'_, '_, Instance<T>, F> {
+    fn collect_set(&self) -> HashSet<Instance<T>> {
+        self.iter().collect()
+    }
+
+    fn contains_instance(&self, instance: Instance<T>) -> bool {
+        self.contains(instance.entity())
+    }
+}
+
+/// The [`WorldQuery`] which [`Valid`] delegates to.
+#[cfg(not(feature = "disable"))]
+type ValidQuery<D> = (D, With<Checked>, Without<Invalid>);
+
+// NOTE: Entities are never checked when checks are disabled, so they must all be considered valid.
+#[cfg(feature = "disable")]
+type ValidQuery<D> = (D, (), ());
+
+// SAFETY: All methods delegate to `ValidQuery<D>`, which is a valid `WorldQuery`.
+unsafe impl<D: QueryData> WorldQuery for Valid<D> {
+    type Item<'w> = D::Item<'w>;
+    type Fetch<'w> = <ValidQuery<D> as WorldQuery>::Fetch<'w>;
+    type State = <ValidQuery<D> as WorldQuery>::State;
+
+    fn shrink<'wlong: 'wshort, 'wshort>(item: Self::Item<'wlong>) -> Self::Item<'wshort> {
+        D::shrink(item)
+    }
+
+    fn shrink_fetch<'wlong: 'wshort, 'wshort>(fetch: Self::Fetch<'wlong>) -> Self::Fetch<'wshort> {
+        <ValidQuery<D> as WorldQuery>::shrink_fetch(fetch)
+    }
+
+    unsafe fn init_fetch<'w>(
+        world: UnsafeWorldCell<'w>,
+        state: &Self::State,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Fetch<'w> {
+        <ValidQuery<D> as WorldQuery>::init_fetch(world, state, last_run, this_run)
+    }
+
+    const IS_DENSE: bool = <ValidQuery<D> as WorldQuery>::IS_DENSE;
+
+    unsafe fn set_archetype<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        state: &Self::State,
+        archetype: &'w Archetype,
+        table: &'w Table,
+    ) {
+        <ValidQuery<D> as WorldQuery>::set_archetype(fetch, state, archetype, table);
+    }
+
+    unsafe fn set_table<'w>(fetch: &mut Self::Fetch<'w>, state: &Self::State, table: &'w Table) {
+        <ValidQuery<D> as WorldQuery>::set_table(fetch, state, table);
+    }
+
+    unsafe fn fetch<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        entity: Entity,
+        table_row: TableRow,
+    ) -> Self::Item<'w> {
+        <ValidQuery<D> as WorldQuery>::fetch(fetch, entity, table_row).0
+    }
+
+    fn update_component_access(state: &Self::State, access: &mut FilteredAccess<ComponentId>) {
+        <ValidQuery<D> as WorldQuery>::update_component_access(state, access);
+    }
+
+    fn init_state(world: &mut World) -> Self::State {
+        <ValidQuery<D> as WorldQuery>::init_state(world)
+    }
+
+    fn get_state(components: &Components) -> Option<Self::State> {
+        <ValidQuery<D> as WorldQuery>::get_state(components)
+    }
+
+    fn matches_component_set(
+        state: &Self::State,
+        set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        <ValidQuery<D> as WorldQuery>::matches_component_set(state, set_contains_id)
+    }
+}
+
+// SAFETY: `Valid<D>` accesses the same components as `D`, and its read-only form is `Valid<D::ReadOnly>`.
+unsafe impl<D: QueryData> QueryData for Valid<D> {
+    type ReadOnly = Valid<D::ReadOnly>;
+}
+
+// SAFETY: `Valid<D>` is read-only if `D` is read-only.
+unsafe impl<D: ReadOnlyQueryData> ReadOnlyQueryData for Valid<D> {}
+
+// SAFETY: `Valid` does not fetch any data, and only filters archetypes.
+unsafe impl QueryFilter for Valid {
+    const IS_ARCHETYPAL: bool = true;
+
+    unsafe fn filter_fetch(_: &mut Self::Fetch<'_>, _: Entity, _: TableRow) -> bool {
+        true
+    }
+}
+
+/// A [`QueryFilter`] which indicates that an [`Entity`] has not been checked yet.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// fn pending_entities(query: Query<Entity, Unchecked>) {
+///     for entity in query.iter() {
+///         println!("{entity} is not checked yet");
+///     }
+/// }
+/// ```
+#[derive(QueryFilter)]
+pub struct Unchecked(pub(crate) Without<Checked>);
+
+/// A [`Component`] which exempts an [`Entity`] from all checks.
+///
+/// # Usage
+///
+/// This is useful for entities which are intentionally incomplete, such as editor previews, debug spawns,
+/// or prefabs which are constructed over multiple frames. Once this component is removed, the entity is
+/// checked as usual.
+///
+/// See [`SkipCheck`] to only exempt an entity from the checks of a single kind.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Apple;
+///
+/// #[derive(Component)]
+/// struct Fresh;
+///
+/// let mut app = App::new();
+/// app.add_plugins(MinimalPlugins)
+///     .check::<Apple, Without<Fresh>>(purge());
+///
+/// let entity = app.world_mut().spawn((Apple, SkipChecks)).id();
+/// app.update();
+/// assert!(app.world().get_entity(entity).is_ok());
+/// ```
+#[derive(Component, Reflect, Clone, Copy, Debug, Default)]
+#[reflect(Component, Default)]
+pub struct SkipChecks;
+
+/// A [`Component`] which exempts an [`Entity`] from all checks of kind `T`.
+///
+/// See [`SkipChecks`] for details.
+#[derive(Component)]
+pub struct SkipCheck<T: Kind>(pub(crate) PhantomData<T>);
+
+impl<T: Kind> Default for SkipCheck<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// Instances of `T` which are not checked yet, and not exempt from any checks of kind `T`.
+pub(crate) type Pending<T> = (Unchecked, Without<SkipChecks>, Without<SkipCheck<T>>);
+
+/// A [`QueryFilter`] which indicates that an [`Entity`] has been checked, regardless of whether it is valid.
+///
+/// See [`Valid`] to only query valid entities.
+#[derive(QueryFilter)]
+pub struct IsChecked(pub(crate) With<Checked>);
+
+/// A [`QueryFilter`] which indicates that an [`Entity`] has been checked and is invalid.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// fn show_warnings(query: Query<Entity, IsInvalid>) {
+///     for entity in query.iter() {
+///         println!("{entity} is invalid!");
+///     }
+/// }
+/// ```
+#[derive(QueryFilter)]
+pub struct IsInvalid(pub(crate) With<Invalid>);
@@ -0,0 +1,189 @@
+//! Reasons for which entities are invalidated.
+
+use bevy_ecs::{component::Tick, prelude::*, reflect::ReflectComponent, system::EntityCommands};
+use bevy_reflect::Reflect;
+use bevy_utils::tracing::{error, warn};
+
+use crate::{
+    panic_invalid, CheckFailure, Checked, DryRun, Invalid, OnCheckFailure, Outcome, Severity,
+    StrictChecks, ValidationLog, ValidationLogEntry,
+};
+
+/// An extension trait used to manually mark an [`Entity`] as invalid.
+///
+/// # Usage
+///
+/// Use this when a system detects an invalid entity outside of any registered [`Check`](crate::Check).
+/// The entity is treated exactly as if it failed a check with the [`invalid`](crate::invalid) policy.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Health(f32);
+///
+/// fn detect_corruption(query: Query<(Entity, &Health)>, mut commands: Commands) {
+///     for (entity, health) in query.iter() {
+///         if health.0.is_nan() {
+///             commands.entity(entity).mark_invalid("Health is NaN");
+///         }
+///     }
+/// }
+/// ```
+pub trait MarkInvalid {
+    fn mark_invalid(self, reason: impl Into<String>) -> Self;
+}
+
+impl MarkInvalid for &mut EntityCommands<'_> {
+    fn mark_invalid(self, reason: impl Into<String>) -> Self {
+        let reason = reason.into();
+        self.queue(move |entity: Entity, world: &mut World| {
+            invalidate(world, entity, &reason, "");
+        })
+    }
+}
+
+impl MarkInvalid for &mut EntityWorldMut<'_> {
+    fn mark_invalid(self, reason: impl Into<String>) -> Self {
+        let reason = reason.into();
+        let entity = self.id();
+        self.world_scope(|world| invalidate(world, entity, &reason, ""));
+        self
+    }
+}
+
+/// A [`Component`] which lists all failed checks of an invalid [`Entity`].
+///
+/// # Usage
+///
+/// When several checks fail for the same entity, each failure is recorded, in order. This includes any reasons
+/// given to [`mark_invalid`](MarkInvalid::mark_invalid). All reasons are removed when the entity is checked again.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// fn show_reasons(query: Query<(Entity, &InvalidReasons)>) {
+///     for (entity, reasons) in query.iter() {
+///         for reason in reasons.iter() {
+///             println!("{entity} failed {}", reason.check);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Component, Reflect, Clone, Debug, Default)]
+#[reflect(Component, Default)]
+pub struct InvalidReasons(pub(crate) Vec<InvalidReason>);
+
+impl InvalidReasons {
+    /// Iterates over all failures, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &InvalidReason> {
+        self.0.iter()
+    }
+
+    /// Returns the number of failures.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if there are no failures.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// A single failed check, as recorded in [`InvalidReasons`].
+#[derive(Reflect, Clone, Debug, PartialEq, Eq)]
+pub struct InvalidReason {
+    /// Name of the failed check, or the reason if the entity was invalidated manually.
+    pub check: String,
+    /// Description of the check filter or condition, or empty if the entity was invalidated manually.
+    pub filter: String,
+    /// The change tick at which the check failed.
+    pub tick: Tick,
+}
+
+/// Records a failed check in the [`InvalidReasons`] of the given entity.
+pub(crate) fn add_invalid_reason(
+    entity: Entity,
+    check: &str,
+    filter: &str,
+    world: &World,
+    commands: &mut Commands,
+) {
+    let reason = InvalidReason {
+        check: check.to_owned(),
+        filter: filter.to_owned(),
+        tick: world.read_change_tick(),
+    };
+    commands.queue(move |world: &mut World| {
+        if let Ok(mut entity) = world.get_entity_mut(entity) {
+            push_invalid_reason(&mut entity, reason);
+        }
+    });
+}
+
+/// Marks the given `entity` as invalid outside of any check system.
+///
+/// The entity is treated exactly as if it failed a check with the [`invalid`](crate::invalid) policy.
+pub(crate) fn invalidate(world: &mut World, entity: Entity, check: &str, filter: &str) {
+    let Ok(entity_ref) = world.get_entity(entity) else {
+        return;
+    };
+    let reason = if filter.is_empty() {
+        check.to_owned()
+    } else {
+        format!("{check}: {filter}")
+    };
+    if let Some(on_failure) = world.get_resource::<OnCheckFailure>() {
+        (on_failure.0)(&CheckFailure {
+            entity,
+            kind: String::new(),
+            check: check.to_owned(),
+            reason: reason.clone(),
+            severity: Severity::Error,
+            outcome: Some(Outcome::Invalid),
+        });
+    }
+    if world.contains_resource::<DryRun>() {
+        warn!("{entity} would be invalid: {reason}");
+        return;
+    }
+    if world.get_resource::<StrictChecks>().is_some_and(|s| s.0) {
+        panic_invalid(entity_ref, world, &reason);
+    }
+    error!("{entity} is invalid: {reason}");
+    let tick = world.read_change_tick();
+    let invalid = Invalid::new(check, world);
+    let time = invalid.time;
+    let mut entity_mut = world.entity_mut(entity);
+    entity_mut.insert((Checked, invalid));
+    push_invalid_reason(
+        &mut entity_mut,
+        InvalidReason {
+            check: check.to_owned(),
+            filter: filter.to_owned(),
+            tick,
+        },
+    );
+    if let Some(mut log) = world.get_resource_mut::<ValidationLog>() {
+        log.push(ValidationLogEntry {
+            time,
+            entity,
+            check: check.to_owned(),
+            outcome: Outcome::Invalid,
+            message: format!("{entity} is invalid: {reason}"),
+        });
+    }
+}
+
+fn push_invalid_reason(entity: &mut EntityWorldMut, reason: InvalidReason) {
+    if let Some(mut reasons) = entity.get_mut::<InvalidReasons>() {
+        reasons.0.push(reason);
+    } else {
+        entity.insert(InvalidReasons(vec![reason]));
+    }
+}
@@ -0,0 +1,451 @@
+//! Registry of all checks added to an app.
+
+use std::any::TypeId;
+use std::borrow::Cow;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    prelude::*,
+    schedule::{InternedScheduleLabel, ScheduleLabel},
+    world::WorldId,
+};
+use bevy_utils::{
+    tracing::{warn, Level},
+    HashMap, HashSet,
+};
+use moonshine_kind::prelude::*;
+
+use crate::{
+    check_name, check_now, evaluate, CheckFilter, FormatFn, Gate, Invalid, Policy, Severity,
+    SkipCheck, SkipChecks, Unchecked,
+};
+
+/// Warns about (or panics on) any checks which are registered more than once.
+pub(crate) fn detect_conflicts(registry: Res<CheckRegistry>) {
+    let mut seen: HashMap<(TypeId, TypeId, Option<usize>), &CheckEntry> = HashMap::new();
+    for check in registry.iter() {
+        if let Some(previous) = seen.insert(conflict_key(check), check) {
+            report_conflict(previous, check, registry.strict);
+        }
+    }
+}
+
+/// Returns the key of the given check, which is the same for any two conflicting checks.
+fn conflict_key(check: &CheckEntry) -> (TypeId, TypeId, Option<usize>) {
+    (check.kind, check.filter, check.condition)
+}
+
+/// Warns about (or panics on, if `strict`) two conflicting checks.
+fn report_conflict(previous: &CheckEntry, check: &CheckEntry, strict: bool) {
+    let name = &check.name;
+    let message = if previous.policy != check.policy {
+        format!(
+            "check '{name}' is registered with conflicting policies: {:?} and {:?}",
+            previous.policy, check.policy
+        )
+    } else if matches!(
+        (&previous.action, &check.action),
+        (Some(Policy::Repair(a)), Some(Policy::Repair(b))) if !a.ptr_eq(b)
+    ) {
+        format!("check '{name}' is registered with conflicting repairs")
+    } else {
+        format!("check '{name}' is registered more than once")
+    };
+
+    if strict {
+        panic!("{message}");
+    }
+    warn!("{message}");
+}
+
+/// A [`Resource`] which lists all registered checks.
+///
+/// # Usage
+///
+/// This resource is useful for tools, debug interfaces, and documentation generators which need to
+/// enumerate all checks. Checks may also be disabled at runtime using [`set_enabled`](CheckRegistry::set_enabled).
+///
+/// Note that dynamic checks are not included in the registry.
+///
+/// # Multiple Worlds
+///
+/// Each [`World`] has its own registry. Tools which host multiple worlds may maintain separate rule sets
+/// by building a registry with [`add`](CheckRegistry::add) and inserting it into each world.
+/// Checks added this way are not scheduled; use [`ApplyChecks::apply_checks`] to evaluate them.
+///
+/// A registry may also be cloned to share the same rule set between worlds. Only checks which may be applied
+/// immediately are cloned, since other checks (such as [`check_static`](crate::Check::check_static)) depend on
+/// the systems of the app they were added to.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Apple;
+///
+/// #[derive(Component)]
+/// struct Fresh;
+///
+/// let mut app = App::new();
+/// app.check::<Apple, Without<Fresh>>(purge().with_label("food"));
+///
+/// let registry = app.world().resource::<CheckRegistry>();
+/// let check = registry.iter().next().unwrap();
+/// assert_eq!(check.policy(), PolicyKind::Purge);
+/// assert_eq!(check.labels(), ["food"]);
+/// assert!(check.is_enabled());
+/// ```
+#[derive(Resource, Default)]
+pub struct CheckRegistry {
+    pub(crate) checks: Vec<CheckEntry>,
+    pub(crate) strict: bool,
+    pub(crate) disabled_groups: HashSet<String>,
+}
+
+impl Clone for CheckRegistry {
+    fn clone(&self) -> Self {
+        Self {
+            checks: self
+                .checks
+                .iter()
+                .filter(|check| check.action.is_some())
+                .cloned()
+                .collect(),
+            strict: self.strict,
+            disabled_groups: self.disabled_groups.clone(),
+        }
+    }
+}
+
+impl CheckRegistry {
+    /// Panics if any check is added to this registry more than once, or with conflicting policies.
+    ///
+    /// See [`CheckPlugin::strict_registration`](crate::CheckPlugin::strict_registration) for details.
+    pub fn strict_registration(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    /// Iterates over all registered checks, in order of registration.
+    pub fn iter(&self) -> impl Iterator<Item = &CheckEntry> {
+        self.checks.iter()
+    }
+
+    /// Returns the first check with the given name, if it exists.
+    pub fn get(&self, name: &str) -> Option<&CheckEntry> {
+        self.iter().find(|check| check.name == name)
+    }
+
+    /// Returns the number of registered checks.
+    pub fn len(&self) -> usize {
+        self.checks.len()
+    }
+
+    /// Returns true if no checks are registered.
+    pub fn is_empty(&self) -> bool {
+        self.checks.is_empty()
+    }
+
+    /// Enables or disables all checks with the given name.
+    ///
+    /// Disabled checks are not evaluated, and any instances are left unchecked until the check is enabled again.
+    /// Returns false if no such check exists.
+    pub fn set_enabled(&mut self, name: &str, enabled: bool) -> bool {
+        let mut found = false;
+        for check in self.checks.iter_mut().filter(|check| check.name == name) {
+            check.enabled = enabled;
+            found = true;
+        }
+        found
+    }
+
+    /// Enables or disables all checks in the given group, including any which are registered later.
+    ///
+    /// See [`in_check_group`](crate::ConfigureCheck::in_check_group) for details.
+    pub fn set_group_enabled(&mut self, group: &str, enabled: bool) {
+        if enabled {
+            self.disabled_groups.remove(group);
+        } else {
+            self.disabled_groups.insert(group.to_owned());
+        }
+        for check in self
+            .checks
+            .iter_mut()
+            .filter(|check| check.group.as_deref() == Some(group))
+        {
+            check.enabled = enabled;
+        }
+    }
+
+    /// Returns true if the given group is enabled.
+    pub fn is_group_enabled(&self, group: &str) -> bool {
+        !self.disabled_groups.contains(group)
+    }
+
+    /// Adds a new check to this registry, without scheduling it.
+    ///
+    /// See [`ApplyChecks::apply_checks`] for details.
+    pub fn add<T: Kind, F: CheckFilter>(&mut self, policy: Policy) -> &mut Self {
+        let check = CheckEntry {
+            name: check_name::<T, F>(),
+            kind: TypeId::of::<T>(),
+            kind_name: std::any::type_name::<T>().into(),
+            filter: TypeId::of::<F>(),
+            filter_name: std::any::type_name::<F>().into(),
+            condition: None,
+            policy: PolicyKind::of(&policy),
+            labels: Vec::new(),
+            group: None,
+            schedule: PreUpdate.intern(),
+            enabled: true,
+            severity: Severity::Error,
+            evaluate: evaluate::<T, F>(),
+            action: Some(policy),
+            skip: skip_check::<T>,
+            pending: pending_instances::<T>(),
+            gate: None,
+            description: moonshine_util::get_short_name(std::any::type_name::<F>()),
+            log_level: Some(Level::ERROR),
+            formatter: None,
+            lenient: false,
+        };
+        if let Some(previous) = self
+            .checks
+            .iter()
+            .find(|previous| conflict_key(previous) == conflict_key(&check))
+        {
+            report_conflict(previous, &check, self.strict);
+        }
+        self.checks.push(check);
+        self
+    }
+}
+
+/// An extension trait used to evaluate the checks of a [`World`] without any schedules.
+pub trait ApplyChecks {
+    /// Evaluates all checks in the [`CheckRegistry`] of this world against all unchecked entities,
+    /// and applies their policies immediately.
+    ///
+    /// # Usage
+    ///
+    /// This is useful for worlds which are not run by an [`App`], such as editor previews, or worlds
+    /// spawned in tests. Each world is evaluated against its own registry.
+    ///
+    /// Checks which are statically dispatched are not evaluated, and results are not included in the [`CheckReport`](crate::CheckReport).
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Apple;
+    ///
+    /// #[derive(Component)]
+    /// struct Fresh;
+    ///
+    /// let mut registry = CheckRegistry::default();
+    /// registry.add::<Apple, Without<Fresh>>(purge());
+    ///
+    /// let mut world = World::new();
+    /// world.insert_resource(registry);
+    ///
+    /// let entity = world.spawn(Apple).id();
+    /// world.apply_checks();
+    /// assert!(world.get_entity(entity).is_err());
+    /// ```
+    fn apply_checks(&mut self);
+}
+
+impl ApplyChecks for World {
+    fn apply_checks(&mut self) {
+        let Some(registry) = self.get_resource::<CheckRegistry>() else {
+            return;
+        };
+        // NOTE: Only instances of checked kinds are evaluated, once for each kind.
+        let mut kinds = HashSet::new();
+        let collectors: Vec<CollectPending> = registry
+            .iter()
+            .filter(|check| check.enabled && kinds.insert(check.kind))
+            .map(|check| check.pending.clone())
+            .collect();
+        let mut entities = Vec::new();
+        for collect in collectors {
+            collect(self, &mut entities);
+        }
+        entities.sort_unstable();
+        entities.dedup();
+        for entity in entities {
+            check_now(self, entity);
+        }
+    }
+}
+
+/// Collects all unchecked instances of a checked kind in the given world.
+pub(crate) type CollectPending = Arc<dyn Fn(&mut World, &mut Vec<Entity>) + Send + Sync>;
+
+/// Returns a [`CollectPending`] function for kind `T`.
+///
+/// The query state is cached per world, so it is only initialized once.
+pub(crate) fn pending_instances<T: Kind>() -> CollectPending {
+    type State<T> = (
+        WorldId,
+        QueryState<Instance<T>, (Unchecked, Without<SkipChecks>)>,
+    );
+    let state: Mutex<Option<State<T>>> = Mutex::default();
+    Arc::new(move |world: &mut World, entities: &mut Vec<Entity>| {
+        let mut state = state.lock().unwrap();
+        if !state.as_ref().is_some_and(|(id, _)| *id == world.id()) {
+            *state = Some((world.id(), QueryState::new(world)));
+        }
+        let (_, query) = state.as_mut().unwrap();
+        entities.extend(query.iter(world).map(|instance| instance.entity()));
+    })
+}
+
+/// A single registered check, as listed in the [`CheckRegistry`].
+#[derive(Clone)]
+pub struct CheckEntry {
+    pub(crate) name: String,
+    pub(crate) kind: TypeId,
+    pub(crate) kind_name: Cow<'static, str>,
+    pub(crate) filter: TypeId,
+    pub(crate) filter_name: Cow<'static, str>,
+    /// Identity of the additional condition of this check, if any.
+    pub(crate) condition: Option<usize>,
+    pub(crate) policy: PolicyKind,
+    pub(crate) labels: Vec<String>,
+    pub(crate) group: Option<String>,
+    pub(crate) schedule: InternedScheduleLabel,
+    pub(crate) enabled: bool,
+    pub(crate) severity: Severity,
+    pub(crate) evaluate: Evaluate,
+    /// The policy of this check, if it may be applied immediately.
+    pub(crate) action: Option<Policy>,
+    /// Returns true if the given entity is exempt from this check.
+    pub(crate) skip: fn(&World, Entity) -> bool,
+    pub(crate) pending: CollectPending,
+    /// The condition under which this check applies, if not always.
+    pub(crate) gate: Option<Gate>,
+    /// Description of the check filter or condition, used as the default failure reason.
+    pub(crate) description: String,
+    pub(crate) log_level: Option<Level>,
+    pub(crate) formatter: Option<FormatFn>,
+    pub(crate) lenient: bool,
+}
+
+impl CheckEntry {
+    /// Returns the name of this check, as it appears in logs and the [`CheckReport`](crate::CheckReport).
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the [`TypeId`] of the checked [`Kind`].
+    pub fn kind(&self) -> TypeId {
+        self.kind
+    }
+
+    /// Returns the type name of the checked [`Kind`].
+    pub fn kind_name(&self) -> &str {
+        &self.kind_name
+    }
+
+    /// Returns the type name of the check filter.
+    pub fn filter_name(&self) -> &str {
+        &self.filter_name
+    }
+
+    /// Returns the kind of [`Policy`] applied if this check fails.
+    pub fn policy(&self) -> PolicyKind {
+        self.policy
+    }
+
+    /// Returns all labels of this check.
+    ///
+    /// See [`with_label`](crate::ConfigureCheck::with_label) for details.
+    pub fn labels(&self) -> &[String] {
+        &self.labels
+    }
+
+    /// Returns the group of this check, if any.
+    ///
+    /// See [`in_check_group`](crate::ConfigureCheck::in_check_group) for details.
+    pub fn group(&self) -> Option<&str> {
+        self.group.as_deref()
+    }
+
+    /// Returns the schedule in which this check is evaluated.
+    pub fn schedule(&self) -> InternedScheduleLabel {
+        self.schedule
+    }
+
+    /// Returns true if this check is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Returns the [`Severity`] of this check.
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+}
+
+pub(crate) fn skip_check<T: Kind>(world: &World, entity: Entity) -> bool {
+    world.get::<SkipCheck<T>>(entity).is_some()
+}
+
+impl Debug for CheckEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CheckEntry")
+            .field("name", &self.name)
+            .field("kind", &self.kind_name)
+            .field("filter", &self.filter_name)
+            .field("policy", &self.policy)
+            .field("labels", &self.labels)
+            .field("group", &self.group)
+            .field("schedule", &self.schedule)
+            .field("enabled", &self.enabled)
+            .field("severity", &self.severity)
+            .finish()
+    }
+}
+
+/// The kind of a [`Policy`], without any of its associated data.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum PolicyKind {
+    /// See [`Policy::Invalid`].
+    Invalid,
+    /// See [`Policy::Purge`].
+    Purge,
+    /// See [`Policy::PurgeAfter`].
+    PurgeAfter,
+    /// See [`Policy::Panic`].
+    Panic,
+    /// See [`Policy::Repair`].
+    Repair,
+    /// A policy which saves the instance before applying another policy.
+    Dump,
+    /// A custom [`PolicyImpl`](crate::PolicyImpl).
+    Custom,
+}
+
+impl PolicyKind {
+    /// Returns the kind of the given [`Policy`].
+    pub fn of(policy: &Policy) -> Self {
+        match policy {
+            Policy::Invalid => Self::Invalid,
+            Policy::Purge => Self::Purge,
+            Policy::PurgeAfter(_) => Self::PurgeAfter,
+            Policy::Panic => Self::Panic,
+            Policy::Repair(_) => Self::Repair,
+            Policy::Dump { .. } => Self::Dump,
+        }
+    }
+}
+
+/// Returns `None` if the entity is not checked, otherwise returns `true` if it passes the check.
+pub(crate) type Evaluate = Arc<dyn Fn(&mut World, Entity) -> Option<bool> + Send + Sync>;
@@ -0,0 +1,92 @@
+//! Methods which expose the validation state of the world over the [Bevy Remote Protocol](bevy_remote).
+
+use bevy_ecs::prelude::*;
+use bevy_remote::{error_codes, BrpError, BrpResult, RemotePlugin};
+use serde_json::{json, Value};
+
+use crate::{CheckAgain, Checked, Invalid};
+
+/// The method path for a `moonshine_check/list_invalid` request.
+///
+/// Returns a list of all invalid entities, each with the reason it was invalidated.
+pub const BRP_LIST_INVALID_METHOD: &str = "moonshine_check/list_invalid";
+
+/// The method path for a `moonshine_check/revalidate` request.
+///
+/// Forces the given entities to be checked again. If no entities are given, all checked entities are checked again.
+pub const BRP_REVALIDATE_METHOD: &str = "moonshine_check/revalidate";
+
+/// An extension trait used to add all check methods to a [`RemotePlugin`].
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_remote::RemotePlugin;
+/// use moonshine_check::remote::CheckRemoteMethods;
+///
+/// let mut app = App::new();
+/// app.add_plugins(RemotePlugin::default().with_check_methods());
+/// ```
+pub trait CheckRemoteMethods {
+    fn with_check_methods(self) -> Self;
+}
+
+impl CheckRemoteMethods for RemotePlugin {
+    fn with_check_methods(self) -> Self {
+        self.with_method(BRP_LIST_INVALID_METHOD, process_list_invalid_request)
+            .with_method(BRP_REVALIDATE_METHOD, process_revalidate_request)
+    }
+}
+
+/// Handles a `moonshine_check/list_invalid` request coming from a client.
+pub fn process_list_invalid_request(
+    In(_): In<Option<Value>>,
+    query: Query<(Entity, &Invalid)>,
+) -> BrpResult {
+    let entities: Vec<Value> = query
+        .iter()
+        .map(|(entity, invalid)| {
+            json!({
+                "entity": entity.to_bits(),
+                "reason": invalid.reason,
+                "time": invalid.time.as_secs_f64(),
+            })
+        })
+        .collect();
+    Ok(Value::Array(entities))
+}
+
+/// Handles a `moonshine_check/revalidate` request coming from a client.
+pub fn process_revalidate_request(In(params): In<Option<Value>>, world: &mut World) -> BrpResult {
+    let entities: Vec<Entity> = match params.and_then(|params| params.get("entities").cloned()) {
+        Some(entities) => serde_json::from_value::<Vec<u64>>(entities)
+            .map_err(|error| BrpError {
+                code: error_codes::INVALID_PARAMS,
+                message: error.to_string(),
+                data: None,
+            })?
+            .into_iter()
+            .map(|bits| {
+                Entity::try_from_bits(bits).map_err(|_| BrpError {
+                    code: error_codes::INVALID_PARAMS,
+                    message: format!("{bits} is not a valid entity"),
+                    data: None,
+                })
+            })
+            .collect::<Result<_, _>>()?,
+        None => world
+            .query_filtered::<Entity, With<Checked>>()
+            .iter(world)
+            .collect(),
+    };
+
+    let mut count = 0;
+    for entity in entities {
+        if let Ok(mut entity) = world.get_entity_mut(entity) {
+            entity.check_again();
+            count += 1;
+        }
+    }
+
+    Ok(json!({ "count": count }))
+}
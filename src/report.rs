@@ -0,0 +1,134 @@
+//! Statistics of each check pass.
+
+use bevy_ecs::prelude::*;
+
+use crate::Outcome;
+
+/// A [`Resource`] which summarizes the results of all checks during the last check pass.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Apple;
+///
+/// #[derive(Component)]
+/// struct Fresh;
+///
+/// let mut app = App::new();
+/// app.add_plugins(MinimalPlugins)
+///     .check::<Apple, Without<Fresh>>(purge());
+///
+/// app.world_mut().spawn(Apple);
+/// app.update();
+///
+/// let report = app.world().resource::<CheckReport>();
+/// assert_eq!(report.total().purged, 1);
+/// ```
+#[derive(Resource, Clone, Debug, Default)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+pub struct CheckReport {
+    pub(crate) checks: Vec<(String, CheckStats)>,
+}
+
+impl CheckReport {
+    /// Iterates over the names and statistics of all registered checks, in order of registration.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &CheckStats)> {
+        self.checks
+            .iter()
+            .map(|(name, stats)| (name.as_str(), stats))
+    }
+
+    /// Returns the statistics of the first check with the given name, if it exists.
+    pub fn get(&self, name: &str) -> Option<&CheckStats> {
+        self.iter()
+            .find_map(|(check, stats)| (check == name).then_some(stats))
+    }
+
+    /// Returns the sum of the statistics of all checks.
+    pub fn total(&self) -> CheckStats {
+        self.checks
+            .iter()
+            .fold(CheckStats::default(), |total, (_, stats)| total + *stats)
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl CheckReport {
+    /// Serializes this report into a JSON string.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins(MinimalPlugins).init_resource::<CheckReport>();
+    /// app.update();
+    ///
+    /// let json = app.world().resource::<CheckReport>().to_json().unwrap();
+    /// println!("{json}");
+    /// ```
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Serializes this report into a RON string.
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, Default::default())
+    }
+}
+
+/// Statistics of a single [`Check`](crate::Check) during a check pass. See [`CheckReport`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+pub struct CheckStats {
+    /// Number of instances which were checked.
+    pub checked: usize,
+    /// Number of instances which passed the check.
+    pub passed: usize,
+    /// Number of instances which were marked as invalid.
+    pub invalid: usize,
+    /// Number of instances which were repaired.
+    pub repaired: usize,
+    /// Number of instances which were purged.
+    pub purged: usize,
+    /// Number of instances which could not be repaired, and were handled by a fallback policy instead.
+    ///
+    /// See [`try_repair`](crate::try_repair) for details.
+    pub repair_failed: usize,
+    /// Number of instances which failed a check with [`Severity::Warning`](crate::Severity::Warning), and were still marked as valid.
+    ///
+    /// These instances are also counted as passed.
+    pub warned: usize,
+}
+
+impl CheckStats {
+    pub(crate) fn record(&mut self, outcome: Outcome) {
+        self.checked += 1;
+        match outcome {
+            Outcome::Valid => self.passed += 1,
+            Outcome::Invalid => self.invalid += 1,
+            Outcome::Repaired => self.repaired += 1,
+            Outcome::Purged => self.purged += 1,
+        }
+    }
+}
+
+impl std::ops::Add for CheckStats {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            checked: self.checked + other.checked,
+            passed: self.passed + other.passed,
+            invalid: self.invalid + other.invalid,
+            repaired: self.repaired + other.repaired,
+            purged: self.purged + other.purged,
+            repair_failed: self.repair_failed + other.repair_failed,
+            warned: self.warned + other.warned,
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! Checks which are registered at runtime, typically from scripts.
+//!
+//! # Usage
+//!
+//! This module is agnostic of any particular scripting solution. Scripting integrations (such as
+//! [`bevy_mod_scripting`](https://github.com/makspll/bevy_mod_scripting)) may expose [`ScriptedChecks`]
+//! to scripts through their world access bindings, and forward script callbacks as fixers.
+//!
+//! Scripted checks use the same [`CheckDefinition`] as [data-driven checks](crate::config),
+//! and are evaluated along with all other checks.
+//!
+//! # Example
+//! ```
+//! use bevy::prelude::*;
+//! use moonshine_check::config::{CheckDefinition, DynamicPolicy};
+//! use moonshine_check::scripting::{ScriptedChecks, ScriptedChecksPlugin};
+//!
+//! let mut app = App::new();
+//! app.add_plugins(ScriptedChecksPlugin);
+//!
+//! // Called from a script binding:
+//! app.world_mut().resource_mut::<ScriptedChecks>().add_with_fixer(
+//!     CheckDefinition {
+//!         kind: "my_game::Apple".to_string(),
+//!         requires: vec!["my_game::Fresh".to_string()],
+//!         forbids: vec![],
+//!         policy: DynamicPolicy::Invalid,
+//!     },
+//!     |world: &mut World, entity: Entity| {
+//!         // Invoke the script callback ...
+//!     },
+//! );
+//! ```
+
+use std::sync::{Arc, Mutex};
+
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use moonshine_save::load::LoadSystem;
+
+use crate::config::{CheckDefinition, DynamicCheck};
+use crate::{add_check_systems, check_schedule, init, CheckLedger, CheckSystems};
+
+/// A [`Plugin`] which evaluates all [`ScriptedChecks`].
+pub struct ScriptedChecksPlugin;
+
+impl Plugin for ScriptedChecksPlugin {
+    fn build(&self, app: &mut App) {
+        init(app.main_mut());
+        let schedule = check_schedule(app.main());
+        app.init_resource::<ScriptedChecks>();
+        add_check_systems(
+            app.main_mut(),
+            schedule,
+            (
+                resolve_scripted_checks
+                    .after(LoadSystem::Load)
+                    .before(CheckSystems),
+                run_scripted_checks
+                    .run_if(not(resource_exists::<CheckLedger>))
+                    .in_set(CheckSystems),
+            ),
+        );
+    }
+}
+
+/// A [`Resource`] used to register checks at runtime.
+///
+/// Any checks added to this resource are evaluated starting from the next frame.
+#[derive(Resource, Default)]
+pub struct ScriptedChecks {
+    pending: Vec<(CheckDefinition, Option<ScriptFixer>)>,
+    checks: Mutex<Vec<DynamicCheck>>,
+}
+
+/// A callback used to repair an instance which failed a scripted check.
+pub type ScriptFixer = Arc<dyn Fn(&mut World, Entity) + Send + Sync>;
+
+impl ScriptedChecks {
+    /// Adds a new check, using the [`policy`](CheckDefinition::policy) of the given definition.
+    pub fn add(&mut self, definition: CheckDefinition) {
+        self.pending.push((definition, None));
+    }
+
+    /// Adds a new check which repairs any failed instances using the given fixer.
+    ///
+    /// The [`policy`](CheckDefinition::policy) of the given definition is ignored.
+    pub fn add_with_fixer(
+        &mut self,
+        definition: CheckDefinition,
+        fixer: impl Fn(&mut World, Entity) + Send + Sync + 'static,
+    ) {
+        self.pending.push((definition, Some(Arc::new(fixer))));
+    }
+
+    /// Returns the number of scripted checks, including any which are not yet evaluated.
+    pub fn len(&self) -> usize {
+        self.pending.len() + self.checks.lock().unwrap().len()
+    }
+
+    /// Returns true if there are no scripted checks.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+fn resolve_scripted_checks(world: &mut World) {
+    world.resource_scope(|world, mut scripted: Mut<ScriptedChecks>| {
+        let ScriptedChecks { pending, checks } = &mut *scripted;
+        let checks = checks.get_mut().unwrap();
+        for (definition, fixer) in pending.drain(..) {
+            checks.push(DynamicCheck::new(definition, fixer));
+        }
+
+        for check in checks.iter_mut() {
+            check.resolve(world);
+        }
+    });
+}
+
+// NOTE: Scripted checks are evaluated with all other checks, before any of them are marked as checked.
+fn run_scripted_checks(world: &World, mut commands: Commands) {
+    let scripted = world.resource::<ScriptedChecks>();
+    for check in scripted.checks.lock().unwrap().iter_mut() {
+        check.run(world, &mut commands);
+    }
+}
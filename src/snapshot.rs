@@ -0,0 +1,112 @@
+//! Snapshots of instances taken when a check is evaluated.
+
+use bevy_ecs::prelude::*;
+use bevy_reflect::{PartialReflect, Reflect};
+
+use crate::Outcome;
+
+/// A hook which captures a snapshot of some components of an instance when a [`Check`](crate::Check) has a given [`Outcome`].
+///
+/// Each snapshot is sent as an [`OutcomeSnapshot`] event.
+/// Components are captured *before* the [`Policy`](crate::Policy) is applied, so the snapshot always reflects the state
+/// of the instance at the time it was checked.
+///
+/// Use [`sample_every`](SnapshotHook::sample_every) and [`max_per_frame`](SnapshotHook::max_per_frame)
+/// to bound the overhead of capturing snapshots when many instances are checked at once.
+pub struct SnapshotHook {
+    outcomes: Vec<Outcome>,
+    captures: Vec<fn(EntityRef) -> Option<Box<dyn PartialReflect>>>,
+    sample_every: usize,
+    max_per_frame: usize,
+    pub(crate) count: usize,
+    frame_count: usize,
+}
+
+impl SnapshotHook {
+    /// Creates a new hook which is invoked for any of the given [`Outcome`]s.
+    pub fn on(outcomes: impl IntoIterator<Item = Outcome>) -> Self {
+        Self {
+            outcomes: outcomes.into_iter().collect(),
+            captures: Vec::new(),
+            sample_every: 1,
+            max_per_frame: usize::MAX,
+            count: 0,
+            frame_count: 0,
+        }
+    }
+
+    /// Includes the component `T` in each snapshot, if present on the instance.
+    pub fn capture<T: Component + Reflect>(mut self) -> Self {
+        self.captures
+            .push(|entity| entity.get::<T>().map(|component| component.clone_value()));
+        self
+    }
+
+    /// Only captures one snapshot out of every `n` matching outcomes.
+    pub fn sample_every(mut self, n: usize) -> Self {
+        self.sample_every = n.max(1);
+        self
+    }
+
+    /// Captures at most `n` snapshots per frame.
+    pub fn max_per_frame(mut self, n: usize) -> Self {
+        self.max_per_frame = n;
+        self
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.frame_count = 0;
+    }
+
+    fn sample(&mut self, outcome: Outcome) -> bool {
+        if !self.outcomes.contains(&outcome) || self.frame_count >= self.max_per_frame {
+            return false;
+        }
+        self.count += 1;
+        if (self.count - 1) % self.sample_every != 0 {
+            return false;
+        }
+        self.frame_count += 1;
+        true
+    }
+}
+
+/// An [`Event`] sent by a [`SnapshotHook`].
+#[derive(Event)]
+pub struct OutcomeSnapshot {
+    /// The checked entity. Note that this entity may no longer exist if it was purged.
+    pub entity: Entity,
+    /// Name of the check which produced this snapshot.
+    pub check: String,
+    /// The [`Outcome`] of the check.
+    pub outcome: Outcome,
+    /// Values of all captured components which were present on the entity.
+    pub components: Vec<Box<dyn PartialReflect>>,
+}
+
+pub(crate) fn notify(
+    hooks: &mut [SnapshotHook],
+    check: &str,
+    outcome: Outcome,
+    entity: EntityRef,
+    commands: &mut Commands,
+) {
+    for hook in hooks.iter_mut() {
+        if !hook.sample(outcome) {
+            continue;
+        }
+        let snapshot = OutcomeSnapshot {
+            entity: entity.id(),
+            check: check.to_owned(),
+            outcome,
+            components: hook
+                .captures
+                .iter()
+                .filter_map(|capture| capture(entity))
+                .collect(),
+        };
+        commands.queue(move |world: &mut World| {
+            world.send_event(snapshot);
+        });
+    }
+}
@@ -0,0 +1,31 @@
+//! Strict mode, which escalates all check failures to panics.
+
+use bevy_ecs::prelude::*;
+
+/// A [`Resource`] which, when enabled, treats every [`invalid`](crate::invalid) and [`purge`](crate::purge) policy as [`panic`](crate::panic).
+///
+/// # Usage
+///
+/// Use this to make tests, CI, and development builds fail fast, while keeping release builds forgiving.
+/// By default, strict mode is only enabled in debug builds.
+///
+/// Checks may be exempted from strict mode using [`lenient`](crate::ConfigureCheck::lenient).
+/// Statically dispatched policies (see [`check_static`](crate::Check::check_static)) are not affected.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// let mut app = App::new();
+/// // Panic on any invalid entity, but only in debug builds:
+/// app.init_resource::<StrictChecks>();
+/// ```
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StrictChecks(pub bool);
+
+impl Default for StrictChecks {
+    fn default() -> Self {
+        Self(cfg!(debug_assertions))
+    }
+}
@@ -0,0 +1,299 @@
+//! Utilities for testing checks.
+//!
+//! # Example
+//! ```
+//! use bevy::prelude::*;
+//! use moonshine_check::prelude::*;
+//! use moonshine_check::test::RunChecks;
+//! use moonshine_check::{assert_invalid, assert_valid};
+//!
+//! #[derive(Component)]
+//! struct Apple;
+//!
+//! #[derive(Component)]
+//! struct Fresh;
+//!
+//! let mut app = App::new();
+//! app.add_plugins(MinimalPlugins)
+//!     .check::<Apple, Without<Fresh>>(invalid());
+//!
+//! let fresh = app.world_mut().spawn((Apple, Fresh)).id();
+//! let rotten = app.world_mut().spawn(Apple).id();
+//! app.run_checks_once();
+//!
+//! assert_valid!(app.world(), fresh);
+//! assert_invalid!(app.world(), rotten, "Without<Fresh>");
+//! ```
+
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+
+use crate::{check_schedule, CheckReport, Checked, Invalid};
+
+/// An extension trait used to run all checks of an [`App`] immediately.
+pub trait RunChecks {
+    /// Runs all checks once, and applies their policies.
+    ///
+    /// Note that this runs the entire check schedule (by default, [`PreUpdate`]), which contains all check systems.
+    fn run_checks_once(&mut self) -> &mut Self;
+}
+
+impl RunChecks for App {
+    fn run_checks_once(&mut self) -> &mut Self {
+        let schedule = check_schedule(self.main());
+        self.world_mut().run_schedule(schedule);
+        self
+    }
+}
+
+/// Returns true if the given `entity` has been checked and is valid.
+pub fn is_valid(world: &World, entity: Entity) -> bool {
+    world
+        .get_entity(entity)
+        .is_ok_and(|entity| entity.contains::<Checked>() && !entity.contains::<Invalid>())
+}
+
+/// Returns the reason the given `entity` is invalid, or `None` if it is not invalid.
+pub fn invalid_reason(world: &World, entity: Entity) -> Option<&str> {
+    world
+        .get::<Invalid>(entity)
+        .map(|invalid| invalid.reason.as_str())
+}
+
+/// Panics if the given `entity` is not checked and valid. See [`assert_valid!`](crate::assert_valid).
+#[track_caller]
+pub fn assert_valid(world: &World, entity: Entity) {
+    if let Some(reason) = invalid_reason(world, entity) {
+        panic!("{entity} is expected to be valid, but it is invalid: {reason}");
+    }
+    assert!(
+        is_valid(world, entity),
+        "{entity} is expected to be valid, but it is not checked"
+    );
+}
+
+/// Panics if the given `entity` is not invalid, or if the reason it is invalid does not contain `reason`.
+/// See [`assert_invalid!`](crate::assert_invalid).
+#[track_caller]
+pub fn assert_invalid(world: &World, entity: Entity, reason: &str) {
+    match invalid_reason(world, entity) {
+        Some(actual) => assert!(
+            actual.contains(reason),
+            "{entity} is expected to be invalid with reason {reason:?}, but it is invalid: {actual}"
+        ),
+        None => panic!("{entity} is expected to be invalid, but it is not"),
+    }
+}
+
+/// Asserts that an entity has been checked and is valid.
+///
+/// See [module documentation](crate::test) for an example.
+#[macro_export]
+macro_rules! assert_valid {
+    ($world:expr, $entity:expr $(,)?) => {
+        $crate::test::assert_valid($world, $entity)
+    };
+}
+
+/// Asserts that an entity is invalid, and that the reason it is invalid contains the given string.
+///
+/// See [module documentation](crate::test) for an example.
+#[macro_export]
+macro_rules! assert_invalid {
+    ($world:expr, $entity:expr, $reason:expr $(,)?) => {
+        $crate::test::assert_invalid($world, $entity, $reason)
+    };
+}
+
+/// A harness which spawns random combinations of components and verifies that all checks converge.
+///
+/// # Usage
+///
+/// Each round, the fuzzer spawns a batch of entities, each with a random subset of the given components.
+/// It then runs the checks until a pass in which no instance is checked.
+///
+/// The fuzzer fails if any check panics, or if the checks do not converge within a given number of passes.
+/// This is useful to detect contradictory checks and repairs, such as a repair which never fixes the instance,
+/// or two repairs which undo each other.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+/// use moonshine_check::test::Fuzzer;
+///
+/// #[derive(Component, Clone, Default)]
+/// struct Apple;
+///
+/// #[derive(Component, Clone, Default)]
+/// struct Fresh;
+///
+/// let mut app = App::new();
+/// app.add_plugins(MinimalPlugins)
+///     .check::<Apple, Without<Fresh>>(repair_insert_default::<Fresh>());
+///
+/// Fuzzer::new(42)
+///     .with(Apple)
+///     .with(Fresh)
+///     .run(&mut app)
+///     .unwrap();
+/// ```
+pub struct Fuzzer {
+    seed: u64,
+    rounds: usize,
+    batch_size: usize,
+    max_passes: usize,
+    constructors: Vec<(String, Constructor)>,
+}
+
+type Constructor = Box<dyn Fn(&mut EntityWorldMut) + Send + Sync>;
+
+impl Fuzzer {
+    /// Creates a new fuzzer with the given random seed.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rounds: 16,
+            batch_size: 64,
+            max_passes: 8,
+            constructors: Vec::new(),
+        }
+    }
+
+    /// Adds a bundle which may be inserted into spawned entities.
+    pub fn with<B: Bundle + Clone>(self, bundle: B) -> Self {
+        self.with_fn(move || bundle.clone())
+    }
+
+    /// Adds a bundle constructor which may be used to insert a bundle into spawned entities.
+    pub fn with_fn<B: Bundle>(mut self, f: impl Fn() -> B + Send + Sync + 'static) -> Self {
+        let name = moonshine_util::get_short_name(std::any::type_name::<B>());
+        self.constructors.push((
+            name,
+            Box::new(move |entity: &mut EntityWorldMut| {
+                entity.insert(f());
+            }),
+        ));
+        self
+    }
+
+    /// Sets the number of rounds. Default is 16.
+    pub fn rounds(mut self, rounds: usize) -> Self {
+        self.rounds = rounds;
+        self
+    }
+
+    /// Sets the number of entities spawned each round. Default is 64.
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Sets the maximum number of passes each round may take to converge. Default is 8.
+    pub fn max_passes(mut self, max_passes: usize) -> Self {
+        self.max_passes = max_passes;
+        self
+    }
+
+    /// Runs the fuzzer on the given [`App`].
+    pub fn run(&self, app: &mut App) -> Result<(), FuzzError> {
+        let mut rng = XorShift(self.seed.max(1));
+        let schedule = check_schedule(app.main());
+        for round in 0..self.rounds {
+            let mut spawned = Vec::with_capacity(self.batch_size);
+            for _ in 0..self.batch_size {
+                let mut entity = app.world_mut().spawn_empty();
+                let mut components = Vec::new();
+                for (name, constructor) in &self.constructors {
+                    if rng.next_u64() & 1 == 1 {
+                        constructor(&mut entity);
+                        components.push(name.clone());
+                    }
+                }
+                spawned.push((entity.id(), components));
+            }
+
+            let mut converged = false;
+            for _ in 0..self.max_passes {
+                let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                    app.world_mut().run_schedule(schedule);
+                }));
+                if let Err(payload) = result {
+                    let message = payload
+                        .downcast_ref::<&str>()
+                        .map(|message| message.to_string())
+                        .or_else(|| payload.downcast_ref::<String>().cloned())
+                        .unwrap_or_default();
+                    return Err(FuzzError::Panic { round, message });
+                }
+
+                let checked = app
+                    .world()
+                    .get_resource::<CheckReport>()
+                    .map(|report| report.total().checked)
+                    .unwrap_or_default();
+                if checked == 0 {
+                    converged = true;
+                    break;
+                }
+            }
+
+            if !converged {
+                let entities = spawned
+                    .into_iter()
+                    .filter(|(entity, _)| app.world().get_entity(*entity).is_ok())
+                    .collect();
+                return Err(FuzzError::Diverged { round, entities });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An error returned by [`Fuzzer::run`].
+#[derive(Debug)]
+pub enum FuzzError {
+    /// A check panicked.
+    Panic {
+        /// Index of the round in which the panic occurred.
+        round: usize,
+        /// The panic message.
+        message: String,
+    },
+    /// The checks did not converge within the maximum number of passes.
+    Diverged {
+        /// Index of the round which did not converge.
+        round: usize,
+        /// All entities spawned during the round which still exist, with the names of their spawned components.
+        entities: Vec<(Entity, Vec<String>)>,
+    },
+}
+
+impl std::fmt::Display for FuzzError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FuzzError::Panic { round, message } => {
+                write!(f, "check panicked in round {round}: {message}")
+            }
+            FuzzError::Diverged { round, entities } => {
+                write!(f, "checks did not converge in round {round}: {entities:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for FuzzError {}
+
+/// A minimal pseudo-random number generator, to keep fuzzing deterministic without any dependencies.
+struct XorShift(u64);
+
+impl XorShift {
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+}
@@ -0,0 +1,101 @@
+//! Detection of mass check failures.
+
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_utils::tracing::error;
+
+use crate::CheckReport;
+
+/// A [`Resource`] which limits the number of entities which may fail checks before the application reacts.
+///
+/// # Usage
+///
+/// When this resource exists, the total number of invalid and purged instances is compared against the limit
+/// after each check pass. If the limit is exceeded, a [`ValidationOverload`] event is sent, and optionally,
+/// the application exits with an error.
+///
+/// This is useful to abort a load if the saved data is so corrupted that the application can't reasonably recover.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_check::prelude::*;
+///
+/// let mut app = App::new();
+/// app.insert_resource(ValidationThreshold::per_frame(1000).exit_on_overload());
+/// ```
+#[derive(Resource, Clone, Debug)]
+pub struct ValidationThreshold {
+    pub(crate) limit: usize,
+    per_frame: bool,
+    exit: bool,
+}
+
+impl ValidationThreshold {
+    /// Creates a new threshold for the number of failed instances during a single check pass.
+    pub fn per_frame(limit: usize) -> Self {
+        Self {
+            limit,
+            per_frame: true,
+            exit: false,
+        }
+    }
+
+    /// Creates a new threshold for the total number of failed instances since the application started.
+    pub fn total(limit: usize) -> Self {
+        Self {
+            limit,
+            per_frame: false,
+            exit: false,
+        }
+    }
+
+    /// Requests [`AppExit`] when this threshold is exceeded, in addition to sending a [`ValidationOverload`] event.
+    pub fn exit_on_overload(mut self) -> Self {
+        self.exit = true;
+        self
+    }
+
+    /// Returns the maximum number of failed instances.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+}
+
+/// An [`Event`] sent when a [`ValidationThreshold`] is exceeded.
+#[derive(Event, Clone, Debug)]
+pub struct ValidationOverload {
+    /// Number of failed instances, either during the last check pass or in total.
+    pub count: usize,
+    /// The exceeded limit.
+    pub limit: usize,
+}
+
+pub(crate) fn check_threshold(
+    threshold: Res<ValidationThreshold>,
+    report: Res<CheckReport>,
+    mut total: Local<usize>,
+    mut overloads: EventWriter<ValidationOverload>,
+    mut exit: EventWriter<AppExit>,
+) {
+    let stats = report.total();
+    let failed = stats.invalid + stats.purged;
+    let limit = threshold.limit;
+    let (count, exceeded) = if threshold.per_frame {
+        (failed, failed > limit)
+    } else {
+        // NOTE: Total threshold is only exceeded once.
+        let previous = *total;
+        *total += failed;
+        (*total, previous <= limit && *total > limit)
+    };
+    if !exceeded {
+        return;
+    }
+
+    error!("validation overload: {count} instances failed checks (limit: {limit})");
+    overloads.send(ValidationOverload { count, limit });
+    if threshold.exit {
+        exit.send(AppExit::error());
+    }
+}
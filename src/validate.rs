@@ -0,0 +1,514 @@
+//! Immediate validation of entities, outside of the check schedule.
+
+use std::any::TypeId;
+
+use bevy_ecs::{prelude::*, world::CommandQueue};
+use bevy_hierarchy::DespawnRecursiveExt;
+use bevy_utils::tracing::Level;
+use moonshine_kind::prelude::*;
+
+use crate::{
+    add_invalid_reason, describe, log_action, mark_checked, panic_invalid, planned_outcome, record,
+    write_dump, CheckEntry, CheckFailure, CheckLedger, CheckRegistry, CheckReport, CheckStats,
+    Checked, DryRun, Invalid, OnCheckFailure, Outcome, PendingPurge, Policy, RepairFailed,
+    Severity, SkipChecks, StrictChecks,
+};
+#[cfg(feature = "graveyard")]
+use crate::{bury, Graveyard};
+
+/// A [`SystemSet`] which checks all unchecked entities during [`Update`](bevy_app::prelude::Update).
+///
+/// See [`CheckPlugin::flush_checks`](crate::CheckPlugin::flush_checks) for details.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, SystemSet)]
+pub struct CheckFlush;
+
+/// Checks all unchecked entities and applies their policies immediately.
+pub(crate) fn flush_checks(world: &mut World) {
+    world.apply_checks();
+}
+
+/// An error which describes a failed [`Check`](crate::Check).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CheckError {
+    /// The entity which failed the check.
+    pub entity: Entity,
+    /// Name of the failed check.
+    pub check: String,
+}
+
+impl std::fmt::Display for CheckError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} is invalid: {}", self.entity, self.check)
+    }
+}
+
+impl std::error::Error for CheckError {}
+
+/// An extension trait used to validate an [`Entity`] immediately.
+pub trait ValidateNow {
+    /// Evaluates all registered checks for the given `entity` against the current state of the world.
+    ///
+    /// # Usage
+    ///
+    /// Unlike scheduled checks, this does not apply any [`Policy`] or mark the entity as checked.
+    /// It is useful in editors and tests where the validity of an entity is needed immediately.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Apple;
+    ///
+    /// #[derive(Component)]
+    /// struct Fresh;
+    ///
+    /// let mut app = App::new();
+    /// app.check::<Apple, Without<Fresh>>(purge());
+    ///
+    /// let entity = app.world_mut().spawn(Apple).id();
+    /// assert!(app.world_mut().validate_now(entity).is_err());
+    /// ```
+    fn validate_now(&mut self, entity: Entity) -> Result<(), Vec<CheckError>>;
+
+    /// Evaluates all registered checks of kind `T` against the given bundle, before it is spawned.
+    ///
+    /// # Usage
+    ///
+    /// The bundle is evaluated in isolation, so only checks which can be expressed against the components
+    /// of the bundle are meaningful. Any check conditions which query other entities or resources
+    /// are evaluated against an empty world.
+    ///
+    /// Since the bundle is never spawned, the entity of any returned [`CheckError`] is [`Entity::PLACEHOLDER`].
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component, Clone)]
+    /// struct Apple;
+    ///
+    /// #[derive(Component, Clone)]
+    /// struct Fresh;
+    ///
+    /// let mut app = App::new();
+    /// app.check::<Apple, Without<Fresh>>(purge());
+    ///
+    /// assert!(app.world().validate_bundle::<Apple, _>(&Apple).is_err());
+    /// assert!(app.world().validate_bundle::<Apple, _>(&(Apple, Fresh)).is_ok());
+    /// ```
+    fn validate_bundle<T: Kind, B: Bundle + Clone>(
+        &self,
+        bundle: &B,
+    ) -> Result<(), Vec<CheckError>>;
+}
+
+impl ValidateNow for World {
+    fn validate_now(&mut self, entity: Entity) -> Result<(), Vec<CheckError>> {
+        if !self.contains_resource::<CheckRegistry>() {
+            return Ok(());
+        }
+
+        let errors = self.resource_scope(|world, registry: Mut<CheckRegistry>| {
+            registry
+                .checks
+                .iter()
+                .filter(|check| check.enabled && (check.evaluate)(world, entity) == Some(false))
+                .map(|check| CheckError {
+                    entity,
+                    check: check.name.clone(),
+                })
+                .collect::<Vec<_>>()
+        });
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn validate_bundle<T: Kind, B: Bundle + Clone>(
+        &self,
+        bundle: &B,
+    ) -> Result<(), Vec<CheckError>> {
+        let Some(registry) = self.get_resource::<CheckRegistry>() else {
+            return Ok(());
+        };
+
+        let mut world = World::new();
+        let entity = world.spawn(bundle.clone()).id();
+        let errors: Vec<CheckError> = registry
+            .checks
+            .iter()
+            .filter(|check| check.enabled && check.kind == TypeId::of::<T>())
+            .filter(|check| (check.evaluate)(&mut world, entity) == Some(false))
+            .map(|check| CheckError {
+                entity: Entity::PLACEHOLDER,
+                check: check.name.clone(),
+            })
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// An extension trait used to spawn an [`Entity`] which is checked immediately.
+pub trait SpawnChecked {
+    /// Spawns a new entity with the given bundle, and immediately applies the policies of all registered checks.
+    ///
+    /// # Usage
+    ///
+    /// By default, new entities are checked during the next check pass. Until then, other systems may observe
+    /// an unchecked invalid entity. With this method, the entity is checked as soon as it is spawned
+    /// (or when the commands are applied), so it is never observed in an unchecked state.
+    ///
+    /// Note that the returned entity may no longer exist if it was purged.
+    ///
+    /// Checks which are statically dispatched are still evaluated in the next check pass.
+    /// Checks applied immediately are not included in the [`CheckReport`], and do not notify any [`SnapshotHook`](crate::SnapshotHook)s.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use moonshine_check::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Apple;
+    ///
+    /// #[derive(Component)]
+    /// struct Fresh;
+    ///
+    /// let mut app = App::new();
+    /// app.check::<Apple, Without<Fresh>>(purge());
+    ///
+    /// let entity = app.world_mut().spawn_checked(Apple);
+    /// assert!(app.world().get_entity(entity).is_err());
+    /// ```
+    fn spawn_checked(&mut self, bundle: impl Bundle) -> Entity;
+}
+
+impl SpawnChecked for World {
+    fn spawn_checked(&mut self, bundle: impl Bundle) -> Entity {
+        let entity = self.spawn(bundle).id();
+        check_now(self, entity);
+        entity
+    }
+}
+
+impl SpawnChecked for Commands<'_, '_> {
+    fn spawn_checked(&mut self, bundle: impl Bundle) -> Entity {
+        let entity = self.spawn(bundle).id();
+        self.queue(move |world: &mut World| check_now(world, entity));
+        entity
+    }
+}
+
+/// Evaluates all registered checks for the given entity, and applies their policies immediately.
+pub(crate) fn check_now(world: &mut World, entity: Entity) {
+    if !world.contains_resource::<CheckRegistry>()
+        || world.get_entity(entity).map_or(true, |entity| {
+            entity.contains::<Checked>() || entity.contains::<SkipChecks>()
+        })
+        || world
+            .get_resource::<CheckLedger>()
+            .is_some_and(|ledger| ledger.is_checked(entity))
+    {
+        return;
+    }
+
+    let ledger = world.contains_resource::<CheckLedger>();
+
+    let mut evaluated = false;
+    let mut deferred = false;
+    let failed: Vec<(CheckEntry, Policy)> =
+        world.resource_scope(|world, registry: Mut<CheckRegistry>| {
+            let mut failed = Vec::new();
+            for check in registry.iter() {
+                if !check.enabled
+                    || (check.skip)(world, entity)
+                    || check.gate.is_some_and(|gate| !gate.applies(world))
+                {
+                    continue;
+                }
+                let Some(passed) = (check.evaluate)(world, entity) else {
+                    continue;
+                };
+                evaluated = true;
+                let Some(policy) = &check.action else {
+                    deferred = true;
+                    continue;
+                };
+                if !passed {
+                    failed.push((check.clone(), policy.clone()));
+                }
+            }
+            failed
+        });
+
+    let dry_run = world.contains_resource::<DryRun>();
+    let mut applied = false;
+    for (check, policy) in failed {
+        if world.get_entity(entity).is_err() {
+            return;
+        }
+        let reason = describe(
+            check.formatter.as_ref(),
+            world,
+            world.entity(entity),
+            &check.name,
+            &check.description,
+        );
+        let kind = moonshine_util::get_short_name(&check.kind_name);
+        let strict = !check.lenient && world.get_resource::<StrictChecks>().is_some_and(|s| s.0);
+        let exclusive = matches!(
+            planned_outcome(&policy),
+            Some(Outcome::Invalid | Outcome::Purged)
+        );
+        let warning = check.severity == Severity::Warning;
+        if let Some(on_failure) = world.get_resource::<OnCheckFailure>() {
+            (on_failure.0)(&CheckFailure {
+                entity,
+                kind: kind.clone(),
+                check: check.name.clone(),
+                reason: reason.clone(),
+                severity: check.severity,
+                outcome: if warning {
+                    Some(Outcome::Valid)
+                } else if strict && exclusive {
+                    None
+                } else {
+                    planned_outcome(&policy)
+                },
+            });
+        }
+        if warning {
+            // NOTE: Instances which fail a warning are still valid.
+            log!(
+                check.log_level.map(|_| Level::WARN),
+                "{entity} is suspicious: {reason}"
+            );
+            record_now(world, &check, |stats| {
+                stats.warned += 1;
+                stats.record(Outcome::Valid);
+            });
+            continue;
+        }
+        if dry_run {
+            match planned_outcome(&policy) {
+                Some(outcome) => {
+                    log!(check.log_level, "{entity} would be {outcome:?}: {reason}");
+                    record_now(world, &check, |stats| stats.record(outcome));
+                }
+                None => {
+                    log!(check.log_level, "{entity} would panic: {reason}");
+                    record_now(world, &check, |stats| stats.record(Outcome::Invalid));
+                }
+            }
+            continue;
+        }
+        applied = true;
+        apply_now(world, entity, &check, &kind, &reason, strict, &policy);
+    }
+
+    // NOTE: In marker-free mode, statically dispatched checks never run, so they do not defer the entity.
+    // In dry-run mode, entities are never marked as checked.
+    if !applied && !dry_run && evaluated && (ledger || !deferred) {
+        mark_checked(world, entity);
+    }
+}
+
+/// Updates the [`CheckReport`] statistics of the given check, if it is reported.
+fn record_now(world: &mut World, check: &CheckEntry, f: impl FnOnce(&mut CheckStats)) {
+    let Some(mut report) = world.get_resource_mut::<CheckReport>() else {
+        return;
+    };
+    // NOTE: Registries may be shared between worlds, so find the check by name.
+    if let Some((_, stats)) = report
+        .checks
+        .iter_mut()
+        .find(|(name, _)| *name == check.name)
+    {
+        f(stats);
+    }
+}
+
+/// Applies the given policy of a failed check to the given entity immediately.
+///
+/// This is the immediate equivalent of the policies applied by check systems.
+fn apply_now(
+    world: &mut World,
+    entity: Entity,
+    check: &CheckEntry,
+    kind: &str,
+    reason: &str,
+    strict: bool,
+    policy: &Policy,
+) {
+    let name = check.name.as_str();
+    let log_level = check.log_level;
+    let policy = if strict
+        && matches!(
+            policy,
+            Policy::Invalid | Policy::Purge | Policy::PurgeAfter(_)
+        ) {
+        &Policy::Panic
+    } else {
+        policy
+    };
+    let ledger = world.contains_resource::<CheckLedger>();
+    let mut queue = CommandQueue::default();
+    let mut repair_failed = false;
+    let (outcome, next) = {
+        let mut commands = Commands::new(&mut queue, world);
+        let entity_ref = world.entity(entity);
+        match policy {
+            Policy::Invalid => {
+                if ledger {
+                    let name = name.to_owned();
+                    commands.queue(move |world: &mut World| {
+                        let mut ledger = world.resource_mut::<CheckLedger>();
+                        ledger.checked.insert(entity);
+                        ledger.invalid.insert(entity, name);
+                    });
+                } else {
+                    commands
+                        .entity(entity)
+                        .try_insert((Checked, Invalid::new(name, world)));
+                    add_invalid_reason(entity, name, &check.description, world, &mut commands);
+                }
+                let message = format!("{entity} is invalid: {reason}");
+                log_action(
+                    world,
+                    entity,
+                    name,
+                    Outcome::Invalid,
+                    message,
+                    &mut commands,
+                );
+                log!(log_level, "{entity} is invalid: {reason}");
+                (Some(Outcome::Invalid), None)
+            }
+            Policy::PurgeAfter(delay) if !ledger => {
+                commands.entity(entity).try_insert((
+                    Checked,
+                    Invalid::new(name, world),
+                    PendingPurge::new(kind, name, *delay, world),
+                ));
+                add_invalid_reason(entity, name, &check.description, world, &mut commands);
+                let message =
+                    format!("{entity} is invalid and will be purged after {delay:?}: {reason}");
+                log_action(
+                    world,
+                    entity,
+                    name,
+                    Outcome::Invalid,
+                    message,
+                    &mut commands,
+                );
+                log!(
+                    log_level,
+                    "{entity} is invalid and will be purged after {delay:?}: {reason}"
+                );
+                (Some(Outcome::Invalid), None)
+            }
+            // NOTE: Without any markers, pending purges can't be tracked, so instances are purged immediately.
+            Policy::Purge | Policy::PurgeAfter(_) => {
+                #[cfg(feature = "graveyard")]
+                if world.contains_resource::<Graveyard>() {
+                    let check = name.to_owned();
+                    commands.queue(move |world: &mut World| bury(world, entity, check));
+                }
+                record(
+                    world,
+                    entity_ref,
+                    kind,
+                    name,
+                    Outcome::Purged,
+                    &mut commands,
+                );
+                let message = format!("{entity} is purged: {reason}");
+                log_action(world, entity, name, Outcome::Purged, message, &mut commands);
+                log!(log_level, "{entity} is purged: {reason}");
+                commands.entity(entity).despawn_recursive();
+                (Some(Outcome::Purged), None)
+            }
+            Policy::Panic => panic_invalid(entity_ref, world, reason),
+            Policy::Repair(fixer) => {
+                commands.queue(move |world: &mut World| mark_checked(world, entity));
+                log!(log_level, "{entity} is invalid: {reason}");
+                match fixer.try_fix(entity_ref, &mut commands) {
+                    Ok(()) => {
+                        record(
+                            world,
+                            entity_ref,
+                            kind,
+                            name,
+                            Outcome::Repaired,
+                            &mut commands,
+                        );
+                        let message = format!("{entity} was repaired: {reason}");
+                        log_action(
+                            world,
+                            entity,
+                            name,
+                            Outcome::Repaired,
+                            message,
+                            &mut commands,
+                        );
+                        let repair_log_level = log_level.map(|level| {
+                            if level == Level::ERROR {
+                                Level::WARN
+                            } else {
+                                level
+                            }
+                        });
+                        log!(repair_log_level, "{entity} was repaired.");
+                        (Some(Outcome::Repaired), None)
+                    }
+                    Err(error) => {
+                        log!(log_level, "{entity} could not be repaired: {error}");
+                        commands.send_event(RepairFailed {
+                            entity,
+                            check: name.to_owned(),
+                            error,
+                        });
+                        repair_failed = true;
+                        (
+                            None,
+                            Some(fixer.fallback().cloned().unwrap_or(Policy::Invalid)),
+                        )
+                    }
+                }
+            }
+            Policy::Dump {
+                path,
+                recursive,
+                then,
+            } => {
+                let (path, recursive) = (path.clone(), *recursive);
+                commands
+                    .queue(move |world: &mut World| write_dump(world, entity, &path, recursive));
+                (None, Some(then.as_ref().clone()))
+            }
+        }
+    };
+    queue.apply(world);
+    if repair_failed {
+        record_now(world, check, |stats| stats.repair_failed += 1);
+    }
+    if let Some(outcome) = outcome {
+        record_now(world, check, |stats| stats.record(outcome));
+    }
+    if let Some(next) = next {
+        if world.get_entity(entity).is_ok() {
+            apply_now(world, entity, check, kind, reason, strict, &next);
+        }
+    }
+}